@@ -0,0 +1,155 @@
+use std::path::PathBuf;
+use words_game::coordinates::parse_play_notation;
+use words_game::language::{Language, TileSet};
+use words_game::{Direction, Point, Tile};
+
+/**
+ * Something typed at the prompt
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// `play WORD x,y right|down`, or `play WORD 8H` in Scrabble notation
+    Play {
+        start: Point,
+        dir: Direction,
+        word: String,
+    },
+    Pass,
+    /// `exchange TILES`, e.g. `exchange QVV`
+    Exchange(Vec<Tile>),
+    Undo,
+    Hint,
+    Save(PathBuf),
+    Load(PathBuf),
+    Board,
+    Help,
+    Quit,
+}
+
+fn parse_point(text: &str) -> Result<Point, String> {
+    let mut coordinates = text.split(',').map(|num| num.trim().parse::<i32>());
+
+    match (coordinates.next(), coordinates.next(), coordinates.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Ok(Point::new(x, y)),
+        _ => Point::from_notation(text)
+            .map_err(|_| format!("{} is not a coordinate, use x,y like 7,7 or a square like H8", text)),
+    }
+}
+
+fn parse_direction(text: &str) -> Result<Direction, String> {
+    match text.to_lowercase().as_ref() {
+        "right" | "r" => Ok(Direction::right()),
+        "down" | "d" => Ok(Direction::down()),
+        _ => Err(format!("{} is not a direction, use right or down", text)),
+    }
+}
+
+fn parse_word(text: &str, tile_set: &TileSet) -> Result<String, String> {
+    tile_set
+        .normalize_word(text)
+        .map_err(|err| format!("{} can only have letters: {}", text, err))
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Result<Command, String> {
+        Command::parse_in(line, Language::English)
+    }
+
+    /**
+     * Parses a command for a game in `language`, words and tiles have to
+     * use its letters
+     */
+    pub fn parse_in(line: &str, language: Language) -> Result<Command, String> {
+        let tile_set = language.tile_set();
+        let args: Vec<&str> = line.split_whitespace().collect();
+
+        match args.as_slice() {
+            ["play", word, start, dir] | ["p", word, start, dir] => Ok(Command::Play {
+                start: parse_point(start)?,
+                dir: parse_direction(dir)?,
+                word: parse_word(word, tile_set)?,
+            }),
+            ["play", word, start] | ["p", word, start] => {
+                let (start, dir) = parse_play_notation(start).map_err(|err| err.to_string())?;
+                Ok(Command::Play {
+                    start,
+                    dir,
+                    word: parse_word(word, tile_set)?,
+                })
+            }
+            ["pass"] => Ok(Command::Pass),
+            ["exchange", tiles] | ["x", tiles] => {
+                Ok(Command::Exchange(parse_word(tiles, tile_set)?.chars().map(Tile::Letter).collect()))
+            }
+            ["undo"] | ["u"] => Ok(Command::Undo),
+            ["hint"] | ["h"] => Ok(Command::Hint),
+            ["save", path] => Ok(Command::Save(PathBuf::from(path))),
+            ["load", path] => Ok(Command::Load(PathBuf::from(path))),
+            ["board"] | ["b"] => Ok(Command::Board),
+            ["help"] | ["?"] => Ok(Command::Help),
+            ["quit"] | ["q"] => Ok(Command::Quit),
+            _ => Err(format!("Command not recognized: {}, type help for the commands", line.trim())),
+        }
+    }
+}
+
+pub const HELP: &str = "Commands:
+  play WORD x,y right|down   Play WORD starting at column x and row y (p for short)
+  play WORD 8H | H8          The same in Scrabble notation, row first to go right
+  pass                       Give up your turn
+  exchange TILES             Swap TILES from your hand for new ones (x for short)
+  undo                       Take back your last move and any bot moves after it
+  hint                       Show the best play for your hand
+  board                      Show the board again
+  save FILE / load FILE      Keep the game in FILE to play later
+  quit                       Leave the game";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_moves() {
+        assert_eq!(
+            Command::parse("play actor 7,7 right"),
+            Ok(Command::Play {
+                start: Point::new(7, 7),
+                dir: Direction::right(),
+                word: "ACTOR".to_string(),
+            })
+        );
+        assert_eq!(
+            Command::parse("  x qv "),
+            Ok(Command::Exchange(vec![Tile::Letter('Q'), Tile::Letter('V')]))
+        );
+        assert_eq!(Command::parse("save game.json"), Ok(Command::Save(PathBuf::from("game.json"))));
+        assert_eq!(Command::parse("u"), Ok(Command::Undo));
+    }
+
+    #[test]
+    fn parses_scrabble_notation() {
+        let play = |start, dir| Command::Play { start, dir, word: "ACTOR".to_string() };
+
+        assert_eq!(Command::parse("p actor 8H"), Ok(play(Point::new(7, 7), Direction::right())));
+        assert_eq!(Command::parse("p actor h8"), Ok(play(Point::new(7, 7), Direction::down())));
+        assert_eq!(Command::parse("p actor A4 right"), Ok(play(Point::new(0, 3), Direction::right())));
+        assert!(Command::parse("p actor 8").is_err());
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert!(Command::parse("play actor 7 right").is_err());
+        assert!(Command::parse("play actor 7,7 left").is_err());
+        assert!(Command::parse("exchange a1").is_err());
+        assert!(Command::parse("dance").is_err());
+    }
+
+    #[test]
+    fn parses_words_in_the_game_language() {
+        assert!(Command::parse("play łódź 7,7 right").is_err());
+        assert_eq!(
+            Command::parse_in("x ñ", Language::Spanish),
+            Ok(Command::Exchange(vec![Tile::Letter('Ñ')]))
+        );
+    }
+}
@@ -0,0 +1,10 @@
+/*!
+ * Plays words-game in a terminal, against other people at the same keyboard
+ * or against bots. See `session::Session` for how commands are carried out.
+ */
+
+pub mod commands;
+pub mod session;
+
+pub use commands::Command;
+pub use session::{Outcome, Session};
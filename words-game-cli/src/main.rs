@@ -0,0 +1,140 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use words_game::language::{self, Language};
+use words_game::leave::LeaveTable;
+use words_game::render::{AnsiRenderer, BoardRenderer, UnicodeRenderer};
+use words_game::{Game, PlayerKind};
+use words_game_cli::{Command, Outcome, Session};
+
+const USAGE: &str = "Usage: words-game-cli [--load FILE] [--leaves FILE] [--language LANG]
+                     [--dictionary FILE] [--no-color] [PLAYER...]
+
+Each PLAYER is a name, or bot or bot:NAME for a computer player.
+Without any players you play against a bot.
+
+LANG is en (the default), es, fr, de or pl. Other languages than English
+need a word list passed with --dictionary, one word per line, and bots
+only play English.";
+
+struct Options {
+    seats: Vec<(String, PlayerKind)>,
+    load: Option<String>,
+    leaves: Option<String>,
+    language: Language,
+    dictionary: Option<String>,
+    color: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        seats: vec![],
+        load: None,
+        leaves: None,
+        language: Language::English,
+        dictionary: None,
+        color: true,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "--load" => options.load = Some(args.next().ok_or("--load needs a file")?.clone()),
+            "--leaves" => options.leaves = Some(args.next().ok_or("--leaves needs a file")?.clone()),
+            "--language" => {
+                let language = args.next().ok_or("--language needs a language")?;
+                options.language = language.parse::<Language>().map_err(|err| err.to_string())?;
+            }
+            "--dictionary" => {
+                options.dictionary = Some(args.next().ok_or("--dictionary needs a file")?.clone())
+            }
+            "--no-color" => options.color = false,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            "bot" => options.seats.push((format!("Bot {}", options.seats.len() + 1), PlayerKind::Bot)),
+            arg => match arg.strip_prefix("bot:") {
+                Some(name) => options.seats.push((name.to_string(), PlayerKind::Bot)),
+                None => options.seats.push((arg.to_string(), PlayerKind::Human)),
+            },
+        }
+    }
+
+    if options.seats.is_empty() {
+        options.seats = vec![("You".to_string(), PlayerKind::Human), ("Bot".to_string(), PlayerKind::Bot)];
+    }
+
+    Ok(options)
+}
+
+fn new_game(options: &Options) -> Result<Game, String> {
+    if let Some(ref path) = options.load {
+        let contents = fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path, err))?;
+        return Game::from_serialized(&contents).map_err(|err| format!("{} is not a saved game: {}", path, err));
+    }
+
+    let seats: Vec<(&str, &str, PlayerKind)> = options
+        .seats
+        .iter()
+        .map(|(name, kind)| (&name[..], &name[..], *kind))
+        .collect();
+    Ok(Game::with_players_in(&seats, options.language))
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let leave_table = match options.leaves {
+        Some(ref path) => LeaveTable::from_file(path).map_err(|err| err.to_string())?,
+        None => LeaveTable::new(),
+    };
+    let renderer: Box<dyn BoardRenderer> = if options.color {
+        Box::new(AnsiRenderer)
+    } else {
+        Box::new(UnicodeRenderer)
+    };
+
+    let game = new_game(&options)?;
+    if let Some(ref path) = options.dictionary {
+        language::load_dictionary(game.language, path).map_err(|err| err.to_string())?;
+    }
+
+    let mut session = Session::new(game, leave_table);
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    println!("{}\n", words_game_cli::commands::HELP);
+    loop {
+        for message in session.play_bots() {
+            println!("{}", message);
+        }
+
+        println!("{}", session.render(renderer.as_ref()));
+        if session.is_over() {
+            println!("Game over!\n{}", session.final_standings());
+            return Ok(());
+        }
+
+        print!("{}> ", session.current_player_name());
+        io::stdout().flush().map_err(|err| err.to_string())?;
+
+        let line = match lines.next() {
+            Some(line) => line.map_err(|err| err.to_string())?,
+            None => return Ok(()),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match Command::parse_in(&line, session.game().language).and_then(|command| session.execute(command, renderer.as_ref())) {
+            Ok(Outcome::Message(message)) => println!("{}", message),
+            Ok(Outcome::Quit) => return Ok(()),
+            Err(err) => println!("{}", err),
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Err(err) = parse_args(&args).and_then(run) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
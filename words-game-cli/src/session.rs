@@ -0,0 +1,323 @@
+use super::commands::{Command, HELP};
+use std::fs;
+use words_game::coordinates::play_notation;
+use words_game::language::{spell, unit, Language};
+use words_game::leave::LeaveTable;
+use words_game::movegen::bot_move;
+use words_game::render::BoardRenderer;
+use words_game::{Game, Move, PlayerKind, Point, Tile};
+
+/**
+ * Everything undo needs to put back
+ */
+#[derive(Debug, Clone)]
+struct State {
+    game: Game,
+    /// The tiles placed by the last play, highlighted on the board
+    last_placed: Vec<Point>,
+}
+
+/**
+ * What happened after a command
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Message(String),
+    Quit,
+}
+
+/**
+ * A game being played at the terminal, with the moves made so far so they
+ * can be undone
+ */
+pub struct Session {
+    state: State,
+    history: Vec<State>,
+    leave_table: LeaveTable,
+}
+
+fn describe_move(mv: &Move, name: &str, score: u32) -> String {
+    match mv {
+        Move::Play { start, dir, word } => format!(
+            "{} played {} at {} for {} points",
+            name,
+            spell(word),
+            play_notation(*start, *dir),
+            score
+        ),
+        Move::Pass => format!("{} passed", name),
+        Move::Exchange(tiles) => format!("{} exchanged {} tiles", name, tiles.len()),
+    }
+}
+
+fn render_tiles(tiles: &[Tile], language: Language) -> String {
+    let tiles: Vec<String> = tiles
+        .iter()
+        .map(|tile| format!("{}{}", unit(char::from(*tile)), language.tile_set().point_value(*tile)))
+        .collect();
+    tiles.join(" ")
+}
+
+impl Session {
+    pub fn new(game: Game, leave_table: LeaveTable) -> Session {
+        Session {
+            state: State {
+                game,
+                last_placed: vec![],
+            },
+            history: vec![],
+            leave_table,
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.state.game
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.state.game.is_over()
+    }
+
+    /**
+     * Each player's score once the tiles left on the racks are counted
+     */
+    pub fn final_standings(&self) -> String {
+        let game = &self.state.game;
+        let lines: Vec<String> = game
+            .players
+            .iter()
+            .zip(game.final_scores())
+            .enumerate()
+            .map(|(idx, (player, score))| format!("{}: {} points", player.name(idx), score))
+            .collect();
+        lines.join("\n")
+    }
+
+    pub fn is_bot_turn(&self) -> bool {
+        let game = &self.state.game;
+        game.players[game.get_current_player_idx()].kind == PlayerKind::Bot
+    }
+
+    pub fn current_player_name(&self) -> String {
+        let idx = self.state.game.get_current_player_idx();
+        self.state.game.players[idx].name(idx)
+    }
+
+    /**
+     * The board with the last play highlighted, the scores and the hand of
+     * the player whose turn it is
+     */
+    pub fn render(&self, renderer: &dyn BoardRenderer) -> String {
+        let game = &self.state.game;
+        let current_player_idx = game.get_current_player_idx();
+
+        let mut lines = vec![renderer.render(&game.board, &self.state.last_placed), String::new()];
+        for (idx, player) in game.players.iter().enumerate() {
+            lines.push(format!(
+                "{} {}: {} points{}",
+                if idx == current_player_idx { ">" } else { " " },
+                player.name(idx),
+                player.score,
+                if player.resigned { " (resigned)" } else { "" }
+            ));
+        }
+        lines.push(format!("Tiles left in the bag: {}", game.tile_bag.tiles.len()));
+
+        if !self.is_bot_turn() {
+            let player = &game.players[current_player_idx];
+            lines.push(format!("Hand: {}", render_tiles(&player.hand, game.language)));
+        }
+
+        lines.join("\n")
+    }
+
+    fn make_move(&mut self, mv: &Move) -> Result<String, String> {
+        let name = self.current_player_name();
+        let before = self.state.clone();
+
+        let score = self.state.game.apply_move(mv).map_err(|err| err.to_string())?;
+        self.state.last_placed = before
+            .game
+            .diff(&self.state.game)
+            .placed_tiles()
+            .into_iter()
+            .map(|(point, _)| point)
+            .collect();
+        self.history.push(before);
+
+        Ok(describe_move(mv, &name, score))
+    }
+
+    /**
+     * Takes back moves until it is a person's turn again, so undoing a move
+     * also undoes the bots' replies to it
+     */
+    fn undo(&mut self) -> Result<String, String> {
+        let mut undone = 0;
+
+        while let Some(state) = self.history.pop() {
+            self.state = state;
+            undone += 1;
+
+            if !self.is_bot_turn() {
+                break;
+            }
+        }
+
+        match undone {
+            0 => Err("There is nothing to undo".to_string()),
+            1 => Ok("Took back 1 move".to_string()),
+            undone => Ok(format!("Took back {} moves", undone)),
+        }
+    }
+
+    /**
+     * The move the bot would make for the current player
+     */
+    fn suggested_move(&self) -> Move {
+        bot_move(&self.state.game, &self.leave_table)
+    }
+
+    /**
+     * Plays for every bot in a row whose turn it is, returning what each did
+     */
+    pub fn play_bots(&mut self) -> Vec<String> {
+        let mut messages = vec![];
+
+        while self.is_bot_turn() && !self.is_over() {
+            let mv = self.suggested_move();
+            match self.make_move(&mv) {
+                Ok(message) => messages.push(message),
+                Err(_) => messages.push(self.make_move(&Move::Pass).unwrap()),
+            }
+        }
+
+        messages
+    }
+
+    pub fn execute(&mut self, command: Command, renderer: &dyn BoardRenderer) -> Result<Outcome, String> {
+        let message = match command {
+            Command::Play { start, dir, word } => self.make_move(&Move::Play { start, dir, word })?,
+            Command::Pass => self.make_move(&Move::Pass)?,
+            Command::Exchange(tiles) => self.make_move(&Move::Exchange(tiles))?,
+            Command::Undo => self.undo()?,
+            Command::Hint => match self.suggested_move() {
+                Move::Play { start, dir, word } => {
+                    let mut game = self.state.game.clone();
+                    let score = game.play_word(start, dir, &word).map(|result| result.score).unwrap_or(0);
+                    format!("Try play {} {} for {} points", spell(&word), play_notation(start, dir), score)
+                }
+                _ => "There is no play for your hand, try exchanging".to_string(),
+            },
+            Command::Save(path) => {
+                fs::write(&path, self.state.game.serialize())
+                    .map_err(|err| format!("Could not save to {}: {}", path.display(), err))?;
+                format!("Saved the game to {}", path.display())
+            }
+            Command::Load(path) => {
+                let contents = fs::read_to_string(&path)
+                    .map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+                let game = Game::from_serialized(&contents)
+                    .map_err(|err| format!("{} is not a saved game: {}", path.display(), err))?;
+
+                *self = Session::new(game, self.leave_table.clone());
+                self.render(renderer)
+            }
+            Command::Board => self.render(renderer),
+            Command::Help => HELP.to_string(),
+            Command::Quit => return Ok(Outcome::Quit),
+        };
+
+        Ok(Outcome::Message(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use words_game::render::UnicodeRenderer;
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        s.chars().map(Tile::from).collect()
+    }
+
+    fn session(kinds: &[PlayerKind]) -> Session {
+        let seats: Vec<(&str, &str, PlayerKind)> = kinds.iter().map(|kind| ("", "", *kind)).collect();
+        let mut game = Game::with_players(&seats);
+        for player in game.players.iter_mut() {
+            player.display_name = None;
+        }
+        game.players[0].hand = tiles("ACTORSEOYK");
+
+        Session::new(game, LeaveTable::new())
+    }
+
+    fn run(session: &mut Session, line: &str) -> Result<Outcome, String> {
+        session.execute(Command::parse(line).unwrap(), &UnicodeRenderer)
+    }
+
+    #[test]
+    fn play_and_undo() {
+        let mut session = session(&[PlayerKind::Human, PlayerKind::Human]);
+
+        assert_eq!(
+            run(&mut session, "play actor 7,7 right"),
+            Ok(Outcome::Message("Player 1 played ACTOR at 8H for 8 points".to_string()))
+        );
+        assert!(session.render(&UnicodeRenderer).contains("[A]"));
+        assert!(run(&mut session, "play actor 7,7 down").is_err());
+
+        assert_eq!(run(&mut session, "undo"), Ok(Outcome::Message("Took back 1 move".to_string())));
+        assert_eq!(session.game().players[0].score, 0);
+        assert!(run(&mut session, "undo").is_err());
+        assert_eq!(run(&mut session, "quit"), Ok(Outcome::Quit));
+    }
+
+    #[test]
+    fn bots_reply_and_are_undone_together() {
+        let mut session = session(&[PlayerKind::Human, PlayerKind::Bot, PlayerKind::Bot]);
+
+        run(&mut session, "pass").unwrap();
+        assert_eq!(session.play_bots().len(), 2);
+        assert!(!session.is_bot_turn());
+
+        assert_eq!(run(&mut session, "undo"), Ok(Outcome::Message("Took back 3 moves".to_string())));
+        assert_eq!(session.game().turn, 0);
+    }
+
+    #[test]
+    fn hints_and_saves() {
+        let mut session = session(&[PlayerKind::Human, PlayerKind::Human]);
+
+        match run(&mut session, "hint") {
+            Ok(Outcome::Message(hint)) => assert!(hint.starts_with("Try play ")),
+            other => panic!("Expected a hint, got {:?}", other),
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("game.json");
+        run(&mut session, "pass").unwrap();
+        run(&mut session, &format!("save {}", path.display())).unwrap();
+
+        let mut loaded = Session::new(Game::new(2), LeaveTable::new());
+        run(&mut loaded, &format!("load {}", path.display())).unwrap();
+        assert_eq!(loaded.game().turn, 1);
+        assert!(run(&mut loaded, "undo").is_err());
+    }
+
+    #[test]
+    fn ends_after_everyone_passes_twice() {
+        let mut session = session(&[PlayerKind::Human, PlayerKind::Human]);
+
+        for _ in 0..3 {
+            run(&mut session, "pass").unwrap();
+        }
+        assert!(!session.is_over());
+        run(&mut session, "pass").unwrap();
+        assert!(session.is_over());
+        assert_eq!(session.final_standings().lines().count(), 2);
+
+        run(&mut session, "undo").unwrap();
+        assert!(!session.is_over());
+    }
+}
@@ -0,0 +1,39 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/**
+ * Plays a game through the binary, the way someone at a terminal would
+ */
+fn run_cli(args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_words-game-cli"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn plays_against_a_bot() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("game.json");
+
+    let output = run_cli(
+        &["--no-color", "Ann", "bot:Robo"],
+        &format!("dance\npass\nsave {}\nquit\n", path.display()),
+    );
+
+    assert!(output.contains("Command not recognized: dance"));
+    assert!(output.contains("Ann passed"));
+    assert!(output.contains("Robo "));
+    assert!(output.contains("> Ann: 0 points"));
+
+    let resumed = run_cli(&["--no-color", "--load", path.to_str().unwrap()], "board\n");
+    assert!(resumed.contains("Ann> "));
+}
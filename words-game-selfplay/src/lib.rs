@@ -0,0 +1,13 @@
+/*!
+ * Plays bots against each other to see how rule changes, like the house
+ * rule of 10 tile hands, change the game. `runner::play_games` plays a
+ * batch of games and `stats::Stats` totals them up.
+ */
+
+pub mod rules;
+pub mod runner;
+pub mod stats;
+
+pub use rules::Rules;
+pub use runner::{play_game, play_games, Config, GameSummary};
+pub use stats::Stats;
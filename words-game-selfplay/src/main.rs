@@ -0,0 +1,85 @@
+use std::env;
+use std::fs;
+use std::thread;
+use words_game::leave::LeaveTable;
+use words_game::Board;
+use words_game_selfplay::{play_games, Config, Rules, Stats};
+
+const USAGE: &str = "Usage: words-game-selfplay [OPTIONS]
+
+  --games N          Number of games to play (1000)
+  --players N        Bots in each game (2)
+  --seed N           Game n uses the bag seeded with N + n (0)
+  --hand-size N      Tiles in each hand (10)
+  --board FILE       Board layout in the same format as words_game::BOARD
+  --tiles SPEC       Tile distribution, e.g. \"E12 A9 I9 ... ?2\"
+  --leaves FILE      Leave table for the bots
+  --bingo-tiles N    Plays using at least N tiles are bingos (7)
+  --threads N        Threads to play on (all cores)
+  --json             Print the statistics as JSON";
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("{} needs a number", flag))
+}
+
+fn parse_args(args: &[String]) -> Result<(Config, bool), String> {
+    let mut config = Config {
+        threads: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        ..Config::default()
+    };
+    let mut json = false;
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        match flag.as_ref() {
+            "--games" => config.games = parse_number(flag, args.next())?,
+            "--players" => config.players = parse_number(flag, args.next())?,
+            "--seed" => config.seed = parse_number(flag, args.next())?,
+            "--hand-size" => config.rules.hand_size = parse_number(flag, args.next())?,
+            "--bingo-tiles" => config.bingo_tiles = parse_number(flag, args.next())?,
+            "--threads" => config.threads = parse_number(flag, args.next())?,
+            "--board" => {
+                let path = args.next().ok_or("--board needs a file")?;
+                let layout = fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path, err))?;
+                config.rules.board = Board::from_layout(&layout).map_err(|err| err.to_string())?;
+            }
+            "--tiles" => config.rules.tiles = Rules::parse_tiles(args.next().ok_or("--tiles needs a distribution")?)?,
+            "--leaves" => {
+                let path = args.next().ok_or("--leaves needs a file")?;
+                config.leave_table = LeaveTable::from_file(path).map_err(|err| err.to_string())?;
+            }
+            "--json" => json = true,
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    if config.players < 2 {
+        return Err("Games need at least 2 players".to_string());
+    }
+
+    Ok((config, json))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (config, json) = parse_args(args)?;
+    let stats = Stats::from_games(&play_games(&config)?);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats).unwrap());
+    } else {
+        print!("{}", stats);
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,105 @@
+use words_game::{Board, Game, Tile, TileBag, HAND_SIZE};
+
+/**
+ * The parts of the game that can be changed between experiments
+ */
+#[derive(Debug, Clone)]
+pub struct Rules {
+    pub hand_size: usize,
+    pub board: Board,
+    /// Every tile in a full bag
+    pub tiles: Vec<Tile>,
+}
+
+impl Default for Rules {
+    fn default() -> Rules {
+        Rules {
+            hand_size: HAND_SIZE,
+            board: Board::new(),
+            tiles: TileBag::standard_tiles(),
+        }
+    }
+}
+
+impl Rules {
+    /**
+     * Reads a tile distribution written as letters followed by how many of
+     * them there are, e.g. `E12 A9 I9`, with `?` for the blanks
+     */
+    pub fn parse_tiles(spec: &str) -> Result<Vec<Tile>, String> {
+        let mut tiles = vec![];
+
+        for entry in spec.split(|c: char| c == ',' || c.is_whitespace()).filter(|entry| !entry.is_empty()) {
+            let mut chars = entry.chars();
+            let letter = chars.next().unwrap().to_ascii_uppercase();
+            let count: usize = chars
+                .as_str()
+                .parse()
+                .map_err(|_| format!("{} needs a count after the letter, like E12", entry))?;
+
+            let tile = match letter {
+                '?' => Tile::Blank,
+                letter if letter.is_ascii_uppercase() => Tile::Letter(letter),
+                _ => return Err(format!("{} is not a letter", letter)),
+            };
+            tiles.extend(std::iter::repeat_n(tile, count));
+        }
+
+        Ok(tiles)
+    }
+
+    /**
+     * A game with these rules whose bag is shuffled by `seed`, so the same
+     * seed deals the same tiles in the same order
+     */
+    pub fn new_game(&self, player_count: usize, seed: u64) -> Result<Game, String> {
+        if self.tiles.len() < self.hand_size * player_count {
+            return Err(format!(
+                "{} tiles is not enough to deal {} players {} tiles each",
+                self.tiles.len(),
+                player_count,
+                self.hand_size
+            ));
+        }
+
+        let mut game = Game::new(player_count);
+        game.board = self.board.clone();
+        game.tile_bag = TileBag::from_tiles(self.tiles.clone()).with_seed(seed);
+        for player in game.players.iter_mut() {
+            player.hand = game.tile_bag.draw(self.hand_size).unwrap();
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tile_distributions() {
+        let tiles = Rules::parse_tiles("e2, Q1 z0").unwrap();
+        assert_eq!(tiles, vec![Tile::Letter('E'), Tile::Letter('E'), Tile::Letter('Q')]);
+
+        assert!(Rules::parse_tiles("E").is_err());
+        assert_eq!(Rules::parse_tiles("?2").unwrap(), vec![Tile::Blank, Tile::Blank]);
+        assert!(Rules::parse_tiles("?").is_err());
+        assert!(Rules::parse_tiles("-2").is_err());
+    }
+
+    #[test]
+    fn deals_from_a_seeded_bag() {
+        let rules = Rules {
+            hand_size: 7,
+            ..Rules::default()
+        };
+
+        let game = rules.new_game(3, 42).unwrap();
+        assert_eq!(game.players[2].hand.len(), 7);
+        assert_eq!(game.tile_bag.tiles.len(), rules.tiles.len() - 21);
+        assert_eq!(game.players[0].hand, rules.new_game(3, 42).unwrap().players[0].hand);
+
+        assert!(rules.new_game(20, 42).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use super::rules::Rules;
+use std::thread;
+use words_game::leave::LeaveTable;
+use words_game::movegen::bot_move;
+use words_game::{Move, Point};
+
+/// Games are cut off after this many turns in case the bots stall
+pub const MAX_TURNS: u32 = 500;
+
+/**
+ * How a batch of games is played
+ */
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub rules: Rules,
+    pub games: usize,
+    pub players: usize,
+    /// Game `n` is dealt from a bag seeded with `seed + n`
+    pub seed: u64,
+    pub threads: usize,
+    /// A play using at least this many tiles counts as a bingo
+    pub bingo_tiles: usize,
+    pub leave_table: LeaveTable,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            rules: Rules::default(),
+            games: 1000,
+            players: 2,
+            seed: 0,
+            threads: 1,
+            bingo_tiles: 7,
+            leave_table: LeaveTable::new(),
+        }
+    }
+}
+
+/**
+ * What happened in one game
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSummary {
+    /// Final scores in seat order, seat 0 moved first, after the tiles left
+    /// on the racks are counted
+    pub scores: Vec<u32>,
+    pub turns: u32,
+    pub plays: u32,
+    pub bingos: u32,
+    /// Every square a tile was placed on
+    pub placed: Vec<Point>,
+}
+
+/**
+ * Plays one game between bots until someone goes out with the bag empty,
+ * everyone has gone twice without scoring, or `MAX_TURNS` is reached
+ */
+pub fn play_game(config: &Config, seed: u64) -> Result<GameSummary, String> {
+    let mut game = config.rules.new_game(config.players, seed)?;
+    let mut summary = GameSummary {
+        scores: vec![],
+        turns: 0,
+        plays: 0,
+        bingos: 0,
+        placed: vec![],
+    };
+
+    while summary.turns < MAX_TURNS && !game.is_over() {
+        let mv = bot_move(&game, &config.leave_table);
+        let before = game.clone();
+        if game.apply_move(&mv).is_err() {
+            game.pass();
+        }
+
+        if let Move::Play { .. } = mv {
+            let placed = before.diff(&game).placed_tiles();
+            summary.plays += 1;
+            if placed.len() >= config.bingo_tiles {
+                summary.bingos += 1;
+            }
+            summary.placed.extend(placed.into_iter().map(|(point, _)| point));
+        }
+
+        summary.turns += 1;
+    }
+
+    summary.scores = game.final_scores();
+    Ok(summary)
+}
+
+/**
+ * Plays every game of the batch, spread over `config.threads` threads. The
+ * results are in game order and do not depend on the number of threads.
+ */
+pub fn play_games(config: &Config) -> Result<Vec<GameSummary>, String> {
+    let threads = config.threads.max(1);
+
+    let mut results: Vec<(usize, Result<GameSummary, String>)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread_idx| {
+                scope.spawn(move || {
+                    (thread_idx..config.games)
+                        .step_by(threads)
+                        .map(|game_idx| (game_idx, play_game(config, config.seed + game_idx as u64)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Self play thread panicked"))
+            .collect()
+    });
+
+    results.sort_by_key(|(game_idx, _)| *game_idx);
+    results.into_iter().map(|(_, summary)| summary).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(games: usize, threads: usize) -> Config {
+        Config {
+            games,
+            threads,
+            seed: 11,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn games_are_reproducible() {
+        let one_thread = play_games(&config(4, 1)).unwrap();
+        let three_threads = play_games(&config(4, 3)).unwrap();
+
+        assert_eq!(one_thread, three_threads);
+        assert_ne!(one_thread[0], one_thread[1]);
+    }
+
+    #[test]
+    fn games_run_to_the_end() {
+        let summary = play_game(&config(1, 1), 3).unwrap();
+
+        assert!(summary.turns < MAX_TURNS);
+        assert!(summary.plays > 10);
+        assert_eq!(summary.scores.len(), 2);
+        assert!(summary.scores.iter().all(|score| *score > 0));
+    }
+
+}
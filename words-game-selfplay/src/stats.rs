@@ -0,0 +1,169 @@
+use super::runner::GameSummary;
+use serde::Serialize;
+use std::fmt;
+use words_game::BOARD_SIZE;
+
+/**
+ * Totals over a batch of games
+ */
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub games: usize,
+    pub average_score: f64,
+    /// The average score of each seat, in turn order
+    pub average_score_by_seat: Vec<f64>,
+    pub average_winning_margin: f64,
+    /// Bingos out of all plays
+    pub bingo_rate: f64,
+    pub bingos_per_game: f64,
+    /// How often the player who moved first won outright
+    pub first_player_win_rate: f64,
+    pub tie_rate: f64,
+    pub average_turns: f64,
+    pub min_turns: u32,
+    pub max_turns: u32,
+    /// For each square, by row then column, the fraction of games that had
+    /// a tile placed on it
+    pub square_usage: Vec<Vec<f64>>,
+}
+
+fn ratio(part: f64, whole: f64) -> f64 {
+    if whole == 0.0 {
+        0.0
+    } else {
+        part / whole
+    }
+}
+
+impl Stats {
+    pub fn from_games(summaries: &[GameSummary]) -> Stats {
+        let games = summaries.len() as f64;
+        let seats = summaries.iter().map(|summary| summary.scores.len()).max().unwrap_or(0);
+
+        let mut seat_totals = vec![0.0; seats];
+        let mut square_totals = vec![vec![0.0; BOARD_SIZE as usize]; BOARD_SIZE as usize];
+        let (mut margins, mut first_player_wins, mut ties) = (0.0, 0.0, 0.0);
+
+        for summary in summaries {
+            for (total, score) in seat_totals.iter_mut().zip(&summary.scores) {
+                *total += *score as f64;
+            }
+
+            let mut ranked = summary.scores.clone();
+            ranked.sort_unstable_by(|a, b| b.cmp(a));
+            match ranked.as_slice() {
+                [best, second, ..] if best == second => ties += 1.0,
+                [best, second, ..] => {
+                    margins += (best - second) as f64;
+                    if summary.scores[0] == *best {
+                        first_player_wins += 1.0;
+                    }
+                }
+                _ => {}
+            }
+
+            // Tiles are only placed once on each square per game
+            for point in &summary.placed {
+                square_totals[point.y as usize][point.x as usize] += 1.0;
+            }
+        }
+
+        let plays: u32 = summaries.iter().map(|summary| summary.plays).sum();
+        let bingos: u32 = summaries.iter().map(|summary| summary.bingos).sum();
+        let turns: u32 = summaries.iter().map(|summary| summary.turns).sum();
+
+        Stats {
+            games: summaries.len(),
+            average_score: ratio(seat_totals.iter().sum(), games * seats as f64),
+            average_score_by_seat: seat_totals.iter().map(|total| ratio(*total, games)).collect(),
+            average_winning_margin: ratio(margins, games - ties),
+            bingo_rate: ratio(bingos as f64, plays as f64),
+            bingos_per_game: ratio(bingos as f64, games),
+            first_player_win_rate: ratio(first_player_wins, games),
+            tie_rate: ratio(ties, games),
+            average_turns: ratio(turns as f64, games),
+            min_turns: summaries.iter().map(|summary| summary.turns).min().unwrap_or(0),
+            max_turns: summaries.iter().map(|summary| summary.turns).max().unwrap_or(0),
+            square_usage: square_totals
+                .into_iter()
+                .map(|row| row.into_iter().map(|count| ratio(count, games)).collect())
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seats: Vec<String> = self
+            .average_score_by_seat
+            .iter()
+            .enumerate()
+            .map(|(idx, score)| format!("{}: {:.1}", idx + 1, score))
+            .collect();
+
+        writeln!(f, "Games played:           {}", self.games)?;
+        writeln!(f, "Average score:          {:.1}", self.average_score)?;
+        writeln!(f, "Average score by seat:  {}", seats.join(", "))?;
+        writeln!(f, "Average winning margin: {:.1}", self.average_winning_margin)?;
+        writeln!(f, "Bingo rate:             {:.2}% of plays, {:.2} per game", self.bingo_rate * 100.0, self.bingos_per_game)?;
+        writeln!(f, "First player wins:      {:.1}% ({:.1}% ties)", self.first_player_win_rate * 100.0, self.tie_rate * 100.0)?;
+        writeln!(f, "Game length:            {:.1} turns ({} to {})", self.average_turns, self.min_turns, self.max_turns)?;
+
+        writeln!(f, "Square usage (% of games with a tile placed there):")?;
+        let header: String = (0..BOARD_SIZE).map(|x| format!("{:>4}", x)).collect();
+        writeln!(f, "  {}", header)?;
+        for (y, row) in self.square_usage.iter().enumerate() {
+            let cells: String = row.iter().map(|usage| format!("{:>4.0}", usage * 100.0)).collect();
+            writeln!(f, "{:>2}{}", y, cells)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use words_game::Point;
+
+    fn summary(scores: &[u32], plays: u32, bingos: u32, placed: &[(i32, i32)]) -> GameSummary {
+        GameSummary {
+            scores: scores.to_vec(),
+            turns: plays + 2,
+            plays,
+            bingos,
+            placed: placed.iter().map(|(x, y)| Point::new(*x, *y)).collect(),
+        }
+    }
+
+    #[test]
+    fn totals_games() {
+        let stats = Stats::from_games(&[
+            summary(&[300, 250], 20, 1, &[(7, 7), (8, 7)]),
+            summary(&[200, 260], 18, 0, &[(7, 7)]),
+            summary(&[240, 240], 22, 3, &[(7, 7), (7, 8)]),
+        ]);
+
+        assert_eq!(stats.games, 3);
+        assert_eq!(stats.average_score_by_seat, vec![740.0 / 3.0, 750.0 / 3.0]);
+        assert_eq!(stats.average_winning_margin, 55.0);
+        assert_eq!(stats.bingo_rate, 4.0 / 60.0);
+        assert_eq!(stats.first_player_win_rate, 1.0 / 3.0);
+        assert_eq!(stats.tie_rate, 1.0 / 3.0);
+        assert_eq!((stats.min_turns, stats.max_turns), (20, 24));
+        assert_eq!(stats.square_usage[7][7], 1.0);
+        assert_eq!(stats.square_usage[7][8], 1.0 / 3.0);
+
+        let report = stats.to_string();
+        assert!(report.contains("Average score by seat:  1: 246.7, 2: 250.0"));
+        assert!(report.contains(" 7   0   0   0   0   0   0   0 100  33"));
+    }
+
+    #[test]
+    fn no_games() {
+        let stats = Stats::from_games(&[]);
+
+        assert_eq!(stats.average_score, 0.0);
+        assert_eq!(stats.square_usage.len(), BOARD_SIZE as usize);
+    }
+}
@@ -0,0 +1,408 @@
+use super::persistence::Persistence;
+use super::protocol::{ClientMessage, ServerMessage};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use words_game::normalize::normalize_tiles;
+use words_game::{Error, Game, Move, PlayerKind, Point, Tile};
+
+pub type ClientId = u64;
+
+const MAX_PLAYERS: usize = 4;
+
+struct Seat {
+    game_id: String,
+    player_idx: usize,
+}
+
+struct Client {
+    sender: Sender<ServerMessage>,
+    seat: Option<Seat>,
+}
+
+struct Room {
+    game: Game,
+    /// The clients connected to the game and the player they play as
+    members: Vec<(ClientId, usize)>,
+}
+
+#[derive(Default)]
+struct State {
+    next_client_id: ClientId,
+    clients: HashMap<ClientId, Client>,
+    rooms: HashMap<String, Room>,
+}
+
+impl State {
+    fn send(&self, client_id: ClientId, message: ServerMessage) {
+        if let Some(client) = self.clients.get(&client_id) {
+            // The client is going away if this fails, it gets cleaned up on
+            // disconnect
+            let _ = client.sender.send(message);
+        }
+    }
+
+    fn broadcast(&self, game_id: &str, message: ServerMessage) {
+        for (client_id, _) in self.rooms[game_id].members.iter() {
+            self.send(*client_id, message.clone());
+        }
+    }
+
+    /**
+     * Sends every member of the room their own view of the game
+     */
+    fn push_state(&self, game_id: &str) {
+        let room = &self.rooms[game_id];
+
+        for (client_id, player_idx) in room.members.iter() {
+            if let Ok(view) = room.game.view_for(*player_idx) {
+                self.send(*client_id, ServerMessage::State { view });
+            }
+        }
+    }
+}
+
+/**
+ * Hosts every game on the server and routes messages between the games and
+ * the connected clients. Transports call `connect` for each new connection
+ * and pass on what the client sends to `handle`.
+ */
+pub struct Hub {
+    state: Mutex<State>,
+    persistence: Box<dyn Persistence>,
+}
+
+impl Hub {
+    pub fn new(persistence: Box<dyn Persistence>) -> Hub {
+        Hub {
+            state: Mutex::new(State::default()),
+            persistence,
+        }
+    }
+
+    /**
+     * Registers a new client, messages for it arrive on the returned receiver
+     */
+    pub fn connect(&self) -> (ClientId, Receiver<ServerMessage>) {
+        let mut state = self.state.lock().unwrap();
+        let (sender, receiver) = mpsc::channel();
+
+        let client_id = state.next_client_id;
+        state.next_client_id += 1;
+        state.clients.insert(client_id, Client { sender, seat: None });
+
+        (client_id, receiver)
+    }
+
+    /**
+     * Removes a client. Their seat is kept so they can join again later.
+     */
+    pub fn disconnect(&self, client_id: ClientId) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(Client { seat: Some(seat), .. }) = state.clients.remove(&client_id) {
+            if let Some(room) = state.rooms.get_mut(&seat.game_id) {
+                room.members.retain(|(member, _)| *member != client_id);
+            }
+        }
+    }
+
+    pub fn handle(&self, client_id: ClientId, message: ClientMessage) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Err(message) = self.dispatch(&mut state, client_id, message) {
+            state.send(client_id, ServerMessage::Error { message });
+        }
+    }
+
+    fn dispatch(
+        &self,
+        state: &mut State,
+        client_id: ClientId,
+        message: ClientMessage,
+    ) -> Result<(), String> {
+        match message {
+            ClientMessage::Join { game_id, player_id, name } => {
+                self.join(state, client_id, game_id, &player_id, &name)
+            }
+            ClientMessage::Play { x, y, dir, word } => {
+                let mv = Move::Play {
+                    start: Point::new(x, y),
+                    dir: dir.into(),
+                    word,
+                };
+                self.make_move(state, client_id, mv)
+            }
+            ClientMessage::Pass => self.make_move(state, client_id, Move::Pass),
+            ClientMessage::Exchange { tiles } => {
+                let tiles = parse_tiles(&tiles)?;
+                self.make_move(state, client_id, Move::Exchange(tiles))
+            }
+            ClientMessage::Chat { text } => {
+                let seat = seat_of(state, client_id)?;
+                let room = &state.rooms[&seat.game_id];
+                let name = room.game.players[seat.player_idx].name(seat.player_idx);
+
+                let message = ServerMessage::Chat {
+                    player_idx: seat.player_idx,
+                    name,
+                    text,
+                };
+                let game_id = seat.game_id.clone();
+                state.broadcast(&game_id, message);
+                Ok(())
+            }
+        }
+    }
+
+    fn join(
+        &self,
+        state: &mut State,
+        client_id: ClientId,
+        game_id: String,
+        player_id: &str,
+        name: &str,
+    ) -> Result<(), String> {
+        if seat_of(state, client_id).is_ok() {
+            return Err("You are already in a game".to_string());
+        }
+
+        if !state.rooms.contains_key(&game_id) {
+            let game = self
+                .persistence
+                .load(&game_id)
+                .map_err(|err| err.to_string())?
+                .unwrap_or_else(|| Game::with_players(&[(player_id, name, PlayerKind::Human)]));
+
+            state.rooms.insert(
+                game_id.clone(),
+                Room {
+                    game,
+                    members: vec![],
+                },
+            );
+        }
+
+        let room = state.rooms.get_mut(&game_id).unwrap();
+        let player_idx = match room.game.player_idx_by_id(player_id) {
+            Some(player_idx) => player_idx,
+            None => {
+                if room.game.players.len() >= MAX_PLAYERS {
+                    return Err("The game is full".to_string());
+                }
+
+                let mut game = room.game.clone();
+                let player_idx = game.add_player().map_err(|err| err.to_string())?;
+                let player = &mut game.players[player_idx];
+                player.id = Some(player_id.to_string());
+                player.display_name = Some(name.to_string());
+
+                self.persistence
+                    .save(&game_id, &game)
+                    .map_err(|err| err.to_string())?;
+                room.game = game;
+                player_idx
+            }
+        };
+
+        room.members.push((client_id, player_idx));
+        state.clients.get_mut(&client_id).unwrap().seat = Some(Seat {
+            game_id: game_id.clone(),
+            player_idx,
+        });
+
+        state.send(client_id, ServerMessage::Joined {
+            game_id: game_id.clone(),
+            player_idx,
+        });
+        state.push_state(&game_id);
+        Ok(())
+    }
+
+    fn make_move(&self, state: &mut State, client_id: ClientId, mv: Move) -> Result<(), String> {
+        let seat = seat_of(state, client_id)?;
+        let (game_id, player_idx) = (seat.game_id.clone(), seat.player_idx);
+        let room = state.rooms.get_mut(&game_id).unwrap();
+
+        if room.game.get_current_player_idx() != player_idx {
+            return Err("It is not your turn to play".to_string());
+        }
+
+        let mut game = room.game.clone();
+        let event = match mv {
+            Move::Play { start, dir, ref word } => {
+                let result = game.play_word(start, dir, word).map_err(|err| err.to_string())?;
+                ServerMessage::Played {
+                    player_idx,
+                    words: result.words,
+                    score: result.score,
+                }
+            }
+            Move::Pass => {
+                game.pass();
+                ServerMessage::Passed { player_idx }
+            }
+            Move::Exchange(ref tiles) => {
+                game.exchange(tiles).map_err(|err| err.to_string())?;
+                ServerMessage::Exchanged {
+                    player_idx,
+                    count: tiles.len(),
+                }
+            }
+        };
+
+        self.persistence
+            .save(&game_id, &game)
+            .map_err(|err| err.to_string())?;
+        room.game = game;
+
+        state.broadcast(&game_id, event);
+        state.push_state(&game_id);
+        Ok(())
+    }
+}
+
+fn seat_of(state: &State, client_id: ClientId) -> Result<&Seat, String> {
+    state
+        .clients
+        .get(&client_id)
+        .and_then(|client| client.seat.as_ref())
+        .ok_or_else(|| "Join a game first".to_string())
+}
+
+fn parse_tiles(letters: &str) -> Result<Vec<Tile>, String> {
+    normalize_tiles(letters).map_err(|err| match *err {
+        Error::InvalidCharacter(c) => format!("{} is not a tile", c),
+        ref err => err.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::local::LocalClient;
+    use super::super::persistence::MemoryPersistence;
+    use super::super::protocol::PlayDirection;
+    use std::sync::Arc;
+
+    fn hub() -> Arc<Hub> {
+        Arc::new(Hub::new(Box::new(MemoryPersistence::default())))
+    }
+
+    fn join(client: &LocalClient, player_id: &str) {
+        client.send(ClientMessage::Join {
+            game_id: "game".to_string(),
+            player_id: player_id.to_string(),
+            name: player_id.to_uppercase(),
+        });
+    }
+
+    fn errors(messages: &[ServerMessage]) -> Vec<String> {
+        messages
+            .iter()
+            .filter_map(|message| match message {
+                ServerMessage::Error { message } => Some(message.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn players_get_their_own_view() {
+        let hub = hub();
+        let alice = LocalClient::connect(&hub);
+        let bob = LocalClient::connect(&hub);
+
+        join(&alice, "alice");
+        join(&bob, "bob");
+
+        let messages = bob.drain();
+        match &messages[0] {
+            ServerMessage::Joined { player_idx, .. } => assert_eq!(*player_idx, 1),
+            other => panic!("unexpected {:?}", other),
+        }
+        match messages.last().unwrap() {
+            ServerMessage::State { view } => {
+                assert_eq!(view.viewer_idx, 1);
+                assert_eq!(view.players.len(), 2);
+                assert_eq!(view.players[0].display_name.as_deref(), Some("ALICE"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // Alice also hears about Bob joining
+        assert!(alice
+            .drain()
+            .iter()
+            .any(|message| matches!(message, ServerMessage::State { view } if view.players.len() == 2)));
+    }
+
+    #[test]
+    fn moves_are_checked_and_broadcast() {
+        let hub = hub();
+        let alice = LocalClient::connect(&hub);
+        let bob = LocalClient::connect(&hub);
+        join(&alice, "alice");
+        join(&bob, "bob");
+        alice.drain();
+        bob.drain();
+
+        bob.send(ClientMessage::Pass);
+        assert_eq!(errors(&bob.drain()), vec!["It is not your turn to play".to_string()]);
+
+        alice.send(ClientMessage::Play {
+            x: 0,
+            y: 0,
+            dir: PlayDirection::Right,
+            word: "QQQ".to_string(),
+        });
+        assert_eq!(errors(&alice.drain()).len(), 1);
+
+        alice.send(ClientMessage::Pass);
+        let messages = bob.drain();
+        assert!(matches!(messages[0], ServerMessage::Passed { player_idx: 0 }));
+        match &messages[1] {
+            ServerMessage::State { view } => assert_eq!(view.current_player_idx, 1),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn chat_and_rejoin() {
+        let hub = hub();
+        let alice = LocalClient::connect(&hub);
+        join(&alice, "alice");
+        drop(alice);
+
+        let alice = LocalClient::connect(&hub);
+        join(&alice, "alice");
+        alice.send(ClientMessage::Chat {
+            text: "hi".to_string(),
+        });
+
+        let messages = alice.drain();
+        assert!(matches!(messages[0], ServerMessage::Joined { player_idx: 0, .. }));
+        assert!(matches!(messages.last().unwrap(), ServerMessage::Chat { text, .. } if text == "hi"));
+
+        join(&alice, "alice");
+        assert_eq!(errors(&alice.drain()), vec!["You are already in a game".to_string()]);
+    }
+
+    #[test]
+    fn requires_a_game() {
+        let hub = hub();
+        let client = LocalClient::connect(&hub);
+
+        client.send(ClientMessage::Exchange {
+            tiles: "A".to_string(),
+        });
+        client.send(ClientMessage::Exchange {
+            tiles: "1".to_string(),
+        });
+
+        assert_eq!(
+            errors(&client.drain()),
+            vec!["Join a game first".to_string(), "1 is not a tile".to_string()]
+        );
+    }
+}
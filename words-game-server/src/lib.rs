@@ -0,0 +1,19 @@
+/*!
+ * A server that hosts words-game matches for browser clients.
+ *
+ * Clients talk to the server with JSON messages over a WebSocket, see
+ * `protocol` for the messages. The server is the only one that knows the
+ * full state of each game, every client is sent a view with just their own
+ * hand after each change.
+ */
+
+pub mod hub;
+pub mod local;
+pub mod persistence;
+pub mod protocol;
+pub mod ws;
+
+pub use hub::{ClientId, Hub};
+pub use local::LocalClient;
+pub use persistence::{MemoryPersistence, Persistence};
+pub use protocol::{ClientMessage, PlayDirection, ServerMessage};
@@ -0,0 +1,54 @@
+use super::hub::{ClientId, Hub};
+use super::protocol::{ClientMessage, ServerMessage};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/**
+ * A client that talks to a `Hub` directly, without a socket. Used to test
+ * the server and to run bots in the same process.
+ */
+pub struct LocalClient {
+    hub: Arc<Hub>,
+    id: ClientId,
+    receiver: Receiver<ServerMessage>,
+}
+
+impl LocalClient {
+    pub fn connect(hub: &Arc<Hub>) -> LocalClient {
+        let (id, receiver) = hub.connect();
+
+        LocalClient {
+            hub: Arc::clone(hub),
+            id,
+            receiver,
+        }
+    }
+
+    pub fn send(&self, message: ClientMessage) {
+        self.hub.handle(self.id, message);
+    }
+
+    /**
+     * Waits up to `timeout` for the next message
+     */
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<ServerMessage> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(message) => Some(message),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
+    /**
+     * Every message that has arrived so far
+     */
+    pub fn drain(&self) -> Vec<ServerMessage> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Drop for LocalClient {
+    fn drop(&mut self) {
+        self.hub.disconnect(self.id);
+    }
+}
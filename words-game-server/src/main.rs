@@ -0,0 +1,16 @@
+use std::env;
+use std::net::TcpListener;
+use std::sync::Arc;
+use words_game_server::{ws, Hub, MemoryPersistence};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:3012";
+
+fn main() -> std::io::Result<()> {
+    let address = env::args().nth(1).unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+
+    let listener = TcpListener::bind(&address)?;
+    println!("Listening on ws://{}", address);
+
+    let hub = Arc::new(Hub::new(Box::new(MemoryPersistence::default())));
+    ws::serve(listener, hub)
+}
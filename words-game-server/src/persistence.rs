@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::Mutex;
+use words_game::Game;
+
+/**
+ * Where the server keeps games between restarts. Games are loaded the first
+ * time someone joins them and saved after every move.
+ */
+pub trait Persistence: Send + Sync {
+    fn load(&self, game_id: &str) -> io::Result<Option<Game>>;
+    fn save(&self, game_id: &str, game: &Game) -> io::Result<()>;
+}
+
+/**
+ * Keeps games in memory only, they are lost when the server stops
+ */
+#[derive(Default)]
+pub struct MemoryPersistence {
+    games: Mutex<HashMap<String, Game>>,
+}
+
+impl Persistence for MemoryPersistence {
+    fn load(&self, game_id: &str) -> io::Result<Option<Game>> {
+        Ok(self.games.lock().unwrap().get(game_id).cloned())
+    }
+
+    fn save(&self, game_id: &str, game: &Game) -> io::Result<()> {
+        self.games.lock().unwrap().insert(game_id.to_string(), game.clone());
+        Ok(())
+    }
+}
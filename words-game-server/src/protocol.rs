@@ -0,0 +1,84 @@
+use serde::{Serialize, Deserialize};
+use words_game::{Direction, PlayerView};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayDirection {
+    Right,
+    Down,
+}
+
+impl From<PlayDirection> for Direction {
+    fn from(dir: PlayDirection) -> Direction {
+        match dir {
+            PlayDirection::Right => Direction::right(),
+            PlayDirection::Down => Direction::down(),
+        }
+    }
+}
+
+/**
+ * Messages sent by clients, e.g. `{"type": "play", "x": 7, "y": 7,
+ * "dir": "right", "word": "ACTOR"}`
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Joins the game with `game_id`, creating it if it does not exist yet.
+    /// Joining again with the same `player_id` takes back that seat.
+    Join {
+        game_id: String,
+        player_id: String,
+        name: String,
+    },
+    Play {
+        x: i32,
+        y: i32,
+        dir: PlayDirection,
+        word: String,
+    },
+    Pass,
+    /// The letters to swap, with a space for a blank
+    Exchange {
+        tiles: String,
+    },
+    Chat {
+        text: String,
+    },
+}
+
+/**
+ * Messages sent by the server
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Joined {
+        game_id: String,
+        player_idx: usize,
+    },
+    /// The game as the receiving player sees it, sent after every change
+    State {
+        view: PlayerView,
+    },
+    Played {
+        player_idx: usize,
+        words: Vec<String>,
+        score: u32,
+    },
+    Passed {
+        player_idx: usize,
+    },
+    Exchanged {
+        player_idx: usize,
+        count: usize,
+    },
+    Chat {
+        player_idx: usize,
+        name: String,
+        text: String,
+    },
+    Error {
+        message: String,
+    },
+}
@@ -0,0 +1,85 @@
+use super::hub::Hub;
+use super::protocol::{ClientMessage, ServerMessage};
+use std::io;
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tungstenite::{Error, Message};
+
+/// How long a connection waits for the client before checking for messages
+/// to send it
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/**
+ * Accepts WebSocket connections on `listener` until it fails, each
+ * connection is handled on its own thread
+ */
+pub fn serve(listener: TcpListener, hub: Arc<Hub>) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let hub = Arc::clone(&hub);
+
+        thread::spawn(move || {
+            if let Err(err) = handle_connection(stream, &hub) {
+                eprintln!("Connection closed with error: {}", err);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn to_text(message: &ServerMessage) -> Message {
+    Message::Text(serde_json::to_string(message).unwrap())
+}
+
+fn handle_connection(stream: TcpStream, hub: &Hub) -> Result<(), Box<Error>> {
+    let mut socket = tungstenite::accept(stream).map_err(|err| match err {
+        tungstenite::HandshakeError::Failure(err) => err,
+        tungstenite::HandshakeError::Interrupted(_) => Error::ConnectionClosed,
+    })?;
+    socket
+        .get_mut()
+        .set_read_timeout(Some(POLL_INTERVAL))
+        .map_err(Error::Io)?;
+
+    let (client_id, outgoing) = hub.connect();
+
+    let result = loop {
+        let mut sent = Ok(());
+        for message in outgoing.try_iter() {
+            sent = socket.send(to_text(&message));
+            if sent.is_err() {
+                break;
+            }
+        }
+        if let Err(err) = sent {
+            break Err(err);
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str::<ClientMessage>(&text) {
+                Ok(message) => hub.handle(client_id, message),
+                Err(err) => {
+                    let message = ServerMessage::Error {
+                        message: format!("Bad message: {}", err),
+                    };
+                    if let Err(err) = socket.send(to_text(&message)) {
+                        break Err(err);
+                    }
+                }
+            },
+            Ok(Message::Close(_)) | Err(Error::ConnectionClosed) | Err(Error::AlreadyClosed) => {
+                break Ok(())
+            }
+            Ok(_) => {}
+            Err(Error::Io(ref err))
+                if err.kind() == io::ErrorKind::WouldBlock || err.kind() == io::ErrorKind::TimedOut => {}
+            Err(err) => break Err(err),
+        }
+    };
+
+    hub.disconnect(client_id);
+    result.map_err(Box::new)
+}
@@ -0,0 +1,46 @@
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::thread;
+use tungstenite::Message;
+use words_game_server::{ws, ClientMessage, Hub, MemoryPersistence, ServerMessage};
+
+fn read_message<S: std::io::Read + std::io::Write>(
+    socket: &mut tungstenite::WebSocket<S>,
+) -> ServerMessage {
+    loop {
+        if let Message::Text(text) = socket.read().unwrap() {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+#[test]
+fn join_over_websocket() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let hub = Arc::new(Hub::new(Box::new(MemoryPersistence::default())));
+    thread::spawn(move || ws::serve(listener, hub));
+
+    let (mut socket, _) = tungstenite::connect(format!("ws://{}", address)).unwrap();
+
+    let join = ClientMessage::Join {
+        game_id: "game".to_string(),
+        player_id: "alice".to_string(),
+        name: "Alice".to_string(),
+    };
+    socket
+        .send(Message::Text(serde_json::to_string(&join).unwrap()))
+        .unwrap();
+
+    assert!(matches!(read_message(&mut socket), ServerMessage::Joined { player_idx: 0, .. }));
+    match read_message(&mut socket) {
+        ServerMessage::State { view } => {
+            assert_eq!(view.hand.len(), view.players[0].tile_count);
+            assert_eq!(view.players[0].display_name.as_deref(), Some("Alice"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+
+    socket.send(Message::Text("not json".to_string())).unwrap();
+    assert!(matches!(read_message(&mut socket), ServerMessage::Error { .. }));
+}
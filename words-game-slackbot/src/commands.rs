@@ -0,0 +1,198 @@
+use words_game::coordinates::parse_play_notation;
+use words_game::normalize::normalize_word;
+use words_game::{Direction, Point};
+
+/**
+ * A parsed `/scrabbler` command, the same commands the Phoenix app accepts
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Help,
+    /// Starts a game with the tagged users, as `(user id, user name)`
+    Start { players: Vec<(String, String)> },
+    Board,
+    Hand,
+    Play {
+        start: Point,
+        dir: Direction,
+        word: String,
+    },
+    Dict { word: String, mode: DictMode },
+    Quit,
+}
+
+/**
+ * What `dict` looks up
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DictMode {
+    /// Whether the word is valid
+    Check,
+    /// Words that can be made from the letters
+    Make,
+    /// Words that fit a pattern like `?A??R`
+    Match,
+    /// Letters that can go before or after the word
+    Hooks,
+}
+
+impl Command {
+    /**
+     * Parses the text typed after the command name
+     */
+    pub fn parse(text: &str) -> Result<Command, String> {
+        let text = text.trim();
+        let (name, rest) = match text.find(' ') {
+            Some(idx) => (&text[..idx], text[idx..].trim()),
+            None => (text, ""),
+        };
+
+        match name {
+            "help" => Ok(Command::Help),
+            "start" => Ok(Command::Start {
+                players: parse_tags(rest),
+            }),
+            "board" => Ok(Command::Board),
+            "hand" => Ok(Command::Hand),
+            "play" => parse_play(rest),
+            "dict" => parse_dict(rest),
+            "quit" => Ok(Command::Quit),
+            _ => Err("Command not recognized".to_string()),
+        }
+    }
+}
+
+fn parse_dict(text: &str) -> Result<Command, String> {
+    let (mode, word) = match text.find(' ') {
+        Some(idx) => match &text[..idx] {
+            "make" => (DictMode::Make, text[idx..].trim()),
+            "match" => (DictMode::Match, text[idx..].trim()),
+            "hooks" => (DictMode::Hooks, text[idx..].trim()),
+            _ => (DictMode::Check, text),
+        },
+        None => (DictMode::Check, text),
+    };
+
+    if word.is_empty() {
+        return Err("Please specify a word".to_string());
+    }
+
+    Ok(Command::Dict {
+        word: word.to_string(),
+        mode,
+    })
+}
+
+/**
+ * Finds the users tagged in `text`. Slack escapes tags as `<@U123|name>`.
+ */
+fn parse_tags(text: &str) -> Vec<(String, String)> {
+    text.split("<@")
+        .skip(1)
+        .filter_map(|tag| {
+            let tag = &tag[..tag.find('>')?];
+            let (id, name) = tag.split_at(tag.find('|')?);
+            Some((id.to_string(), name[1..].to_string()))
+        })
+        .filter(|(id, name)| !id.is_empty() && !name.is_empty())
+        .collect()
+}
+
+/**
+ * Parses `ACTOR 7,7 right`, the coordinates can also be written `7:7` or
+ * `7, 7`. Scrabble notation like `ACTOR 8H` works too.
+ */
+fn parse_play(text: &str) -> Result<Command, String> {
+    let invalid = || "command is invalid".to_string();
+
+    let normalized = text.replace(':', ",").replace(", ", ",");
+    let parts: Vec<&str> = normalized.split_whitespace().collect();
+    let (word, coords, dir) = match parts[..] {
+        [word, coords, dir] => (word, coords, dir),
+        [word, notation] => {
+            let (start, dir) = parse_play_notation(notation).map_err(|err| err.to_string())?;
+            return Ok(Command::Play {
+                start,
+                dir,
+                word: normalize_word(word).map_err(|_| invalid())?,
+            });
+        }
+        _ => return Err(invalid()),
+    };
+
+    let (x, y) = match coords.split(',').collect::<Vec<_>>()[..] {
+        [x, y] => (x.parse().map_err(|_| invalid())?, y.parse().map_err(|_| invalid())?),
+        _ => return Err(invalid()),
+    };
+
+    let dir = match &dir.to_lowercase()[..] {
+        "right" => Direction::right(),
+        "down" => Direction::down(),
+        _ => return Err(invalid()),
+    };
+
+    Ok(Command::Play {
+        start: Point::new(x, y),
+        dir,
+        word: normalize_word(word).map_err(|_| invalid())?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_play() {
+        let expected = Command::Play {
+            start: Point::new(7, 8),
+            dir: Direction::right(),
+            word: "ACTOR".to_string(),
+        };
+
+        assert_eq!(Command::parse("play actor 7,8 right"), Ok(expected.clone()));
+        assert_eq!(Command::parse("play ACTOR 7:8 Right"), Ok(expected.clone()));
+        assert_eq!(Command::parse("  play ACTOR 7, 8 right "), Ok(expected.clone()));
+        assert_eq!(Command::parse("play actor 9h"), Ok(expected));
+        assert!(Command::parse("play ACTOR 7 right").is_err());
+        assert!(Command::parse("play ACTOR 7,8 up").is_err());
+        assert!(Command::parse("play AC7OR 7,8 down").is_err());
+    }
+
+    #[test]
+    fn parses_start_tags() {
+        let command = Command::parse("start <@U1|alice>, <@U2|bob> <@bad>").unwrap();
+
+        assert_eq!(
+            command,
+            Command::Start {
+                players: vec![
+                    ("U1".to_string(), "alice".to_string()),
+                    ("U2".to_string(), "bob".to_string())
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn other_commands() {
+        assert_eq!(Command::parse("help"), Ok(Command::Help));
+        assert_eq!(Command::parse("hand"), Ok(Command::Hand));
+        assert_eq!(
+            Command::parse("dict zax"),
+            Ok(Command::Dict {
+                word: "zax".to_string(),
+                mode: DictMode::Check,
+            })
+        );
+        assert_eq!(
+            Command::parse("dict match ?A??R"),
+            Ok(Command::Dict {
+                word: "?A??R".to_string(),
+                mode: DictMode::Match,
+            })
+        );
+        assert!(Command::parse("dict").is_err());
+        assert!(Command::parse("dance").is_err());
+    }
+}
@@ -0,0 +1,503 @@
+use super::commands::{Command, DictMode};
+use super::http::BOARD_IMAGE_PATH;
+use super::render;
+use super::signature;
+use serde::Serialize;
+use std::collections::HashMap;
+use words_game::finder::{find_words, hooks, WordQuery};
+use words_game::image::{render_png, ImageOptions};
+use words_game::definitions::lookup;
+use words_game::{Game, PlayerKind, Point};
+use words_game_store::{GameId, GameRecord, GameStore, StoreError};
+
+/**
+ * The fields of a slash command request that the bot uses
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandParams {
+    pub team_id: String,
+    pub channel_id: String,
+    pub user_id: String,
+    pub user_name: String,
+    pub command: String,
+    pub text: String,
+}
+
+impl CommandParams {
+    /**
+     * Reads the url encoded form Slack posts
+     */
+    pub fn from_form(body: &[u8]) -> Result<CommandParams, String> {
+        let mut fields: HashMap<String, String> = form_urlencoded::parse(body).into_owned().collect();
+
+        let mut take = |key: &str| {
+            fields
+                .remove(key)
+                .ok_or_else(|| format!("Request is missing {}", key))
+        };
+
+        Ok(CommandParams {
+            team_id: take("team_id")?,
+            channel_id: take("channel_id")?,
+            user_id: take("user_id")?,
+            user_name: take("user_name")?,
+            command: take("command")?,
+            text: take("text")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Response {
+    pub text: String,
+    /// Only the user who sent the command sees ephemeral responses
+    pub ephemeral: bool,
+    /// Attached under the text, used for pictures of the board
+    pub image_url: Option<String>,
+}
+
+impl Response {
+    fn in_channel(text: String) -> Response {
+        Response {
+            text,
+            ephemeral: false,
+            image_url: None,
+        }
+    }
+
+    fn ephemeral(text: String) -> Response {
+        Response {
+            text,
+            ephemeral: true,
+            image_url: None,
+        }
+    }
+
+    /**
+     * The JSON body Slack expects back
+     */
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::json!({
+            "response_type": if self.ephemeral { "ephemeral" } else { "in_channel" },
+            "text": self.text,
+        });
+
+        if let Some(ref image_url) = self.image_url {
+            json["attachments"] = serde_json::json!([{ "fallback": "The game board", "image_url": image_url }]);
+        }
+
+        json.to_string()
+    }
+}
+
+/// Slack messages get hard to read with more words than this
+const FOUND_WORDS_LIMIT: usize = 40;
+
+fn storage_error(err: StoreError) -> String {
+    match err {
+        StoreError::VersionConflict { .. } => {
+            "Someone else changed the game at the same time, please try again".to_string()
+        }
+        err => {
+            eprintln!("{}", err);
+            "Something went wrong, please try again".to_string()
+        }
+    }
+}
+
+pub struct App {
+    store: Box<dyn GameStore>,
+    signing_secret: String,
+    command_name: String,
+    /// Where Slack can reach the server, boards are sent as images when set
+    public_url: Option<String>,
+}
+
+impl App {
+    pub fn new(store: Box<dyn GameStore>, signing_secret: &str, command_name: &str) -> App {
+        App {
+            store,
+            signing_secret: signing_secret.to_string(),
+            command_name: command_name.to_string(),
+            public_url: None,
+        }
+    }
+
+    /**
+     * Sends boards as PNG attachments served from `public_url` instead of as
+     * emojis, for workspaces without the emoji pack installed
+     */
+    pub fn with_public_url(mut self, public_url: &str) -> App {
+        self.public_url = Some(public_url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /**
+     * Handles a raw request from Slack, returning the HTTP status and body.
+     * `now_ms` is the current unix time in milliseconds.
+     */
+    pub fn handle_request(
+        &self,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+        now_ms: u64,
+    ) -> (u16, String) {
+        if let Err(err) = signature::verify(&self.signing_secret, timestamp, signature, body, now_ms / 1000) {
+            return (401, err.to_string());
+        }
+
+        match CommandParams::from_form(body) {
+            Ok(params) => (200, self.handle_command(&params, now_ms).to_json()),
+            Err(err) => (400, err),
+        }
+    }
+
+    pub fn handle_command(&self, params: &CommandParams, now_ms: u64) -> Response {
+        if params.command != self.command_name {
+            return Response::ephemeral(format!("Unsupported command name {}", params.command));
+        }
+
+        let result = Command::parse(&params.text)
+            .and_then(|command| self.execute(command, params, now_ms));
+
+        match result {
+            Ok(response) => response,
+            Err(reason) => Response::ephemeral(reason),
+        }
+    }
+
+    /**
+     * Answers a request for a board image with the HTTP status and body. The
+     * links are signed so they can only be made by the bot.
+     */
+    pub fn handle_board_image(&self, url: &str) -> (u16, Vec<u8>) {
+        let not_found = (404, b"Not found".to_vec());
+
+        let (path, query) = match url.find('?') {
+            Some(idx) => (&url[..idx], &url[idx + 1..]),
+            None => return not_found,
+        };
+        let id: GameId = match path
+            .strip_prefix(BOARD_IMAGE_PATH)
+            .and_then(|file| file.strip_suffix(".png"))
+            .and_then(|id| id.parse().ok())
+        {
+            Some(id) => id,
+            None => return not_found,
+        };
+
+        let (signed, signature) = match query.rfind("&sig=") {
+            Some(idx) => (&url[..path.len() + 1 + idx], &query[idx + 5..]),
+            None => return not_found,
+        };
+        if signature::verify_url(&self.signing_secret, signed, signature).is_err() {
+            return (403, b"Link signature does not match".to_vec());
+        }
+
+        let record = match self.store.load(id) {
+            Ok(record) => record,
+            Err(StoreError::NotFound(_)) => return not_found,
+            Err(err) => {
+                eprintln!("{}", err);
+                return (500, b"Could not load the game".to_vec());
+            }
+        };
+
+        // Tiles are only highlighted on the version of the game they were
+        // played in
+        let fields: HashMap<String, String> = form_urlencoded::parse(query.as_bytes()).into_owned().collect();
+        let mut options = ImageOptions::for_game(&record.game, None);
+        if fields.get("version") == Some(&record.version.to_string()) {
+            options.highlight = fields
+                .get("highlight")
+                .map(|points| points.split('-').filter_map(parse_point).collect())
+                .unwrap_or_default();
+        }
+
+        match render_png(&record.game.board, &options) {
+            Ok(png) => (200, png),
+            Err(err) => {
+                eprintln!("{}", err);
+                (500, b"Could not draw the board".to_vec())
+            }
+        }
+    }
+
+    /**
+     * The game as text with a board image when there is a public url,
+     * otherwise with an emoji board
+     */
+    fn render_game(&self, record: (GameId, u64), game: &Game, highlight: &[Point], ephemeral: bool) -> Response {
+        let public_url = match self.public_url {
+            Some(ref public_url) => public_url,
+            None => {
+                let text = render::render_game(game, highlight);
+                return if ephemeral { Response::ephemeral(text) } else { Response::in_channel(text) };
+            }
+        };
+
+        let (id, version) = record;
+        let highlight: Vec<String> = highlight
+            .iter()
+            .map(|point| format!("{}.{}", point.x, point.y))
+            .collect();
+        let url = format!(
+            "{}{}.png?version={}&highlight={}",
+            BOARD_IMAGE_PATH,
+            id,
+            version,
+            highlight.join("-")
+        );
+        let signature = signature::sign_url(&self.signing_secret, &url);
+
+        let text = render::render_players(game);
+        Response {
+            text,
+            ephemeral,
+            image_url: Some(format!("{}{}&sig={}", public_url, url, signature)),
+        }
+    }
+
+    /**
+     * The game in the channel that the user is playing in
+     */
+    fn find_game(&self, params: &CommandParams) -> Result<GameRecord, String> {
+        self.store
+            .list_by_channel(&params.team_id, &params.channel_id)
+            .map_err(storage_error)?
+            .into_iter()
+            .find(|record| {
+                // Players who quit are no longer in the game
+                record
+                    .game
+                    .player_by_id(&params.user_id)
+                    .is_some_and(|player| !player.resigned)
+            })
+            .ok_or_else(|| "Game not found".to_string())
+    }
+
+    fn execute(&self, command: Command, params: &CommandParams, now_ms: u64) -> Result<Response, String> {
+        match command {
+            Command::Help => Ok(Response::in_channel(render::render_help(&self.command_name))),
+            Command::Start { players } => {
+                if self.find_game(params).is_ok() {
+                    return Err("You are already in a game on this channel!".to_string());
+                }
+
+                let mut seats = vec![(&params.user_id[..], &params.user_name[..], PlayerKind::Human)];
+                seats.extend(
+                    players
+                        .iter()
+                        .map(|(id, name)| (&id[..], &name[..], PlayerKind::Human)),
+                );
+
+                let game = Game::with_players(&seats);
+                let record = self
+                    .store
+                    .create(&params.team_id, &params.channel_id, &game)
+                    .map_err(storage_error)?;
+
+                Ok(self.render_game((record.id, record.version), &game, &[], false))
+            }
+            Command::Board => {
+                let GameRecord { id, version, game, .. } = self.find_game(params)?;
+                Ok(self.render_game((id, version), &game, &[], true))
+            }
+            Command::Hand => {
+                let GameRecord { game, .. } = self.find_game(params)?;
+                let player_idx = game.player_idx_by_id(&params.user_id).unwrap();
+                let hand = render::render_tiles(&game.players[player_idx].hand);
+
+                Ok(Response::ephemeral(format!("Your hand: {}", hand)))
+            }
+            Command::Play { start, dir, word } => {
+                let GameRecord { id, version, mut game, .. } = self.find_game(params)?;
+                if game.player_idx_by_id(&params.user_id) != Some(game.get_current_player_idx()) {
+                    return Err("It is not your turn to play".to_string());
+                }
+
+                let before = game.clone();
+                let result = game
+                    .play_word_at(start, dir, &word, now_ms)
+                    .map_err(|err| err.to_string())?;
+                let version = self.store.save(id, version, &game).map_err(storage_error)?;
+
+                let placed: Vec<Point> = before
+                    .diff(&game)
+                    .placed_tiles()
+                    .into_iter()
+                    .map(|(point, _)| point)
+                    .collect();
+                let mut response = self.render_game((id, version), &game, &placed, false);
+                response.text = format!(
+                    "{}\n{}",
+                    render::render_play_word_result(&result, &params.user_name),
+                    response.text
+                );
+                Ok(response)
+            }
+            Command::Dict { word, mode } => match mode {
+                DictMode::Check => Ok(Response::ephemeral(render::render_lookup(&word, &lookup(&word)))),
+                DictMode::Make | DictMode::Match => {
+                    let mut query = WordQuery {
+                        limit: Some(FOUND_WORDS_LIMIT),
+                        ..WordQuery::default()
+                    };
+                    if mode == DictMode::Make {
+                        query.rack = Some(word);
+                        query.min_length = Some(2);
+                    } else {
+                        query.pattern = Some(word);
+                    }
+
+                    let words = find_words(&query).map_err(|err| err.to_string())?;
+                    Ok(Response::ephemeral(render::render_found_words(&words)))
+                }
+                DictMode::Hooks => {
+                    let hooks = hooks(&word).map_err(|err| err.to_string())?;
+                    Ok(Response::ephemeral(render::render_hooks(&word, &hooks)))
+                }
+            },
+            Command::Quit => {
+                let GameRecord { id, version, mut game, .. } = self.find_game(params)?;
+                let player_idx = game.player_idx_by_id(&params.user_id).unwrap();
+                game.resign_at(player_idx, now_ms).map_err(|err| err.to_string())?;
+
+                // The game only keeps going while there is someone left to
+                // play against
+                self.store.save(id, version, &game).map_err(storage_error)?;
+                if game.active_player_count() < 2 {
+                    self.store.archive(id).map_err(storage_error)?;
+                    Ok(Response::in_channel("Game has ended".to_string()))
+                } else {
+                    Ok(Response::in_channel(format!("{} has left the game", params.user_name)))
+                }
+            }
+        }
+    }
+}
+
+/**
+ * A point written as `x.y` in board image links
+ */
+fn parse_point(text: &str) -> Option<Point> {
+    let mut coordinates = text.split('.').map(|num| num.parse::<i32>().ok());
+    match (coordinates.next(), coordinates.next(), coordinates.next()) {
+        (Some(Some(x)), Some(Some(y)), None) => Some(Point::new(x, y)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use words_game_store::MemoryStore;
+
+    fn app() -> App {
+        App::new(Box::new(MemoryStore::new()), "secret", "/scrabbler")
+    }
+
+    fn params(user_id: &str, text: &str) -> CommandParams {
+        CommandParams {
+            team_id: "T1".to_string(),
+            channel_id: "C1".to_string(),
+            user_id: user_id.to_string(),
+            user_name: user_id.to_lowercase(),
+            command: "/scrabbler".to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn start_and_take_turns() {
+        let app = app();
+
+        let start = app.handle_command(&params("U1", "start <@U2|u2>"), 0);
+        assert!(!start.ephemeral);
+        assert!(start.text.contains("u1: 0 points [current turn]"));
+
+        let again = app.handle_command(&params("U2", "start <@U3|u3>"), 0);
+        assert_eq!(again.text, "You are already in a game on this channel!");
+
+        let not_yours = app.handle_command(&params("U2", "play ACTOR 7,7 right"), 0);
+        assert_eq!(not_yours.text, "It is not your turn to play");
+
+        let hand = app.handle_command(&params("U1", "hand"), 0);
+        assert!(hand.ephemeral);
+        assert_eq!(hand.text.matches(":scrabble-").count(), 10);
+    }
+
+    #[test]
+    fn quitting_ends_two_player_games() {
+        let app = app();
+        app.handle_command(&params("U1", "start <@U2|u2>"), 0);
+
+        let quit = app.handle_command(&params("U2", "quit"), 0);
+        assert_eq!(quit.text, "Game has ended");
+
+        let board = app.handle_command(&params("U1", "board"), 0);
+        assert_eq!(board.text, "Game not found");
+    }
+
+    #[test]
+    fn players_who_quit_leave_the_game() {
+        let app = app();
+        app.handle_command(&params("U1", "start <@U2|u2> <@U3|u3>"), 0);
+
+        let quit = app.handle_command(&params("U3", "quit"), 0);
+        assert_eq!(quit.text, "u3 has left the game");
+
+        let board = app.handle_command(&params("U3", "board"), 0);
+        assert_eq!(board.text, "Game not found");
+        let again = app.handle_command(&params("U3", "quit"), 0);
+        assert_eq!(again.text, "Game not found");
+
+        let board = app.handle_command(&params("U1", "board"), 0);
+        assert_ne!(board.text, "Game not found");
+    }
+
+    #[test]
+    fn dict_and_errors() {
+        let app = app();
+
+        assert_eq!(app.handle_command(&params("U1", "dict actor"), 0).text, "actor is valid");
+        assert_eq!(app.handle_command(&params("U1", "dict actorz"), 0).text, "actorz is not valid");
+        assert_eq!(app.handle_command(&params("U1", "dict match ?UIZ"), 0).text, "QUIZ");
+        assert!(app.handle_command(&params("U1", "dict make QAT"), 0).text.starts_with("QAT, "));
+        assert!(app.handle_command(&params("U1", "dict hooks are"), 0).text.starts_with("Before ARE: "));
+        assert_eq!(app.handle_command(&params("U1", "dance"), 0).text, "Command not recognized");
+
+        let mut other_command = params("U1", "help");
+        other_command.command = "/other".to_string();
+        assert!(app.handle_command(&other_command, 0).text.starts_with("Unsupported command"));
+    }
+
+    #[test]
+    fn board_images() {
+        let app = app().with_public_url("https://bot.example.com/");
+        let start = app.handle_command(&params("U1", "start <@U2|u2>"), 0);
+        assert_eq!(start.text, "u1: 0 points [current turn]\nu2: 0 points ");
+
+        let image_url = start.image_url.unwrap();
+        let path = image_url.strip_prefix("https://bot.example.com").unwrap();
+        assert!(path.starts_with("/boards/"));
+
+        let (status, png) = app.handle_board_image(path);
+        assert_eq!(status, 200);
+        assert_eq!(&png[1..4], b"PNG");
+
+        let tampered = path.replace(".png", "0.png");
+        assert_eq!(app.handle_board_image(&tampered).0, 403);
+        assert_eq!(app.handle_board_image("/boards/1.png").0, 404);
+    }
+
+    #[test]
+    fn response_json() {
+        let response = Response::ephemeral("hi".to_string());
+        let json: serde_json::Value = serde_json::from_str(&response.to_json()).unwrap();
+
+        assert_eq!(json["response_type"], "ephemeral");
+        assert_eq!(json["text"], "hi");
+    }
+}
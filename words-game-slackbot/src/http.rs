@@ -0,0 +1,75 @@
+use super::handler::App;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Slack posts slash commands here
+pub const COMMAND_PATH: &str = "/slack";
+
+/// Board images are served from `<BOARD_IMAGE_PATH><game id>.png`
+pub const BOARD_IMAGE_PATH: &str = "/boards/";
+
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn respond(request: Request, status: u16, body: String) {
+    let content_type = if status == 200 { "application/json" } else { "text/plain" };
+    respond_with(request, status, body.into_bytes(), content_type);
+}
+
+fn respond_with(request: Request, status: u16, body: Vec<u8>, content_type: &str) {
+    let header = Header::from_bytes("Content-Type", content_type).unwrap();
+    let response = Response::from_data(body)
+        .with_status_code(status)
+        .with_header(header);
+
+    if let Err(err) = request.respond(response) {
+        eprintln!("Could not send response: {}", err);
+    }
+}
+
+fn handle(app: &App, mut request: Request) {
+    if *request.method() == Method::Get && request.url().starts_with(BOARD_IMAGE_PATH) {
+        let (status, body) = app.handle_board_image(request.url());
+        let content_type = if status == 200 { "image/png" } else { "text/plain" };
+        return respond_with(request, status, body, content_type);
+    }
+
+    if *request.method() != Method::Post || request.url() != COMMAND_PATH {
+        return respond(request, 404, "Not found".to_string());
+    }
+
+    let mut body = vec![];
+    if request.as_reader().read_to_end(&mut body).is_err() {
+        return respond(request, 400, "Could not read the request".to_string());
+    }
+
+    let (status, response) = app.handle_request(
+        header(&request, "X-Slack-Request-Timestamp"),
+        header(&request, "X-Slack-Signature"),
+        &body,
+        now_ms(),
+    );
+
+    respond(request, status, response);
+}
+
+/**
+ * Answers requests one at a time until the server is shut down
+ */
+pub fn serve(server: &Server, app: &App) {
+    for request in server.incoming_requests() {
+        handle(app, request);
+    }
+}
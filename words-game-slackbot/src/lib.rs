@@ -0,0 +1,14 @@
+/*!
+ * Handles the `/scrabbler` Slack slash command without going through the
+ * Phoenix app. Slack posts each command to the server as a form, see
+ * `handler::App` for how a request is turned into a response.
+ */
+
+pub mod commands;
+pub mod handler;
+pub mod http;
+pub mod render;
+pub mod signature;
+
+pub use commands::{Command, DictMode};
+pub use handler::{App, CommandParams, Response};
@@ -0,0 +1,55 @@
+use std::env;
+use tiny_http::Server;
+use words_game::definitions::{self, Definitions};
+use words_game_slackbot::{http, App};
+use words_game_store::{FileStore, GameStore, MemoryStore, SqliteStore};
+
+const DEFAULT_ADDRESS: &str = "0.0.0.0:4000";
+const DEFAULT_COMMAND_NAME: &str = "/scrabbler";
+
+/**
+ * Uses `DATABASE_PATH` for a SQLite database if it is set, otherwise
+ * `GAMES_DIR` for JSON files, otherwise keeps games in memory
+ */
+fn open_store() -> Box<dyn GameStore> {
+    if let Ok(path) = env::var("DATABASE_PATH") {
+        return Box::new(SqliteStore::open(path).expect("Could not open the database"));
+    }
+
+    if let Ok(dir) = env::var("GAMES_DIR") {
+        return Box::new(FileStore::open(dir).expect("Could not open the games directory"));
+    }
+
+    Box::new(MemoryStore::new())
+}
+
+/**
+ * Definitions are optional, without them `dict` only says if a word is valid
+ */
+fn load_definitions() {
+    if let Ok(path) = env::var("DEFINITIONS_PATH") {
+        let definitions = Definitions::from_file(&path).expect("Could not load the definitions");
+        println!("Loaded definitions for {} words", definitions.len());
+        definitions::install(definitions);
+    }
+}
+
+fn main() {
+    let signing_secret = env::var("SLACK_SIGNING_SECRET").expect("SLACK_SIGNING_SECRET must be set");
+    let command_name = env::var("COMMAND_NAME").unwrap_or_else(|_| DEFAULT_COMMAND_NAME.to_string());
+    let address = env::var("PORT")
+        .map(|port| format!("0.0.0.0:{}", port))
+        .unwrap_or_else(|_| DEFAULT_ADDRESS.to_string());
+
+    load_definitions();
+
+    let server = Server::http(&address).expect("Could not start the server");
+    println!("Listening for {} on http://{}{}", command_name, address, http::COMMAND_PATH);
+
+    let mut app = App::new(open_store(), &signing_secret, &command_name);
+    if let Ok(public_url) = env::var("PUBLIC_URL") {
+        app = app.with_public_url(&public_url);
+    }
+
+    http::serve(&server, &app);
+}
@@ -0,0 +1,155 @@
+use words_game::definitions::Lookup;
+use words_game::finder::Hooks;
+use words_game::render::{BoardRenderer, SlackEmojiRenderer};
+use words_game::{Board, Game, PlayWordResult, Player, Point, Tile};
+
+pub fn render_tiles(tiles: &[Tile]) -> String {
+    SlackEmojiRenderer::tile_emojis(tiles)
+}
+
+/**
+ * The board with a row and column of coordinates along the top and left,
+ * pointing out the rows and columns of the highlighted tiles
+ */
+pub fn render_board(board: &Board, highlight: &[Point]) -> String {
+    SlackEmojiRenderer.render(board, highlight)
+}
+
+pub fn render_player(player: &Player, idx: usize, is_turn: bool) -> String {
+    format!(
+        "{}: {} points {}",
+        player.name(idx),
+        player.score,
+        if is_turn { "[current turn]" } else { "" }
+    )
+}
+
+pub fn render_players(game: &Game) -> String {
+    let current_player_idx = game.get_current_player_idx();
+
+    let players: Vec<String> = game
+        .players
+        .iter()
+        .enumerate()
+        .map(|(idx, player)| render_player(player, idx, idx == current_player_idx))
+        .collect();
+
+    players.join("\n")
+}
+
+pub fn render_game(game: &Game, highlight: &[Point]) -> String {
+    format!("{}\n{}", render_board(&game.board, highlight), render_players(game))
+}
+
+pub fn render_play_word_result(result: &PlayWordResult, player_name: &str) -> String {
+    let breakdown: Vec<String> = result.breakdown.iter().map(ToString::to_string).collect();
+
+    format!(
+        "{} played the words:\n{}\nFor {} points",
+        player_name,
+        breakdown.join("\n"),
+        result.score
+    )
+}
+
+/**
+ * Whether `word` is valid, followed by its definitions if there are any
+ */
+pub fn render_lookup(word: &str, lookup: &Lookup) -> String {
+    let mut lines = vec![format!("{} is {}", word, if lookup.valid { "valid" } else { "not valid" })];
+
+    for definition in &lookup.definitions {
+        let part_of_speech = match definition.part_of_speech {
+            Some(ref part_of_speech) => format!("_{}_ ", part_of_speech),
+            None => String::new(),
+        };
+        let lexicons = if definition.lexicons.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", definition.lexicons.join(", "))
+        };
+
+        lines.push(format!(">{}{}{}", part_of_speech, definition.definition, lexicons));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_found_words(words: &[String]) -> String {
+    if words.is_empty() {
+        "No words found".to_string()
+    } else {
+        words.join(", ")
+    }
+}
+
+pub fn render_hooks(word: &str, hooks: &Hooks) -> String {
+    let render_letters = |letters: &[char]| {
+        if letters.is_empty() {
+            "none".to_string()
+        } else {
+            letters.iter().map(char::to_string).collect::<Vec<String>>().join(" ")
+        }
+    };
+
+    format!(
+        "Before {0}: {1}\nAfter {0}: {2}",
+        word.to_uppercase(),
+        render_letters(&hooks.front),
+        render_letters(&hooks.back)
+    )
+}
+
+pub fn render_help(command_name: &str) -> String {
+    format!(
+        "Here is how you use the Words with Coworkers bot\n\
+         >`{0} help` - Brings up this help dialogue\n\
+         >`{0} start [tags]` - Start a game with the person you're chatting to\n\
+         \n\
+         in game:\n\
+         >`{0} board` - Show the state of the board\n\
+         >`{0} hand` - Shows you your hand\n\
+         >`{0} play <word> <x>:<y> <right|down>` - To play a word\n\
+         >`{0} play <word> <8H|H8>` - The same in Scrabble notation, row first to go right\n\
+         >`{0} dict <word>` - To check if a word is valid\n\
+         >`{0} dict make <letters>` - Words you can make from some letters, `?` for a blank\n\
+         >`{0} dict match <pattern>` - Words that fit a pattern like `?A??R`\n\
+         >`{0} dict hooks <word>` - Letters that can go before or after a word\n\
+         >`{0} quit` - Quit the current game",
+        command_name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use words_game::definitions::Definitions;
+    use words_game::BOARD_SIZE;
+
+    #[test]
+    fn board_uses_slack_emojis() {
+        let board = render_board(&Board::new(), &[]);
+        let lines: Vec<&str> = board.lines().collect();
+
+        assert_eq!(lines.len(), BOARD_SIZE as usize + 1);
+        assert!(lines[0].starts_with(":scrabble-board::zero::one:"));
+        assert!(lines[1].starts_with(":zero::scrabble-triple-word::scrabble-board:"));
+        assert!(lines[8].contains(":scrabble-start:"));
+    }
+
+    #[test]
+    fn lookups() {
+        let definitions = Definitions::parse("ZAX\tn\ta slate cutting tool\tTWL,CSW\n").unwrap();
+
+        assert_eq!(
+            render_lookup("zax", &definitions.lookup("zax")),
+            "zax is valid\n>_n_ a slate cutting tool (TWL, CSW)"
+        );
+        assert_eq!(render_lookup("zaxx", &definitions.lookup("zaxx")), "zaxx is not valid");
+    }
+
+    #[test]
+    fn tiles() {
+        assert_eq!(render_tiles(&[Tile::Letter('Q'), Tile::Blank]), ":scrabble-q::scrabble-blank:");
+    }
+}
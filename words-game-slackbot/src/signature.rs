@@ -0,0 +1,168 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Requests older than this are rejected so they can not be replayed
+pub const MAX_REQUEST_AGE_SECS: u64 = 5 * 60;
+
+const VERSION: &str = "v0";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureError {
+    MissingHeader(&'static str),
+    BadTimestamp,
+    Expired,
+    Mismatch,
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SignatureError::MissingHeader(header) => write!(f, "Missing header {}", header),
+            SignatureError::BadTimestamp => write!(f, "Request timestamp is not a number"),
+            SignatureError::Expired => write!(f, "Request is too old"),
+            SignatureError::Mismatch => write!(f, "Request signature does not match"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+fn mac(signing_secret: &str, timestamp: &str, body: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(signing_secret.as_bytes())
+        .expect("HMAC takes keys of any size");
+
+    mac.update(VERSION.as_bytes());
+    mac.update(b":");
+    mac.update(timestamp.as_bytes());
+    mac.update(b":");
+    mac.update(body);
+    mac
+}
+
+/**
+ * Links the bot hands out are signed with a different prefix than requests,
+ * so a signature for one can never be used as the other
+ */
+fn url_mac(signing_secret: &str, url: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(signing_secret.as_bytes())
+        .expect("HMAC takes keys of any size");
+
+    mac.update(b"url:");
+    mac.update(url.as_bytes());
+    mac
+}
+
+/**
+ * The signature for a link the bot hands out, so that only links it made
+ * are answered
+ */
+pub fn sign_url(signing_secret: &str, url: &str) -> String {
+    hex::encode(url_mac(signing_secret, url).finalize().into_bytes())
+}
+
+pub fn verify_url(signing_secret: &str, url: &str, signature: &str) -> Result<(), SignatureError> {
+    let digest = hex::decode(signature).map_err(|_| SignatureError::Mismatch)?;
+
+    url_mac(signing_secret, url)
+        .verify_slice(&digest)
+        .map_err(|_| SignatureError::Mismatch)
+}
+
+/**
+ * The `X-Slack-Signature` Slack would send for `body` at `timestamp`
+ */
+pub fn sign(signing_secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let digest = mac(signing_secret, timestamp, body).finalize().into_bytes();
+    format!("{}={}", VERSION, hex::encode(digest))
+}
+
+/**
+ * Checks that a request came from Slack, using the `X-Slack-Request-Timestamp`
+ * and `X-Slack-Signature` headers. `now` is the current unix time in seconds.
+ *
+ * See https://api.slack.com/authentication/verifying-requests-from-slack
+ */
+pub fn verify(
+    signing_secret: &str,
+    timestamp: Option<&str>,
+    signature: Option<&str>,
+    body: &[u8],
+    now: u64,
+) -> Result<(), SignatureError> {
+    let timestamp = timestamp.ok_or(SignatureError::MissingHeader("X-Slack-Request-Timestamp"))?;
+    let signature = signature.ok_or(SignatureError::MissingHeader("X-Slack-Signature"))?;
+
+    let sent_at: u64 = timestamp.parse().map_err(|_| SignatureError::BadTimestamp)?;
+    // Requests too far ahead of our clock are as suspect as old ones
+    if now.abs_diff(sent_at) > MAX_REQUEST_AGE_SECS {
+        return Err(SignatureError::Expired);
+    }
+
+    let digest = signature
+        .strip_prefix("v0=")
+        .and_then(|digest| hex::decode(digest).ok())
+        .ok_or(SignatureError::Mismatch)?;
+
+    // Compares in constant time
+    mac(signing_secret, timestamp, body)
+        .verify_slice(&digest)
+        .map_err(|_| SignatureError::Mismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from Slack's documentation
+    const SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+    const TIMESTAMP: &str = "1531420618";
+    const BODY: &str = "token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+    const SIGNATURE: &str = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503";
+
+    #[test]
+    fn matches_slack_example() {
+        assert_eq!(sign(SECRET, TIMESTAMP, BODY.as_bytes()), SIGNATURE);
+        assert_eq!(
+            verify(SECRET, Some(TIMESTAMP), Some(SIGNATURE), BODY.as_bytes(), 1531420618 + 10),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_bad_requests() {
+        let body = BODY.as_bytes();
+        let now = 1531420618;
+
+        assert_eq!(
+            verify(SECRET, Some(TIMESTAMP), Some(SIGNATURE), b"tampered", now),
+            Err(SignatureError::Mismatch)
+        );
+        assert_eq!(
+            verify("other secret", Some(TIMESTAMP), Some(SIGNATURE), body, now),
+            Err(SignatureError::Mismatch)
+        );
+        assert_eq!(
+            verify(SECRET, Some(TIMESTAMP), Some(SIGNATURE), body, now + 301),
+            Err(SignatureError::Expired)
+        );
+        assert_eq!(
+            verify(SECRET, Some(TIMESTAMP), Some(SIGNATURE), body, now - 301),
+            Err(SignatureError::Expired)
+        );
+        assert_eq!(
+            verify(SECRET, None, Some(SIGNATURE), body, now),
+            Err(SignatureError::MissingHeader("X-Slack-Request-Timestamp"))
+        );
+        assert_eq!(
+            verify(SECRET, Some("soon"), Some(SIGNATURE), body, now),
+            Err(SignatureError::BadTimestamp)
+        );
+        assert_eq!(
+            verify(SECRET, Some(TIMESTAMP), Some("v0=zz"), body, now),
+            Err(SignatureError::Mismatch)
+        );
+    }
+}
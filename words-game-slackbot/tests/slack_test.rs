@@ -0,0 +1,103 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tiny_http::Server;
+use words_game_slackbot::{http, signature, App};
+use words_game_store::MemoryStore;
+
+const SECRET: &str = "test-signing-secret";
+
+/**
+ * Starts the bot on a free port, standing in for the deployed server
+ */
+fn start_bot() -> SocketAddr {
+    let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+    let address = server.server_addr().to_ip().unwrap();
+    let app = App::new(Box::new(MemoryStore::new()), SECRET, "/scrabbler");
+
+    thread::spawn(move || http::serve(&server, &app));
+    address
+}
+
+fn payload(name: &str) -> String {
+    let path = format!("{}/tests/payloads/{}.txt", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(path).unwrap()
+}
+
+/**
+ * Posts `body` the way Slack does and returns the status and body of the
+ * response
+ */
+fn post(address: SocketAddr, body: &str, signature: Option<String>) -> (u16, String) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        .to_string();
+    let signature = signature.unwrap_or_else(|| signature::sign(SECRET, &timestamp, body.as_bytes()));
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "POST /slack HTTP/1.1\r\n\
+         Host: {}\r\n\
+         Content-Type: application/x-www-form-urlencoded\r\n\
+         X-Slack-Request-Timestamp: {}\r\n\
+         X-Slack-Signature: {}\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        address,
+        timestamp,
+        signature,
+        body.len(),
+        body
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let status = response[9..12].parse().unwrap();
+    let body = response.split("\r\n\r\n").nth(1).unwrap_or("").to_string();
+    (status, body)
+}
+
+fn text_of(body: &str) -> (String, String) {
+    let json: serde_json::Value = serde_json::from_str(body).unwrap();
+    (
+        json["response_type"].as_str().unwrap().to_string(),
+        json["text"].as_str().unwrap().to_string(),
+    )
+}
+
+#[test]
+fn recorded_commands() {
+    let address = start_bot();
+
+    let (status, body) = post(address, &payload("start"), None);
+    assert_eq!(status, 200);
+    let (response_type, text) = text_of(&body);
+    assert_eq!(response_type, "in_channel");
+    assert!(text.starts_with(":scrabble-board::zero:"));
+    assert!(text.contains("Steve: 0 points [current turn]"));
+    assert!(text.contains("maria: 0 points"));
+
+    let (_, body) = post(address, &payload("play_out_of_turn"), None);
+    assert_eq!(text_of(&body), ("ephemeral".to_string(), "It is not your turn to play".to_string()));
+
+    let (_, body) = post(address, &payload("hand"), None);
+    let (response_type, text) = text_of(&body);
+    assert_eq!(response_type, "ephemeral");
+    assert!(text.starts_with("Your hand: :scrabble-"));
+}
+
+#[test]
+fn unsigned_requests_are_rejected() {
+    let address = start_bot();
+
+    let (status, body) = post(address, &payload("start"), Some("v0=00".to_string()));
+    assert_eq!(status, 401);
+    assert_eq!(body, "Request signature does not match");
+}
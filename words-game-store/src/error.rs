@@ -0,0 +1,59 @@
+use super::GameId;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum StoreError {
+    NotFound(GameId),
+    /// The game was saved by someone else since it was loaded
+    VersionConflict {
+        id: GameId,
+        expected: u64,
+        actual: u64,
+    },
+    /// A stored game could not be read back
+    Corrupt(String),
+    Io(io::Error),
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+}
+
+pub type Result<T> = std::result::Result<T, StoreError>;
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StoreError::NotFound(id) => write!(f, "Game {} not found", id),
+            StoreError::VersionConflict { id, expected, actual } => write!(
+                f,
+                "Game {} was changed by someone else (expected version {}, found {})",
+                id, expected, actual
+            ),
+            StoreError::Corrupt(ref err) => write!(f, "Stored game is corrupt: {}", err),
+            StoreError::Io(ref err) => write!(f, "Storage error: {}", err),
+            #[cfg(feature = "sqlite")]
+            StoreError::Sqlite(ref err) => write!(f, "Database error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(err: io::Error) -> Self {
+        StoreError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(err: serde_json::Error) -> Self {
+        StoreError::Corrupt(err.to_string())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for StoreError {
+    fn from(err: rusqlite::Error) -> Self {
+        StoreError::Sqlite(err)
+    }
+}
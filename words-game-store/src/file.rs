@@ -0,0 +1,172 @@
+use super::{GameId, GameRecord, GameStore, Result, StoreError};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use words_game::Game;
+
+const EXTENSION: &str = "json";
+
+/**
+ * Keeps each game as a JSON file in a directory, named after its id. Files
+ * are replaced atomically, but the version checks are only enforced between
+ * users of the same `FileStore`, so only one process should use a
+ * directory at a time.
+ */
+pub struct FileStore {
+    dir: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileStore {
+    /**
+     * Uses `dir` for the games, creating it if needed
+     */
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<FileStore> {
+        fs::create_dir_all(dir.as_ref())?;
+
+        Ok(FileStore {
+            dir: dir.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        })
+    }
+
+    fn path(&self, id: GameId) -> PathBuf {
+        self.dir.join(format!("{}.{}", id, EXTENSION))
+    }
+
+    fn ids(&self) -> Result<Vec<GameId>> {
+        let mut ids = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION) {
+                if let Some(id) = path.file_stem().and_then(|stem| stem.to_str()?.parse().ok()) {
+                    ids.push(id);
+                }
+            }
+        }
+
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    fn read(&self, id: GameId) -> Result<GameRecord> {
+        let contents = fs::read_to_string(self.path(id)).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => StoreError::NotFound(id),
+            _ => err.into(),
+        })?;
+
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    fn write(&self, record: &GameRecord) -> Result<()> {
+        let path = self.path(record.id);
+        let tmp_path = path.with_extension("tmp");
+
+        fs::write(&tmp_path, serde_json::to_string(record)?)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    fn list<F: Fn(&GameRecord) -> bool>(&self, filter: F) -> Result<Vec<GameRecord>> {
+        let _lock = self.lock.lock().unwrap();
+
+        let mut records = vec![];
+        for id in self.ids()? {
+            let record = self.read(id)?;
+            if !record.archived && filter(&record) {
+                records.push(record);
+            }
+        }
+
+        Ok(records)
+    }
+}
+
+impl GameStore for FileStore {
+    fn create(&self, team_id: &str, channel_id: &str, game: &Game) -> Result<GameRecord> {
+        let _lock = self.lock.lock().unwrap();
+        let id = self.ids()?.last().map_or(1, |id| id + 1);
+
+        let record = GameRecord {
+            id,
+            team_id: team_id.to_string(),
+            channel_id: channel_id.to_string(),
+            version: 1,
+            archived: false,
+            game: game.clone(),
+        };
+        self.write(&record)?;
+
+        Ok(record)
+    }
+
+    fn load(&self, id: GameId) -> Result<GameRecord> {
+        let _lock = self.lock.lock().unwrap();
+        self.read(id)
+    }
+
+    fn save(&self, id: GameId, expected_version: u64, game: &Game) -> Result<u64> {
+        let _lock = self.lock.lock().unwrap();
+        let mut record = self.read(id)?;
+
+        if record.version != expected_version {
+            return Err(StoreError::VersionConflict {
+                id,
+                expected: expected_version,
+                actual: record.version,
+            });
+        }
+
+        record.version += 1;
+        record.game = game.clone();
+        self.write(&record)?;
+
+        Ok(record.version)
+    }
+
+    fn list_by_channel(&self, team_id: &str, channel_id: &str) -> Result<Vec<GameRecord>> {
+        self.list(|record| record.team_id == team_id && record.channel_id == channel_id)
+    }
+
+    fn list_by_team(&self, team_id: &str) -> Result<Vec<GameRecord>> {
+        self.list(|record| record.team_id == team_id)
+    }
+
+    fn archive(&self, id: GameId) -> Result<()> {
+        let _lock = self.lock.lock().unwrap();
+        let mut record = self.read(id)?;
+
+        record.archived = true;
+        self.write(&record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::conformance;
+
+    fn store(dir: &tempfile::TempDir) -> FileStore {
+        FileStore::open(dir.path().join("games")).unwrap()
+    }
+
+    #[test]
+    fn conforms() {
+        let dirs: Vec<_> = (0..3).map(|_| tempfile::tempdir().unwrap()).collect();
+
+        conformance::create_and_load(&store(&dirs[0]));
+        conformance::optimistic_saves(&store(&dirs[1]));
+        conformance::listing_and_archiving(&store(&dirs[2]));
+    }
+
+    #[test]
+    fn survives_reopening() {
+        let dir = tempfile::tempdir().unwrap();
+        let record = store(&dir).create("T1", "C1", &Game::new(2)).unwrap();
+
+        let reopened = store(&dir);
+        assert_eq!(reopened.load(record.id).unwrap().version, 1);
+        assert_eq!(reopened.create("T1", "C1", &Game::new(2)).unwrap().id, record.id + 1);
+    }
+}
@@ -0,0 +1,119 @@
+/*!
+ * Storage for games, shared by the Rust front-ends.
+ *
+ * Every saved game has a version that goes up by one on each save. Saving
+ * with a version other than the current one fails with
+ * `StoreError::VersionConflict`, so two requests that both loaded the same
+ * game can not overwrite each other's moves.
+ */
+
+mod error;
+mod file;
+mod memory;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use error::{Result, StoreError};
+pub use file::FileStore;
+pub use memory::MemoryStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+
+use serde::{Serialize, Deserialize};
+use words_game::Game;
+
+pub type GameId = u64;
+
+/**
+ * A stored game and where it is being played
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub id: GameId,
+    pub team_id: String,
+    pub channel_id: String,
+    pub version: u64,
+    /// Archived games are finished, they are kept but no longer listed
+    pub archived: bool,
+    pub game: Game,
+}
+
+pub trait GameStore: Send + Sync {
+    fn create(&self, team_id: &str, channel_id: &str, game: &Game) -> Result<GameRecord>;
+
+    fn load(&self, id: GameId) -> Result<GameRecord>;
+
+    /**
+     * Replaces the game if it is still at `expected_version`, returning the
+     * new version
+     */
+    fn save(&self, id: GameId, expected_version: u64, game: &Game) -> Result<u64>;
+
+    /// The games in a channel that have not been archived, oldest first
+    fn list_by_channel(&self, team_id: &str, channel_id: &str) -> Result<Vec<GameRecord>>;
+
+    /// The games in a team that have not been archived, oldest first
+    fn list_by_team(&self, team_id: &str) -> Result<Vec<GameRecord>>;
+
+    fn archive(&self, id: GameId) -> Result<()>;
+}
+
+#[cfg(test)]
+pub(crate) mod conformance {
+    //! Checks every backend behaves the same way
+
+    use super::*;
+
+    pub fn create_and_load(store: &dyn GameStore) {
+        let game = Game::new(2);
+        let record = store.create("T1", "C1", &game).unwrap();
+
+        assert_eq!(record.version, 1);
+        assert!(!record.archived);
+
+        let loaded = store.load(record.id).unwrap();
+        assert_eq!(loaded.team_id, "T1");
+        assert_eq!(loaded.channel_id, "C1");
+        assert_eq!(loaded.game.serialize(), game.serialize());
+
+        assert!(matches!(store.load(record.id + 100), Err(StoreError::NotFound(_))));
+    }
+
+    pub fn optimistic_saves(store: &dyn GameStore) {
+        let mut game = Game::new(2);
+        let record = store.create("T1", "C1", &game).unwrap();
+
+        game.players[0].score = 10;
+        assert_eq!(store.save(record.id, 1, &game).unwrap(), 2);
+
+        // A second writer that loaded version 1 loses
+        game.players[0].score = 20;
+        match store.save(record.id, 1, &game) {
+            Err(StoreError::VersionConflict { expected, actual, .. }) => {
+                assert_eq!((expected, actual), (1, 2));
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+
+        assert_eq!(store.load(record.id).unwrap().game.players[0].score, 10);
+        assert!(matches!(store.save(record.id + 100, 1, &game), Err(StoreError::NotFound(_))));
+    }
+
+    pub fn listing_and_archiving(store: &dyn GameStore) {
+        let game = Game::new(2);
+        let first = store.create("T1", "C1", &game).unwrap();
+        let second = store.create("T1", "C1", &game).unwrap();
+        store.create("T1", "C2", &game).unwrap();
+        store.create("T2", "C1", &game).unwrap();
+
+        let ids = |records: Vec<GameRecord>| records.iter().map(|record| record.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(store.list_by_channel("T1", "C1").unwrap()), vec![first.id, second.id]);
+        assert_eq!(store.list_by_team("T1").unwrap().len(), 3);
+
+        store.archive(first.id).unwrap();
+        assert_eq!(ids(store.list_by_channel("T1", "C1").unwrap()), vec![second.id]);
+        assert!(store.load(first.id).unwrap().archived);
+        assert!(matches!(store.archive(first.id + 100), Err(StoreError::NotFound(_))));
+    }
+}
@@ -0,0 +1,98 @@
+use super::{GameId, GameRecord, GameStore, Result, StoreError};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use words_game::Game;
+
+/**
+ * Keeps games in memory, for tests and for running without a database
+ */
+#[derive(Default)]
+pub struct MemoryStore {
+    records: Mutex<BTreeMap<GameId, GameRecord>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+
+    fn list<F: Fn(&GameRecord) -> bool>(&self, filter: F) -> Vec<GameRecord> {
+        let records = self.records.lock().unwrap();
+
+        records
+            .values()
+            .filter(|record| !record.archived && filter(record))
+            .cloned()
+            .collect()
+    }
+}
+
+impl GameStore for MemoryStore {
+    fn create(&self, team_id: &str, channel_id: &str, game: &Game) -> Result<GameRecord> {
+        let mut records = self.records.lock().unwrap();
+        let id = records.keys().next_back().map_or(1, |id| id + 1);
+
+        let record = GameRecord {
+            id,
+            team_id: team_id.to_string(),
+            channel_id: channel_id.to_string(),
+            version: 1,
+            archived: false,
+            game: game.clone(),
+        };
+        records.insert(id, record.clone());
+
+        Ok(record)
+    }
+
+    fn load(&self, id: GameId) -> Result<GameRecord> {
+        let records = self.records.lock().unwrap();
+        records.get(&id).cloned().ok_or(StoreError::NotFound(id))
+    }
+
+    fn save(&self, id: GameId, expected_version: u64, game: &Game) -> Result<u64> {
+        let mut records = self.records.lock().unwrap();
+        let record = records.get_mut(&id).ok_or(StoreError::NotFound(id))?;
+
+        if record.version != expected_version {
+            return Err(StoreError::VersionConflict {
+                id,
+                expected: expected_version,
+                actual: record.version,
+            });
+        }
+
+        record.version += 1;
+        record.game = game.clone();
+        Ok(record.version)
+    }
+
+    fn list_by_channel(&self, team_id: &str, channel_id: &str) -> Result<Vec<GameRecord>> {
+        Ok(self.list(|record| record.team_id == team_id && record.channel_id == channel_id))
+    }
+
+    fn list_by_team(&self, team_id: &str) -> Result<Vec<GameRecord>> {
+        Ok(self.list(|record| record.team_id == team_id))
+    }
+
+    fn archive(&self, id: GameId) -> Result<()> {
+        let mut records = self.records.lock().unwrap();
+        let record = records.get_mut(&id).ok_or(StoreError::NotFound(id))?;
+
+        record.archived = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::conformance;
+
+    #[test]
+    fn conforms() {
+        conformance::create_and_load(&MemoryStore::new());
+        conformance::optimistic_saves(&MemoryStore::new());
+        conformance::listing_and_archiving(&MemoryStore::new());
+    }
+}
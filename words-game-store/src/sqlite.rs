@@ -0,0 +1,199 @@
+use super::{GameId, GameRecord, GameStore, Result, StoreError};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::Path;
+use std::sync::Mutex;
+use words_game::Game;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS games (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        team_id TEXT NOT NULL,
+        channel_id TEXT NOT NULL,
+        version INTEGER NOT NULL,
+        archived INTEGER NOT NULL DEFAULT 0,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS games_by_channel ON games (team_id, channel_id);
+";
+
+const COLUMNS: &str = "id, team_id, channel_id, version, archived, data";
+
+/**
+ * Keeps games in a SQLite database. Saves are a single conditional update,
+ * so the version check also holds between processes sharing the file.
+ */
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+/**
+ * A row of the games table, before the game has been parsed
+ */
+struct StoredRow {
+    id: i64,
+    team_id: String,
+    channel_id: String,
+    version: i64,
+    archived: bool,
+    data: String,
+}
+
+impl StoredRow {
+    fn read(row: &Row) -> rusqlite::Result<StoredRow> {
+        Ok(StoredRow {
+            id: row.get(0)?,
+            team_id: row.get(1)?,
+            channel_id: row.get(2)?,
+            version: row.get(3)?,
+            archived: row.get(4)?,
+            data: row.get(5)?,
+        })
+    }
+
+    fn into_record(self) -> Result<GameRecord> {
+        Ok(GameRecord {
+            id: self.id as GameId,
+            team_id: self.team_id,
+            channel_id: self.channel_id,
+            version: self.version as u64,
+            archived: self.archived,
+            game: Game::from_serialized(&self.data)?,
+        })
+    }
+}
+
+impl SqliteStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<SqliteStore> {
+        SqliteStore::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<SqliteStore> {
+        SqliteStore::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<SqliteStore> {
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(SqliteStore {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn query(&self, filter: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<GameRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(&format!(
+            "SELECT {} FROM games WHERE archived = 0 AND {} ORDER BY id",
+            COLUMNS, filter
+        ))?;
+
+        let rows = statement
+            .query_map(params, StoredRow::read)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        rows.into_iter().map(StoredRow::into_record).collect()
+    }
+}
+
+impl GameStore for SqliteStore {
+    fn create(&self, team_id: &str, channel_id: &str, game: &Game) -> Result<GameRecord> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO games (team_id, channel_id, version, data) VALUES (?1, ?2, 1, ?3)",
+            params![team_id, channel_id, game.serialize()],
+        )?;
+
+        Ok(GameRecord {
+            id: conn.last_insert_rowid() as GameId,
+            team_id: team_id.to_string(),
+            channel_id: channel_id.to_string(),
+            version: 1,
+            archived: false,
+            game: game.clone(),
+        })
+    }
+
+    fn load(&self, id: GameId) -> Result<GameRecord> {
+        let conn = self.conn.lock().unwrap();
+        let row = conn
+            .query_row(
+                &format!("SELECT {} FROM games WHERE id = ?1", COLUMNS),
+                params![id as i64],
+                StoredRow::read,
+            )
+            .optional()?;
+
+        row.ok_or(StoreError::NotFound(id))?.into_record()
+    }
+
+    fn save(&self, id: GameId, expected_version: u64, game: &Game) -> Result<u64> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE games SET data = ?1, version = version + 1 WHERE id = ?2 AND version = ?3",
+            params![game.serialize(), id as i64, expected_version as i64],
+        )?;
+
+        if updated == 1 {
+            return Ok(expected_version + 1);
+        }
+
+        let actual: Option<i64> = conn
+            .query_row("SELECT version FROM games WHERE id = ?1", params![id as i64], |row| row.get(0))
+            .optional()?;
+
+        match actual {
+            Some(actual) => Err(StoreError::VersionConflict {
+                id,
+                expected: expected_version,
+                actual: actual as u64,
+            }),
+            None => Err(StoreError::NotFound(id)),
+        }
+    }
+
+    fn list_by_channel(&self, team_id: &str, channel_id: &str) -> Result<Vec<GameRecord>> {
+        self.query("team_id = ?1 AND channel_id = ?2", &[&team_id, &channel_id])
+    }
+
+    fn list_by_team(&self, team_id: &str) -> Result<Vec<GameRecord>> {
+        self.query("team_id = ?1", &[&team_id])
+    }
+
+    fn archive(&self, id: GameId) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute("UPDATE games SET archived = 1 WHERE id = ?1", params![id as i64])?;
+
+        if updated == 0 {
+            return Err(StoreError::NotFound(id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::conformance;
+
+    #[test]
+    fn conforms() {
+        conformance::create_and_load(&SqliteStore::open_in_memory().unwrap());
+        conformance::optimistic_saves(&SqliteStore::open_in_memory().unwrap());
+        conformance::listing_and_archiving(&SqliteStore::open_in_memory().unwrap());
+    }
+
+    #[test]
+    fn shared_file_detects_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("games.db");
+        let first = SqliteStore::open(&path).unwrap();
+        let second = SqliteStore::open(&path).unwrap();
+
+        let record = first.create("T1", "C1", &Game::new(2)).unwrap();
+        second.save(record.id, 1, &Game::new(2)).unwrap();
+
+        assert!(matches!(
+            first.save(record.id, 1, &Game::new(2)),
+            Err(StoreError::VersionConflict { actual: 2, .. })
+        ));
+    }
+}
@@ -36,23 +36,57 @@ impl Into<words_game::Direction> for Direction {
 pub struct PlayWordResult {
     score: u32,
     words: Vec<String>,
+    breakdown: Vec<words_game::WordScore>,
+}
+
+#[wasm_bindgen]
+impl PlayWordResult {
+    #[wasm_bindgen(getter)]
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn words(&self) -> Vec<JsValue> {
+        self.words.iter().map(|word| JsValue::from_str(word)).collect()
+    }
+
+    /**
+     * One line per formed word, like `ACTOR: A(1) C(3) T(1) O(1) R(1x2) = 8`
+     */
+    pub fn describe(&self) -> Vec<JsValue> {
+        self.breakdown.iter().map(|word| JsValue::from_str(&word.to_string())).collect()
+    }
+
+    /**
+     * The full per letter breakdown as JSON
+     */
+    pub fn breakdown(&self) -> String {
+        serde_json::to_string(&self.breakdown).unwrap()
+    }
 }
 
 impl From<words_game::PlayWordResult> for PlayWordResult {
     fn from(other: words_game::PlayWordResult) -> Self {
-        Self { score: other.score, words: other.words}
+        Self { score: other.score, words: other.words, breakdown: other.breakdown }
     }
 }
 
 #[wasm_bindgen]
 pub struct Player {
     pub score: u32,
+    pub is_bot: bool,
+    pub resigned: bool,
     /*
-     * Skip this field because it is not copy, we manually
+     * Skip these fields because they are not copy, we manually
      * define our own getters and setters
      */
     #[wasm_bindgen(skip)]
     pub hand: String,
+    #[wasm_bindgen(skip)]
+    pub id: Option<String>,
+    #[wasm_bindgen(skip)]
+    pub display_name: Option<String>,
 }
 
 #[wasm_bindgen]
@@ -61,6 +95,16 @@ impl Player {
     pub fn hand(&self) -> String {
         self.hand.clone()
     }
+
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> Option<String> {
+        self.id.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn display_name(&self) -> Option<String> {
+        self.display_name.clone()
+    }
 }
 
 impl From<&words_game::Player> for Player {
@@ -69,7 +113,14 @@ impl From<&words_game::Player> for Player {
                              .map(|x| Into::<char>::into(*x))
                              .collect();
 
-        Self {score: other.score, hand }
+        Self {
+            score: other.score,
+            is_bot: other.kind == words_game::PlayerKind::Bot,
+            resigned: other.resigned,
+            hand,
+            id: other.id.clone(),
+            display_name: other.display_name.clone(),
+        }
     }
 }
 
@@ -83,6 +134,10 @@ pub struct Board {
 
 #[wasm_bindgen]
 impl Board {
+    /**
+     * One character per square, and tiles followed by the square they
+     * cover, like `E3` for an E on a triple word or `E.` on a plain square
+     */
     #[wasm_bindgen(getter)]
     pub fn cells(&self) -> String {
         self.cells.clone()
@@ -93,7 +148,7 @@ impl From<&words_game::Board> for Board {
     fn from(other: &words_game::Board) -> Self {
         Self {
             board_dimension: words_game::BOARD_SIZE,
-            cells: other.cells.iter().map(Into::<char>::into).collect()
+            cells: other.encode_cells()
         }
     }
 }
@@ -112,6 +167,44 @@ impl Game {
         Self(words_game::Game::new(player_count))
     }
 
+    /**
+     * A game in the language with the code `language`, e.g. `es`
+     */
+    pub fn with_language(player_count: usize, language: &str) -> Result<Game> {
+        utils::set_panic_hook();
+        let language: words_game::Language = language.parse().map_err(err_mapper)?;
+        Ok(Self(words_game::Game::with_language(player_count, language)))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn language(&self) -> String {
+        self.0.language.code().to_string()
+    }
+
+    /**
+     * Sets the id and display name of the player at `idx`
+     */
+    pub fn set_player_identity(&mut self, idx: usize, id: &str, display_name: &str) -> Result<()> {
+        let player = self.0.players.get_mut(idx)
+                        .ok_or_else(|| JsValue::from_str("Player does not exist"))?;
+
+        player.id = Some(id.to_string());
+        player.display_name = Some(display_name.to_string());
+        Ok(())
+    }
+
+    pub fn player_idx_by_id(&self, id: &str) -> Option<usize> {
+        self.0.player_idx_by_id(id)
+    }
+
+    pub fn add_player(&mut self) -> Result<usize> {
+        self.0.add_player().map_err(err_mapper)
+    }
+
+    pub fn resign(&mut self, player_idx: usize) -> Result<()> {
+        self.0.resign(player_idx).map_err(err_mapper)
+    }
+
     pub fn get_current_player_idx(&mut self) -> usize {
         self.0.get_current_player_idx()
     }
@@ -135,6 +228,65 @@ impl Game {
         }
     }
 
+    /**
+     * Times are in milliseconds, timestamps are the value of `Date.now()`
+     */
+    pub fn set_time_control(
+        &mut self,
+        initial_ms: f64,
+        increment_ms: f64,
+        overtime_penalty_per_minute: u32,
+        now: f64
+    ) {
+        self.0.set_time_control(
+            words_game::TimeControl::new(
+                initial_ms as u64,
+                increment_ms as u64,
+                overtime_penalty_per_minute
+            ),
+            now as u64
+        )
+    }
+
+    pub fn tick(&mut self, now: f64) {
+        self.0.tick(now as u64)
+    }
+
+    pub fn time_remaining(&self, player_idx: usize, now: f64) -> Option<f64> {
+        self.0.time_remaining(player_idx, now as u64).map(|ms| ms as f64)
+    }
+
+    pub fn apply_time_penalties(&mut self, now: f64) -> Result<Vec<u32>> {
+        self.0.apply_time_penalties(now as u64).map_err(err_mapper)
+    }
+
+    /**
+     * Returns the game as `player_idx` sees it as JSON, without the other
+     * players' hands or the tile bag. Use this for anything sent to a client.
+     */
+    pub fn view_for(&self, player_idx: usize) -> Result<String> {
+        self.0.view_for(player_idx)
+            .map(|view| view.serialize())
+            .map_err(err_mapper)
+    }
+
+    /**
+     * Returns the tiles `player_idx` has not seen as JSON, with the count of
+     * each letter and how many are left in the bag
+     */
+    pub fn unseen_tiles(&self, player_idx: usize) -> String {
+        serde_json::to_string(&self.0.unseen_tiles(player_idx)).unwrap()
+    }
+
+    /**
+     * Returns the board as an SVG with everyone's scores, and the rack of
+     * `player_idx` if given. The page needs to load the Bungee font.
+     */
+    pub fn board_svg(&self, player_idx: Option<usize>) -> String {
+        let options = words_game::image::ImageOptions::for_game(&self.0, player_idx);
+        words_game::image::render_svg(&self.0.board, &options)
+    }
+
     #[wasm_bindgen(getter)]
     pub fn players(&self) -> Vec<JsValue> {
         self.0.players.iter().map(|x| JsValue::from(Player::from(x))).collect()
@@ -155,9 +307,88 @@ impl Game {
 
         Ok(Self(board))
     }
+
+    /**
+     * Returns a JSON encoded diff that turns this game into `other`
+     */
+    pub fn diff(&self, other: &Game) -> String {
+        serde_json::to_string(&self.0.diff(&other.0)).unwrap()
+    }
+
+    pub fn apply_patch(&mut self, patch: &str) -> Result<()> {
+        let patch: words_game::GameDiff = serde_json::from_str(patch)
+                        .map_err(err_mapper)?;
+
+        self.0.apply_patch(&patch).map_err(err_mapper)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Game> {
+        let game = words_game::Game::from_bytes(bytes)
+                        .map_err(err_mapper)?;
+
+        Ok(Self(game))
+    }
 }
 
 #[wasm_bindgen]
 pub fn check_dictionary(s: &str) -> bool {
     words_game::check_dictionary(s)
 }
+
+/**
+ * Searches the dictionary. `query` is a `WordQuery` as JSON, e.g.
+ * `{"rack": "RETAINS", "min_length": 7}` or `{"pattern": "?A??R"}`, and the
+ * matching words are returned as a JSON array.
+ */
+#[wasm_bindgen]
+pub fn find_words(query: &str) -> Result<String> {
+    let query: words_game::finder::WordQuery = serde_json::from_str(query).map_err(err_mapper)?;
+
+    words_game::finder::find_words(&query)
+        .map(|words| serde_json::to_string(&words).unwrap())
+        .map_err(err_mapper)
+}
+
+/**
+ * Returns the letters that can go before and after `word` as JSON,
+ * `{"front": [...], "back": [...]}`
+ */
+#[wasm_bindgen]
+pub fn hooks(word: &str) -> Result<String> {
+    words_game::finder::hooks(word)
+        .map(|hooks| serde_json::to_string(&hooks).unwrap())
+        .map_err(err_mapper)
+}
+
+/**
+ * Installs the word list for `language`, one word per line. Returns how
+ * many words it has.
+ */
+#[wasm_bindgen]
+pub fn install_dictionary(language: &str, contents: &str) -> Result<usize> {
+    let language: words_game::Language = language.parse().map_err(err_mapper)?;
+    words_game::language::install_dictionary(language, contents).map_err(err_mapper)
+}
+
+/**
+ * Loads definitions for `lookup` from the contents of a definitions file
+ */
+#[wasm_bindgen]
+pub fn load_definitions(contents: &str) -> Result<()> {
+    let definitions = words_game::definitions::Definitions::parse(contents).map_err(err_mapper)?;
+    words_game::definitions::install(definitions);
+    Ok(())
+}
+
+/**
+ * Returns whether `word` is valid and its definitions as JSON,
+ * `{"word": ..., "valid": ..., "definitions": [...]}`
+ */
+#[wasm_bindgen]
+pub fn lookup(word: &str) -> String {
+    serde_json::to_string(&words_game::definitions::lookup(word)).unwrap()
+}
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 use std::str;
+use super::normalize::normalize_word;
 
 pub static HAND_SIZE: usize = 10;
 
@@ -31,13 +32,14 @@ pub static BOARD: &str = "\
 
 pub static BOARD_SIZE: u32 = 15;
 
+/// The word list, one uppercase word per line in sorted order
+pub static DICTIONARY_WORDS: &str = include_str!("dictionary.txt");
+
 lazy_static! {
     pub static ref DICTIONARY: HashSet<&'static str> = {
         let mut dict = HashSet::new();
 
-        let words = include_str!("dictionary.txt");
-
-        for line in words.lines() {
+        for line in DICTIONARY_WORDS.lines() {
             dict.insert(line);
         }
 
@@ -45,8 +47,13 @@ lazy_static! {
     };
 }
 
+/**
+ * Checks a word typed by a player, it is normalized first so any case is
+ * accepted. Anything that isn't a word made of letters is not in the
+ * dictionary
+ */
 pub fn check_dictionary(s: &str) -> bool {
-    DICTIONARY.contains(s)
+    normalize_word(s).is_ok_and(|word| DICTIONARY.contains(&word[..]))
 }
 
 #[cfg(test)]
@@ -54,11 +61,20 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn dictionary_has_word() {
         assert_eq!(DICTIONARY.contains("MOTORCYCLE"), true);
     }
 
     #[test]
+    fn check_dictionary_normalizes_words() {
+        assert!(check_dictionary(" motorCycle "));
+        assert!(!check_dictionary("motorcycle!"));
+        assert!(!check_dictionary(""));
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn dictionary_no_word() {
         assert_eq!(DICTIONARY.contains("BLAHBLAHBLAH"), false);
     }
@@ -0,0 +1,178 @@
+//! Optional definitions for dictionary words, so players can see what a
+//! word means and which word lists it is in.
+//!
+//! The word list has no definitions, so they are loaded from a separate
+//! file with one meaning per line and its fields separated by tabs. The
+//! fields are the word, its part of speech (which can be empty), the
+//! definition and optionally the lexicons it is in. See
+//! `definitions.example.tsv` for a small example.
+
+use super::constants::check_dictionary;
+use super::error::{Error, Result};
+use super::normalize::normalize_word;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Definition {
+    pub part_of_speech: Option<String>,
+    pub definition: String,
+    /// Word lists the word is in for this meaning, e.g. `TWL` or `CSW`
+    pub lexicons: Vec<String>,
+}
+
+/**
+ * What is known about a word
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lookup {
+    pub word: String,
+    /// Whether the word can be played
+    pub valid: bool,
+    /// Empty when no definitions are loaded for the word
+    pub definitions: Vec<Definition>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Definitions {
+    entries: HashMap<String, Vec<Definition>>,
+}
+
+lazy_static! {
+    static ref DEFINITIONS: RwLock<Definitions> = RwLock::new(Definitions::default());
+}
+
+impl Definitions {
+    pub fn new() -> Definitions {
+        Definitions::default()
+    }
+
+    pub fn parse(contents: &str) -> Result<Definitions> {
+        let mut definitions = Definitions::new();
+
+        for (line_no, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let bad_line = || Error::InvalidDefinitions(format!("bad entry on line {}", line_no + 1));
+
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let (word, part_of_speech, definition, lexicons) = match fields.as_slice() {
+                [word, part_of_speech, definition] => (word, part_of_speech, definition, &""),
+                [word, part_of_speech, definition, lexicons] => (word, part_of_speech, definition, lexicons),
+                _ => return Err(bad_line().into()),
+            };
+
+            let word = normalize_word(word).map_err(|_| bad_line())?;
+            if definition.is_empty() {
+                return Err(bad_line().into());
+            }
+
+            definitions
+                .entries
+                .entry(word)
+                .or_default()
+                .push(Definition {
+                    part_of_speech: Some(part_of_speech.to_string()).filter(|pos| !pos.is_empty()),
+                    definition: definition.to_string(),
+                    lexicons: lexicons
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|lexicon| !lexicon.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                });
+        }
+
+        Ok(definitions)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Definitions> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| Error::InvalidDefinitions(err.to_string()))?;
+
+        Definitions::parse(&contents)
+    }
+
+    /**
+     * The number of words with definitions
+     */
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn lookup(&self, word: &str) -> Lookup {
+        let word = normalize_word(word).unwrap_or_else(|_| word.trim().to_uppercase());
+
+        Lookup {
+            valid: check_dictionary(&word),
+            definitions: self.entries.get(&word).cloned().unwrap_or_default(),
+            word,
+        }
+    }
+}
+
+/**
+ * Makes `definitions` the ones `lookup` uses, replacing any loaded before
+ */
+pub fn install(definitions: Definitions) {
+    *DEFINITIONS.write().unwrap() = definitions;
+}
+
+/**
+ * Looks up `word` in the definitions passed to `install`. Without any
+ * installed, only whether the word is valid is known.
+ */
+pub fn lookup(word: &str) -> Lookup {
+    DEFINITIONS.read().unwrap().lookup(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../definitions.example.tsv");
+
+    #[test]
+    fn parses_example_file() {
+        let definitions = Definitions::parse(EXAMPLE).unwrap();
+        let anal = definitions.lookup("anal");
+
+        assert!(anal.valid);
+        assert_eq!(anal.word, "ANAL");
+        assert_eq!(anal.definitions.len(), 2);
+        assert_eq!(anal.definitions[0].part_of_speech, Some("adj".to_string()));
+        assert_eq!(anal.definitions[0].lexicons, vec!["TWL", "CSW"]);
+
+        let unknown = definitions.lookup("ACTORZ");
+        assert!(!unknown.valid);
+        assert!(unknown.definitions.is_empty());
+    }
+
+    #[test]
+    fn optional_fields_and_errors() {
+        let definitions = Definitions::parse("zax\t\ta slate cutting tool\n").unwrap();
+        let zax = &definitions.lookup("ZAX").definitions[0];
+        assert_eq!(zax.part_of_speech, None);
+        assert!(zax.lexicons.is_empty());
+
+        assert!(Definitions::parse("ZAX a slate cutting tool").is_err());
+        assert!(Definitions::parse("Z4X\tn\ta slate cutting tool").is_err());
+        assert!(Definitions::parse("ZAX\tn\t").is_err());
+    }
+
+    #[test]
+    fn installed_definitions() {
+        install(Definitions::parse(EXAMPLE).unwrap());
+
+        assert_eq!(lookup("qat").definitions.len(), 1);
+    }
+}
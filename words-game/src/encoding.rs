@@ -0,0 +1,546 @@
+use super::constants::BOARD_SIZE;
+use super::error::{Error, Result};
+use super::language::Language;
+use super::models::coordinates::points;
+use super::models::{Board, BoardCell, Game, Player, PlayerKind, Point, Square, Tile, TileBag, TimeControl};
+
+/*
+ * Compact binary encoding of a Game.
+ *
+ * Layout (version 1):
+ *   u8      version
+ *   u8      flags (bit 0: has_word_been_played, bit 1: timed, bit 2: the
+ *           board has its own layout)
+ *   varint  turn
+ *   varint  seat offset
+ *   varint  scoreless turns
+ *   u8      language, its index in `Language::ALL`
+ *   varint  when timed: initial time, increment, overtime penalty and the
+ *           time the clock was last updated plus one (zero if stopped)
+ *   [u8]    with its own layout, the square of every cell as a code from
+ *           `SQUARES`, two cells to a byte with the first in the low bits
+ *   [u8]    occupancy bitmap of the board, one bit per cell
+ *   [u8]    one tile code for every occupied cell, in cell order
+ *   varint  player count, then per player:
+ *             varint score, tile list,
+ *             u8 flags (bit 0: has id, bit 1: has display name, bit 2: bot,
+ *                       bit 3: resigned, bit 4: has clock),
+ *             the id and display name strings when present,
+ *             the remaining time as a zigzag varint when present
+ *   tiles   the tile bag, in draw order
+ *
+ * A tile list is a varint length followed by one tile code per tile, a
+ * string is a varint length followed by its UTF-8 bytes.
+ * Tile codes are 0 for a blank, 1..=26 for 'A'..='Z' and 27 onwards for the
+ * letters in `EXTRA_LETTERS`.
+ */
+const ENCODING_VERSION: u8 = 1;
+
+const SQUARES: [Square; 6] = [
+    Square::Empty,
+    Square::StartingSpot,
+    Square::DoubleLetter,
+    Square::TripleLetter,
+    Square::DoubleWord,
+    Square::TripleWord,
+];
+
+/// Letters of the other languages' tile sets, new ones can only be added at
+/// the end so that encoded games keep their meaning
+const EXTRA_LETTERS: [char; 16] = [
+    'Ñ', 'Ä', 'Ö', 'Ü', 'Ą', 'Ć', 'Ę', 'Ł', 'Ń', 'Ó', 'Ś', 'Ź', 'Ż',
+    '\u{E000}', '\u{E001}', '\u{E002}',
+];
+
+const FLAG_WORD_PLAYED: u8 = 1;
+const FLAG_TIMED: u8 = 1 << 1;
+const FLAG_LAYOUT: u8 = 1 << 2;
+
+const PLAYER_FLAG_ID: u8 = 1;
+const PLAYER_FLAG_NAME: u8 = 1 << 1;
+const PLAYER_FLAG_BOT: u8 = 1 << 2;
+const PLAYER_FLAG_RESIGNED: u8 = 1 << 3;
+const PLAYER_FLAG_CLOCK: u8 = 1 << 4;
+
+fn encoding_error(msg: &str) -> Box<Error> {
+    Error::InvalidEncoding(msg.to_string()).into()
+}
+
+fn tile_to_code(tile: Tile) -> u8 {
+    match tile {
+        Tile::Blank => 0,
+        Tile::Letter(letter @ 'A'..='Z') => letter as u8 - b'A' + 1,
+        Tile::Letter(letter) => match EXTRA_LETTERS.iter().position(|extra| *extra == letter) {
+            Some(idx) => 27 + idx as u8,
+            None => unreachable!("Trying to encode tile {}", letter),
+        },
+    }
+}
+
+fn code_to_tile(code: u8) -> Result<Tile> {
+    match code {
+        0 => Ok(Tile::Blank),
+        1..=26 => Ok(Tile::Letter((b'A' + code - 1) as char)),
+        _ => EXTRA_LETTERS
+            .get(code as usize - 27)
+            .map(|letter| Tile::Letter(*letter))
+            .ok_or_else(|| encoding_error("unknown tile code")),
+    }
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_signed_varint(buf: &mut Vec<u8>, value: i64) {
+    write_varint(buf, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+fn write_tiles(buf: &mut Vec<u8>, tiles: &[Tile]) {
+    write_varint(buf, tiles.len() as u64);
+    buf.extend(tiles.iter().map(|tile| tile_to_code(*tile)));
+}
+
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+    write_varint(buf, s.len() as u64);
+    buf.extend(s.as_bytes());
+}
+
+fn write_player(buf: &mut Vec<u8>, player: &Player) {
+    write_varint(buf, u64::from(player.score));
+    write_tiles(buf, &player.hand);
+
+    let mut flags = 0;
+    if player.id.is_some() {
+        flags |= PLAYER_FLAG_ID;
+    }
+    if player.display_name.is_some() {
+        flags |= PLAYER_FLAG_NAME;
+    }
+    if player.kind == PlayerKind::Bot {
+        flags |= PLAYER_FLAG_BOT;
+    }
+    if player.resigned {
+        flags |= PLAYER_FLAG_RESIGNED;
+    }
+    if player.time_remaining_ms.is_some() {
+        flags |= PLAYER_FLAG_CLOCK;
+    }
+    buf.push(flags);
+
+    if let Some(ref id) = player.id {
+        write_str(buf, id);
+    }
+    if let Some(ref name) = player.display_name {
+        write_str(buf, name);
+    }
+    if let Some(remaining) = player.time_remaining_ms {
+        write_signed_varint(buf, remaining);
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        ByteReader { bytes, pos: 0 }
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or_else(|| encoding_error("unexpected end of input"))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(encoding_error("varint is too long"))
+    }
+
+    fn read_signed_varint(&mut self) -> Result<i64> {
+        let value = self.read_varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let value = self.read_varint()?;
+        if value > u64::from(u32::MAX) {
+            return Err(encoding_error("value does not fit in 32 bits"));
+        }
+        Ok(value as u32)
+    }
+
+    fn read_tiles(&mut self) -> Result<Vec<Tile>> {
+        let len = self.read_varint()? as usize;
+        if len > self.bytes.len() - self.pos {
+            return Err(encoding_error("unexpected end of input"));
+        }
+
+        (0..len).map(|_| code_to_tile(self.read_u8()?)).collect()
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let len = self.read_varint()? as usize;
+        if len > self.bytes.len() - self.pos {
+            return Err(encoding_error("unexpected end of input"));
+        }
+
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| encoding_error("string is not UTF-8"))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+}
+
+fn read_player(reader: &mut ByteReader) -> Result<Player> {
+    let mut player = Player {
+        score: reader.read_u32()?,
+        hand: reader.read_tiles()?,
+        ..Player::default()
+    };
+
+    let flags = reader.read_u8()?;
+    if flags & PLAYER_FLAG_ID != 0 {
+        player.id = Some(reader.read_string()?);
+    }
+    if flags & PLAYER_FLAG_NAME != 0 {
+        player.display_name = Some(reader.read_string()?);
+    }
+    if flags & PLAYER_FLAG_BOT != 0 {
+        player.kind = PlayerKind::Bot;
+    }
+    player.resigned = flags & PLAYER_FLAG_RESIGNED != 0;
+    if flags & PLAYER_FLAG_CLOCK != 0 {
+        player.time_remaining_ms = Some(reader.read_signed_varint()?);
+    }
+
+    Ok(player)
+}
+
+fn bitmap_len(cell_count: usize) -> usize {
+    cell_count.div_ceil(8)
+}
+
+fn has_default_layout(board: &Board) -> bool {
+    let default = Board::new();
+    points().all(|point| board.square_at(point) == default.square_at(point))
+}
+
+fn write_layout(buf: &mut Vec<u8>, board: &Board) {
+    let codes: Vec<u8> = points()
+        .map(|point| {
+            let square = board.square_at(point).unwrap();
+            SQUARES.iter().position(|known| *known == square).unwrap() as u8
+        })
+        .collect();
+
+    for pair in codes.chunks(2) {
+        buf.push(pair[0] | pair.get(1).map_or(0, |code| code << 4));
+    }
+}
+
+fn read_layout(reader: &mut ByteReader) -> Result<Board> {
+    let cell_count = (BOARD_SIZE * BOARD_SIZE) as usize;
+    let mut cells = Vec::with_capacity(cell_count);
+
+    for _ in 0..cell_count.div_ceil(2) {
+        let byte = reader.read_u8()?;
+        for code in [byte & 0xf, byte >> 4] {
+            let square = SQUARES
+                .get(code as usize)
+                .ok_or_else(|| encoding_error("unknown square code"))?;
+            cells.push(BoardCell::from(*square));
+        }
+    }
+
+    cells.truncate(cell_count);
+    Ok(Board::from_cells(cells))
+}
+
+fn write_board(buf: &mut Vec<u8>, board: &Board) {
+    let cell_count = (BOARD_SIZE * BOARD_SIZE) as usize;
+    let mut bitmap = vec![0u8; bitmap_len(cell_count)];
+    let mut letters = Vec::new();
+
+    for (idx, point) in points().enumerate() {
+        if let Some(tile) = board.tile_at(point) {
+            bitmap[idx / 8] |= 1 << (idx % 8);
+            letters.push(tile_to_code(tile));
+        }
+    }
+
+    buf.extend(bitmap);
+    buf.extend(letters);
+}
+
+fn read_board(reader: &mut ByteReader, mut board: Board) -> Result<Board> {
+    let cell_count = (BOARD_SIZE * BOARD_SIZE) as usize;
+    let mut placements = Vec::new();
+
+    let mut bitmap = Vec::with_capacity(bitmap_len(cell_count));
+    for _ in 0..bitmap_len(cell_count) {
+        bitmap.push(reader.read_u8()?);
+    }
+
+    for idx in 0..cell_count {
+        if bitmap[idx / 8] & (1 << (idx % 8)) != 0 {
+            placements.push((Point::from_index(idx), code_to_tile(reader.read_u8()?)?));
+        }
+    }
+
+    board.place_tiles(&placements);
+
+    Ok(board)
+}
+
+impl Game {
+    /**
+     * Encodes the game into a compact binary form, usually a couple hundred
+     * bytes. Use `Game::from_bytes` to decode it.
+     */
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(256);
+
+        buf.push(ENCODING_VERSION);
+        let mut flags = 0;
+        if self.has_word_been_played {
+            flags |= FLAG_WORD_PLAYED;
+        }
+        if self.time_control.is_some() {
+            flags |= FLAG_TIMED;
+        }
+        let default_layout = has_default_layout(&self.board);
+        if !default_layout {
+            flags |= FLAG_LAYOUT;
+        }
+        buf.push(flags);
+
+        write_varint(&mut buf, u64::from(self.turn));
+        write_varint(&mut buf, u64::from(self.seat_offset));
+        write_varint(&mut buf, u64::from(self.scoreless_turns));
+        let language = Language::ALL.iter().position(|language| *language == self.language);
+        buf.push(language.unwrap() as u8);
+
+        if let Some(time_control) = self.time_control {
+            write_varint(&mut buf, time_control.initial_ms);
+            write_varint(&mut buf, time_control.increment_ms);
+            write_varint(&mut buf, u64::from(time_control.overtime_penalty_per_minute));
+            write_varint(&mut buf, self.clock_updated_at.map_or(0, |at| at + 1));
+        }
+
+        if !default_layout {
+            write_layout(&mut buf, &self.board);
+        }
+        write_board(&mut buf, &self.board);
+
+        write_varint(&mut buf, self.players.len() as u64);
+        for player in self.players.iter() {
+            write_player(&mut buf, player);
+        }
+
+        write_tiles(&mut buf, &self.tile_bag.tiles);
+
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Game> {
+        let mut reader = ByteReader::new(bytes);
+
+        if reader.read_u8()? != ENCODING_VERSION {
+            return Err(encoding_error("unsupported version"));
+        }
+
+        let flags = reader.read_u8()?;
+        let turn = reader.read_u32()?;
+        let seat_offset = reader.read_u32()?;
+        let scoreless_turns = reader.read_u32()?;
+        let language = *Language::ALL
+            .get(reader.read_u8()? as usize)
+            .ok_or_else(|| encoding_error("unknown language"))?;
+
+        let mut time_control = None;
+        let mut clock_updated_at = None;
+        if flags & FLAG_TIMED != 0 {
+            time_control = Some(TimeControl::new(
+                reader.read_varint()?,
+                reader.read_varint()?,
+                reader.read_u32()?,
+            ));
+            clock_updated_at = reader.read_varint()?.checked_sub(1);
+        }
+
+        let layout = if flags & FLAG_LAYOUT != 0 {
+            read_layout(&mut reader)?
+        } else {
+            Board::new()
+        };
+        let board = read_board(&mut reader, layout)?;
+
+        let player_count = reader.read_varint()? as usize;
+        let mut players = Vec::with_capacity(player_count.min(bytes.len()));
+        for _ in 0..player_count {
+            players.push(read_player(&mut reader)?);
+        }
+
+        let tile_bag = TileBag::from_tiles(reader.read_tiles()?);
+
+        if !reader.is_empty() {
+            return Err(encoding_error("trailing bytes after game"));
+        }
+
+        Ok(Game {
+            board,
+            players,
+            turn,
+            tile_bag,
+            has_word_been_played: flags & FLAG_WORD_PLAYED != 0,
+            seat_offset,
+            time_control,
+            clock_updated_at,
+            language,
+            scoreless_turns,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::models::{Direction, Point};
+
+    fn played_game() -> Game {
+        let mut game = Game::new(2);
+        game.players[0].hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+        game.play_word(Point::new(7, 7), Direction::right(), "ACTOR")
+            .unwrap();
+        game
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut game = played_game();
+        game.pass();
+        let decoded = Game::from_bytes(&game.to_bytes()).unwrap();
+
+        assert_eq!(decoded.serialize(), game.serialize());
+    }
+
+    #[test]
+    fn custom_layout_round_trip() {
+        let mut game = Game::new(2);
+        let layout = format!("3{}+{}", ".".repeat(111), ".".repeat(112));
+        game.board = Board::from_layout(&layout).unwrap();
+        game.players[0].hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+        game.play_word(Point::new(7, 7), Direction::right(), "ACTOR")
+            .unwrap();
+
+        let decoded = Game::from_bytes(&game.to_bytes()).unwrap();
+        assert_eq!(decoded.board.cells(), game.board.cells());
+        assert_eq!(decoded.serialize(), game.serialize());
+
+        let default = played_game().to_bytes();
+        assert_eq!(default[1] & FLAG_LAYOUT, 0);
+    }
+
+    #[test]
+    fn encoding_is_compact() {
+        let game = played_game();
+
+        assert!(game.to_bytes().len() < 200);
+        assert!(game.to_bytes().len() * 10 < game.serialize().len());
+    }
+
+    #[test]
+    fn truncated_input() {
+        let bytes = played_game().to_bytes();
+
+        assert!(Game::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Game::from_bytes(&[]).is_err());
+
+        let mut future = bytes.clone();
+        future[0] = ENCODING_VERSION + 1;
+        assert!(Game::from_bytes(&future).is_err());
+    }
+
+    #[test]
+    fn player_identity_round_trip() {
+        let mut game = played_game();
+        game.players[0] = game.players[0].clone().with_identity("U1", "zoë");
+        game.players[1].kind = PlayerKind::Bot;
+        game.resign(1).unwrap();
+
+        let decoded = Game::from_bytes(&game.to_bytes()).unwrap();
+
+        assert_eq!(decoded.player_by_id("U1").unwrap().name(0), "zoë");
+        assert_eq!(decoded.players[1].id, None);
+        assert_eq!(decoded.players[1].kind, PlayerKind::Bot);
+        assert!(decoded.players[1].resigned);
+        assert_eq!(decoded.get_current_player_idx(), game.get_current_player_idx());
+    }
+
+    #[test]
+    fn time_control_round_trip() {
+        let mut game = played_game();
+        game.set_time_control(TimeControl::new(600_000, 5_000, 10), 1_000);
+        game.players[1].time_remaining_ms = Some(-61_000);
+
+        let decoded = Game::from_bytes(&game.to_bytes()).unwrap();
+
+        assert_eq!(decoded.time_control, game.time_control);
+        assert_eq!(decoded.clock_updated_at, Some(1_000));
+        assert_eq!(decoded.players[0].time_remaining_ms, Some(600_000));
+        assert_eq!(decoded.players[1].time_remaining_ms, Some(-61_000));
+    }
+
+    #[test]
+    fn language_round_trip() {
+        for language in Language::ALL.iter() {
+            let game = Game::with_language(2, *language);
+            let decoded = Game::from_bytes(&game.to_bytes()).unwrap();
+
+            assert_eq!(decoded.language, *language);
+            assert_eq!(decoded.serialize(), game.serialize());
+        }
+    }
+
+    #[test]
+    fn varint_round_trip() {
+        for value in [0u64, 1, 127, 128, 300, u64::from(u32::MAX)].iter() {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, *value);
+
+            assert_eq!(ByteReader::new(&buf).read_varint().unwrap(), *value);
+        }
+
+        for value in [0i64, -1, 1, -64, 64, i64::MIN, i64::MAX].iter() {
+            let mut buf = Vec::new();
+            write_signed_varint(&mut buf, *value);
+
+            assert_eq!(ByteReader::new(&buf).read_signed_varint().unwrap(), *value);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use super::error::{Error, Result};
+use super::language::Language;
+use super::models::{Game, Move, Tile};
+use super::movegen::generate_game_moves;
+use std::cmp::Reverse;
+use std::time::{Duration, Instant};
+
+/**
+ * Limits on how long the solver searches
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct EndgameConfig {
+    /// The most turns to look ahead, counting passes
+    pub max_depth: u32,
+    /// Deeper searches are abandoned once this runs out, the result of the
+    /// deepest finished search is used. The first ply is always searched.
+    pub time_budget: Duration,
+}
+
+impl Default for EndgameConfig {
+    fn default() -> Self {
+        EndgameConfig {
+            max_depth: 8,
+            time_budget: Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EndgameSolution {
+    /// The best line of play for both players, starting with the current
+    /// player's move
+    pub principal_variation: Vec<Move>,
+    /// How many points the current player gains over their opponent from
+    /// here to the end of the game, including the end of game adjustments
+    pub spread: i32,
+    /// The depth of the search that found the solution
+    pub depth: u32,
+    /// Whether every line was followed to the end of the game, in which case
+    /// the solution is exact
+    pub exact: bool,
+}
+
+fn rack_value(hand: &[Tile]) -> i32 {
+    hand.iter().map(|tile| tile.point_value() as i32).sum()
+}
+
+struct Search {
+    player_idx: usize,
+    opponent_idx: usize,
+    deadline: Option<Instant>,
+    hit_horizon: bool,
+}
+
+/**
+ * Outcome of a search from one position, relative to the player to move
+ */
+struct Line {
+    spread: i32,
+    moves: Vec<Move>,
+}
+
+impl Search {
+    /**
+     * Negamax with alpha-beta pruning. `opponent_passed` is set when the
+     * previous turn was a pass, a second pass in a row ends the game.
+     * Returns `None` when the time ran out.
+     */
+    fn negamax(
+        &mut self,
+        game: &Game,
+        depth: u32,
+        mut alpha: i32,
+        beta: i32,
+        opponent_passed: bool,
+        hint: &[Move],
+    ) -> Option<Line> {
+        if self.deadline.is_some_and(|deadline| Instant::now() > deadline) {
+            return None;
+        }
+
+        let me = game.get_current_player_idx();
+        let them = if me == self.player_idx { self.opponent_idx } else { self.player_idx };
+        let my_rack = rack_value(&game.players[me].hand);
+        let their_rack = rack_value(&game.players[them].hand);
+
+        if depth == 0 {
+            // Guess that neither player gets rid of their tiles
+            self.hit_horizon = true;
+            return Some(Line {
+                spread: their_rack - my_rack,
+                moves: vec![],
+            });
+        }
+
+        // Try the best line from the last search first, then the highest
+        // scoring plays, which makes the cutoffs happen early
+        let mut candidates = generate_game_moves(game);
+        candidates.sort_by_key(|candidate| Reverse(candidate.score));
+        let mut moves: Vec<Move> = candidates.iter().map(|candidate| candidate.to_move()).collect();
+        moves.push(Move::Pass);
+        if let Some(first) = hint.first() {
+            if let Some(pos) = moves.iter().position(|mv| mv == first) {
+                let mv = moves.remove(pos);
+                moves.insert(0, mv);
+            }
+        }
+
+        let mut best: Option<Line> = None;
+        for mv in moves.into_iter() {
+            let child_hint = match hint.first() {
+                Some(first) if *first == mv => &hint[1..],
+                _ => &[],
+            };
+
+            let line = if mv == Move::Pass {
+                if opponent_passed {
+                    // Both players passed, everyone loses what is left on
+                    // their rack
+                    Line {
+                        spread: their_rack - my_rack,
+                        moves: vec![mv],
+                    }
+                } else {
+                    let mut next = game.clone();
+                    next.pass();
+                    let reply = self.negamax(&next, depth - 1, -beta, -alpha, true, child_hint)?;
+                    Line {
+                        spread: -reply.spread,
+                        moves: prepend(mv, reply.moves),
+                    }
+                }
+            } else {
+                let mut next = game.clone();
+                let score = next.apply_move(&mv).ok()? as i32;
+
+                if next.players[me].hand.is_empty() {
+                    // Going out scores double the tiles left on the other
+                    // rack
+                    Line {
+                        spread: score + 2 * their_rack,
+                        moves: vec![mv],
+                    }
+                } else {
+                    let reply = self.negamax(&next, depth - 1, -beta, -alpha, false, child_hint)?;
+                    Line {
+                        spread: score - reply.spread,
+                        moves: prepend(mv, reply.moves),
+                    }
+                }
+            };
+
+            if best.as_ref().is_none_or(|best| line.spread > best.spread) {
+                alpha = alpha.max(line.spread);
+                best = Some(line);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+fn prepend(mv: Move, mut moves: Vec<Move>) -> Vec<Move> {
+    moves.insert(0, mv);
+    moves
+}
+
+/**
+ * Finds the best way for the current player to finish the game once the
+ * tile bag is empty. With the bag empty both racks are known, so this is a
+ * full search of both players' moves using iterative deepening.
+ *
+ * Only works for games with two players left.
+ */
+pub fn solve_endgame(game: &Game, config: &EndgameConfig) -> Result<EndgameSolution> {
+    if game.language != Language::English {
+        return Err(Error::BadAction("Endgames can only be solved in English".to_string()).into());
+    }
+
+    if !game.tile_bag.tiles.is_empty() {
+        return Err(Error::BadAction("The tile bag is not empty".to_string()).into());
+    }
+
+    if game.active_player_count() != 2 {
+        return Err(Error::BadAction("Endgames need exactly two players".to_string()).into());
+    }
+
+    let player_idx = game.get_current_player_idx();
+    let opponent_idx = (0..game.players.len())
+        .find(|idx| *idx != player_idx && !game.players[*idx].resigned)
+        .unwrap();
+
+    let deadline = Instant::now() + config.time_budget;
+    let mut solution: Option<EndgameSolution> = None;
+
+    for depth in 1..=config.max_depth.max(1) {
+        let mut search = Search {
+            player_idx,
+            opponent_idx,
+            deadline: if depth > 1 { Some(deadline) } else { None },
+            hit_horizon: false,
+        };
+
+        let hint = solution
+            .as_ref()
+            .map(|solution| solution.principal_variation.clone())
+            .unwrap_or_default();
+
+        let line = match search.negamax(game, depth, -i32::MAX, i32::MAX, false, &hint) {
+            Some(line) => line,
+            None => break,
+        };
+
+        let exact = !search.hit_horizon;
+        solution = Some(EndgameSolution {
+            principal_variation: line.moves,
+            spread: line.spread,
+            depth,
+            exact,
+        });
+
+        if exact {
+            break;
+        }
+    }
+
+    Ok(solution.unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::models::{Direction, Point};
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        s.chars().map(Tile::from).collect()
+    }
+
+    fn endgame(first: &str, second: &str) -> Game {
+        let mut game = Game::new(2);
+        game.players[0].hand = tiles("ACTORSEOYK");
+        game.play_word(Point::new(7, 7), Direction::right(), "ACTOR").unwrap();
+        game.pass();
+
+        game.tile_bag.tiles.clear();
+        game.players[0].hand = tiles(first);
+        game.players[1].hand = tiles(second);
+        game
+    }
+
+    #[test]
+    fn goes_out_when_it_can() -> Result<()> {
+        let game = endgame("S", "VV");
+        let solution = solve_endgame(&game, &EndgameConfig::default())?;
+
+        let mut copy = game.clone();
+        let score = copy.apply_move(&solution.principal_variation[0])? as i32;
+
+        assert!(solution.exact);
+        assert_eq!(solution.principal_variation.len(), 1);
+        assert!(copy.players[0].hand.is_empty());
+        assert_eq!(solution.spread, score + 16);
+        Ok(())
+    }
+
+    #[test]
+    fn stuck_racks_pass_out() -> Result<()> {
+        let game = endgame("Q", "V");
+        let solution = solve_endgame(&game, &EndgameConfig::default())?;
+
+        assert!(solution.exact);
+        assert_eq!(solution.principal_variation, vec![Move::Pass, Move::Pass]);
+        assert_eq!(solution.spread, 4 - 10);
+        Ok(())
+    }
+
+    #[test]
+    fn needs_an_empty_bag() {
+        let game = Game::new(2);
+
+        assert!(solve_endgame(&game, &EndgameConfig::default()).is_err());
+    }
+
+    #[test]
+    fn no_time_still_gives_a_move() -> Result<()> {
+        let game = endgame("SE", "QI");
+        let config = EndgameConfig {
+            max_depth: 6,
+            time_budget: Duration::from_secs(0),
+        };
+
+        let solution = solve_endgame(&game, &config)?;
+
+        assert_eq!(solution.depth, 1);
+        assert_eq!(solution.principal_variation.len(), 1);
+        Ok(())
+    }
+}
@@ -9,6 +9,17 @@ pub enum Error {
     StartingTileNotCovered,
     WordDoesNotIntersect,
     NoLettersUsed,
+    InvalidEncoding(String),
+    InvalidLeaveTable(String),
+    ImageRendering(String),
+    InvalidBoardLayout(String),
+    InvalidQuery(String),
+    InvalidDefinitions(String),
+    InvalidCharacter(char),
+    EmptyWord,
+    InvalidDictionary(String),
+    MissingDictionary(String),
+    InvalidCoordinate(String),
 }
 
 impl fmt::Display for Error {
@@ -20,6 +31,21 @@ impl fmt::Display for Error {
             Error::StartingTileNotCovered => write!(f, "Starting tile needs to be covered"),
             Error::WordDoesNotIntersect => write!(f, "Word does not intersect with another word"),
             Error::NoLettersUsed => write!(f, "You must use at least one letter"),
+            Error::InvalidEncoding(ref err) => write!(f, "Invalid game encoding: {}", err),
+            Error::InvalidLeaveTable(ref err) => write!(f, "Invalid leave table: {}", err),
+            Error::ImageRendering(ref err) => write!(f, "Could not render image: {}", err),
+            Error::InvalidBoardLayout(ref err) => write!(f, "Invalid board layout: {}", err),
+            Error::InvalidQuery(ref err) => write!(f, "Invalid word search: {}", err),
+            Error::InvalidDefinitions(ref err) => write!(f, "Invalid definitions: {}", err),
+            Error::InvalidCharacter(c) => write!(f, "{:?} is not a letter", c),
+            Error::EmptyWord => write!(f, "A word is needed"),
+            Error::InvalidDictionary(ref err) => write!(f, "Invalid dictionary: {}", err),
+            Error::MissingDictionary(ref language) => {
+                write!(f, "No dictionary is installed for {}", language)
+            }
+            Error::InvalidCoordinate(ref text) => {
+                write!(f, "{:?} is not a square, use a column A to O and a row 1 to 15 like H8", text)
+            }
         }
     }
 }
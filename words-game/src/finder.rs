@@ -0,0 +1,290 @@
+//! Searches of the dictionary for study tools: what a rack can make, what
+//! fits a pattern, and which letters hook onto a word.
+
+use super::constants::check_dictionary;
+use super::error::{Error, Result};
+use super::normalize::{normalize_letter, normalize_word};
+use super::trie::{Trie, TRIE};
+use serde::{Deserialize, Serialize};
+
+/// Stands for any letter in racks and patterns
+pub const WILDCARD: char = '?';
+
+/**
+ * Filters for `find_words`, every filter that is set has to match
+ */
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WordQuery {
+    /// Letters the words are made from, each used at most once, with `?`
+    /// for a blank
+    pub rack: Option<String>,
+    /// Letters at fixed positions with `?` for any letter, e.g. `?A??R`.
+    /// Words are the same length as the pattern.
+    pub pattern: Option<String>,
+    /// Letters that have to appear in the word
+    pub contains: Option<String>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    /// The most words to return, longest first
+    pub limit: Option<usize>,
+}
+
+/**
+ * The letters that can be put before or after a word to make another word
+ */
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Hooks {
+    pub front: Vec<char>,
+    pub back: Vec<char>,
+}
+
+fn letter_idx(letter: char) -> usize {
+    (letter as u8 - b'A') as usize
+}
+
+/**
+ * Normalizes the letters of `text`, allowing wildcards when
+ * `allow_wildcard` is set, and says which field was wrong if it fails
+ */
+fn normalize(field: &str, text: &str, allow_wildcard: bool) -> Result<Vec<char>> {
+    text.trim()
+        .chars()
+        .map(|c| {
+            if allow_wildcard && c == WILDCARD {
+                Ok(c)
+            } else {
+                normalize_letter(c).map_err(|_| {
+                    Error::InvalidQuery(format!("{} can not contain {:?}", field, c)).into()
+                })
+            }
+        })
+        .collect()
+}
+
+/**
+ * How many of each letter, with blanks counted separately
+ */
+#[derive(Debug, Clone, Default)]
+struct LetterCounts {
+    letters: [u8; 26],
+    blanks: u8,
+}
+
+impl LetterCounts {
+    fn new(chars: &[char]) -> LetterCounts {
+        let mut counts = LetterCounts::default();
+        for c in chars {
+            match *c {
+                WILDCARD => counts.blanks += 1,
+                c => counts.letters[letter_idx(c)] += 1,
+            }
+        }
+        counts
+    }
+}
+
+struct Search<'a> {
+    trie: &'a Trie,
+    pattern: Option<Vec<char>>,
+    rack: Option<LetterCounts>,
+    contains: [u8; 26],
+    min_length: usize,
+    max_length: usize,
+    words: Vec<String>,
+}
+
+impl<'a> Search<'a> {
+    fn contains_required(&self, word: &str) -> bool {
+        let counts = LetterCounts::new(&word.chars().collect::<Vec<char>>());
+        counts
+            .letters
+            .iter()
+            .zip(self.contains.iter())
+            .all(|(have, need)| have >= need)
+    }
+
+    fn visit(&mut self, node: usize, word: &mut String) {
+        if self.trie.is_terminal(node) && word.len() >= self.min_length && self.contains_required(word) {
+            self.words.push(word.clone());
+        }
+
+        let depth = word.len();
+        if depth >= self.max_length {
+            return;
+        }
+
+        let required = self.pattern.as_ref().map(|pattern| pattern[depth]);
+        for (letter, child) in self.trie.children(node) {
+            if required.is_some_and(|required| required != WILDCARD && required != letter) {
+                continue;
+            }
+
+            // Real tiles are used before blanks, a blank can always stand in
+            // for a letter later on
+            let used = match self.rack {
+                None => None,
+                Some(ref mut rack) if rack.letters[letter_idx(letter)] > 0 => {
+                    rack.letters[letter_idx(letter)] -= 1;
+                    Some(false)
+                }
+                Some(ref mut rack) if rack.blanks > 0 => {
+                    rack.blanks -= 1;
+                    Some(true)
+                }
+                Some(_) => continue,
+            };
+
+            word.push(letter);
+            self.visit(child, word);
+            word.pop();
+
+            if let Some(ref mut rack) = self.rack {
+                match used {
+                    Some(true) => rack.blanks += 1,
+                    _ => rack.letters[letter_idx(letter)] += 1,
+                }
+            }
+        }
+    }
+}
+
+/**
+ * The words matching every filter of `query`, longest first and then in
+ * alphabetical order
+ */
+pub fn find_words(query: &WordQuery) -> Result<Vec<String>> {
+    let rack = match query.rack {
+        Some(ref rack) => Some(normalize("rack", rack, true)?),
+        None => None,
+    };
+    let pattern = match query.pattern {
+        Some(ref pattern) => Some(normalize("pattern", pattern, true)?),
+        None => None,
+    };
+    let contains = normalize("contains", query.contains.as_deref().unwrap_or(""), false)?;
+
+    let mut max_length = query.max_length.unwrap_or(usize::MAX);
+    let mut min_length = query.min_length.unwrap_or(1).max(1);
+    if let Some(ref rack) = rack {
+        max_length = max_length.min(rack.len());
+    }
+    if let Some(ref pattern) = pattern {
+        max_length = max_length.min(pattern.len());
+        min_length = min_length.max(pattern.len());
+    }
+
+    let mut search = Search {
+        trie: &TRIE,
+        pattern,
+        rack: rack.map(|rack| LetterCounts::new(&rack)),
+        contains: LetterCounts::new(&contains).letters,
+        min_length,
+        max_length,
+        words: vec![],
+    };
+    search.visit(Trie::ROOT, &mut String::new());
+
+    let mut words = search.words;
+    words.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    if let Some(limit) = query.limit {
+        words.truncate(limit);
+    }
+
+    Ok(words)
+}
+
+/**
+ * The words that use every tile in `rack`, with `?` for a blank
+ */
+pub fn anagrams(rack: &str) -> Result<Vec<String>> {
+    find_words(&WordQuery {
+        rack: Some(rack.to_string()),
+        min_length: Some(rack.chars().count()),
+        ..WordQuery::default()
+    })
+}
+
+pub fn hooks(word: &str) -> Result<Hooks> {
+    let word = normalize_word(word)?;
+    let letters = || (b'A'..=b'Z').map(char::from);
+
+    Ok(Hooks {
+        front: letters()
+            .filter(|letter| check_dictionary(&format!("{}{}", letter, word)))
+            .collect(),
+        back: letters()
+            .filter(|letter| check_dictionary(&format!("{}{}", word, letter)))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rack_words() {
+        let words = find_words(&WordQuery {
+            rack: Some("retains".to_string()),
+            min_length: Some(7),
+            ..WordQuery::default()
+        })
+        .unwrap();
+        assert!(words.contains(&"NASTIER".to_string()));
+        assert!(words.contains(&"STAINER".to_string()));
+
+        let with_blank = anagrams("ZA?").unwrap();
+        assert!(with_blank.contains(&"ZAX".to_string()));
+        assert!(with_blank.contains(&"ADZ".to_string()));
+        assert!(with_blank.iter().all(|word| word.len() == 3));
+
+        let short = find_words(&WordQuery {
+            rack: Some("QAT".to_string()),
+            limit: Some(2),
+            ..WordQuery::default()
+        })
+        .unwrap();
+        assert_eq!(short, vec!["QAT", "AT"]);
+    }
+
+    #[test]
+    fn patterns_and_filters() {
+        let words = find_words(&WordQuery {
+            pattern: Some("?a??r".to_string()),
+            contains: Some("CT".to_string()),
+            ..WordQuery::default()
+        })
+        .unwrap();
+        assert!(words.contains(&"CATER".to_string()));
+        assert!(words
+            .iter()
+            .all(|word| word.len() == 5 && &word[1..2] == "A" && word.ends_with('R')));
+
+        let long = find_words(&WordQuery {
+            contains: Some("QZ".to_string()),
+            min_length: Some(8),
+            max_length: Some(9),
+            ..WordQuery::default()
+        })
+        .unwrap();
+        assert!(long.iter().all(|word| (8..=9).contains(&word.len())));
+        assert!(long.iter().all(|word| word.contains('Q') && word.contains('Z')));
+
+        assert!(find_words(&WordQuery {
+            rack: Some("AB1".to_string()),
+            ..WordQuery::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn hook_letters() {
+        let hooks = hooks("are").unwrap();
+
+        assert!(hooks.front.contains(&'C'));
+        assert!(hooks.back.contains(&'A'));
+        assert!(!hooks.back.contains(&'Q'));
+        assert!(super::hooks("a-b").is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! Board images, as SVG for the browser and PNG for chat attachments.
+//!
+//! Text uses the Bungee font that ships with the web app. SVGs only name the
+//! font, so the page showing them needs to load it, while PNGs are rendered
+//! with a copy of it built in.
+
+use super::constants::BOARD_SIZE;
+use super::language::{unit, Language};
+use super::models::{Board, BoardCell, Game, Point, Tile};
+use std::fmt::Write;
+
+const CELL_SIZE: u32 = 40;
+/// Space for the coordinates along the top and left of the board
+const GUTTER: u32 = 24;
+const FONT_FAMILY: &str = "Bungee, sans-serif";
+
+/**
+ * What to draw besides the board itself
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageOptions {
+    /// Tiles drawn with an outline, usually the ones placed by the last move
+    pub highlight: Vec<Point>,
+    /// A rack shown under the board
+    pub rack: Option<Vec<Tile>>,
+    /// Names and scores listed under the board
+    pub scores: Vec<(String, u32)>,
+    /// Where the tile values come from
+    pub language: Language,
+}
+
+impl ImageOptions {
+    /**
+     * The scores of everyone in the game, and the rack of `player_idx` if
+     * there is one
+     */
+    pub fn for_game(game: &Game, player_idx: Option<usize>) -> ImageOptions {
+        ImageOptions {
+            highlight: vec![],
+            rack: player_idx
+                .and_then(|idx| game.players.get(idx))
+                .map(|player| player.hand.clone()),
+            scores: game
+                .players
+                .iter()
+                .enumerate()
+                .map(|(idx, player)| (player.name(idx), player.score))
+                .collect(),
+            language: game.language,
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn premium_style(cell: &BoardCell) -> (&'static str, &'static str) {
+    match cell {
+        BoardCell::TripleWord => ("#d9534f", "TW"),
+        BoardCell::DoubleWord => ("#f2a7b8", "DW"),
+        BoardCell::TripleLetter => ("#3b7dd8", "TL"),
+        BoardCell::DoubleLetter => ("#a8d8ef", "DL"),
+        BoardCell::StartingSpot => ("#f2a7b8", ""),
+        _ => ("#cfc8b4", ""),
+    }
+}
+
+/**
+ * A five pointed star centered in the cell at `(x, y)`. Bungee has no star
+ * glyph so it is drawn as a shape.
+ */
+fn write_star(svg: &mut String, x: u32, y: u32) {
+    let center = (x as f64 + CELL_SIZE as f64 / 2.0, y as f64 + CELL_SIZE as f64 / 2.0);
+    let points: Vec<String> = (0..10)
+        .map(|i| {
+            let radius = if i % 2 == 0 { 12.0 } else { 5.0 };
+            let angle = std::f64::consts::PI * (i as f64 / 5.0 - 0.5);
+            format!("{:.1},{:.1}", center.0 + radius * angle.cos(), center.1 + radius * angle.sin())
+        })
+        .collect();
+
+    write!(svg, r##"<polygon points="{}" fill="#fff"/>"##, points.join(" ")).unwrap();
+}
+
+/**
+ * A tile with its top left corner at `(x, y)`
+ */
+fn write_tile(svg: &mut String, x: u32, y: u32, tile: Tile, highlighted: bool, language: Language) {
+    let stroke = if highlighted { "#2e7d32" } else { "#a08a5c" };
+    let stroke_width = if highlighted { 3 } else { 1 };
+    let letter = match tile {
+        Tile::Letter(letter) => unit(letter),
+        Tile::Blank => String::new(),
+    };
+    let font_size = if letter.chars().count() > 1 { 15 } else { 20 };
+
+    write!(
+        svg,
+        r##"<rect x="{}" y="{}" width="{}" height="{}" rx="4" fill="#f5deb3" stroke="{}" stroke-width="{}"/>"##,
+        x + 2,
+        y + 2,
+        CELL_SIZE - 4,
+        CELL_SIZE - 4,
+        stroke,
+        stroke_width
+    )
+    .unwrap();
+    write!(
+        svg,
+        r##"<text x="{}" y="{}" font-size="{}" text-anchor="middle" fill="#222">{}</text>"##,
+        x + CELL_SIZE / 2,
+        y + 28,
+        font_size,
+        letter
+    )
+    .unwrap();
+    write!(
+        svg,
+        r##"<text x="{}" y="{}" font-size="9" text-anchor="end" fill="#222">{}</text>"##,
+        x + CELL_SIZE - 5,
+        y + CELL_SIZE - 5,
+        language.tile_set().point_value(tile)
+    )
+    .unwrap();
+}
+
+/**
+ * The board as a standalone SVG document
+ */
+pub fn render_svg(board: &Board, options: &ImageOptions) -> String {
+    let board_size = BOARD_SIZE * CELL_SIZE;
+    let rack_height = if options.rack.is_some() { CELL_SIZE + 16 } else { 0 };
+    let scores_height = options.scores.len() as u32 * 22;
+    let width = GUTTER + board_size + 8;
+    let height = GUTTER + board_size + 8 + rack_height + scores_height + 8;
+
+    let mut svg = String::new();
+    write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="{f}">"#,
+        w = width,
+        h = height,
+        f = FONT_FAMILY
+    )
+    .unwrap();
+    write!(svg, r##"<rect width="{}" height="{}" fill="#fdfbf5"/>"##, width, height).unwrap();
+
+    for i in 0..BOARD_SIZE {
+        let center = GUTTER + i * CELL_SIZE + CELL_SIZE / 2;
+        write!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="11" text-anchor="middle" fill="#666">{}</text>"##,
+            center,
+            GUTTER - 8,
+            i
+        )
+        .unwrap();
+        write!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="11" text-anchor="middle" fill="#666">{}</text>"##,
+            GUTTER / 2,
+            center + 4,
+            i
+        )
+        .unwrap();
+    }
+
+    for (idx, cell) in board.cells().iter().enumerate() {
+        let point = Point::from_index(idx);
+        let (x, y) = (GUTTER + point.x as u32 * CELL_SIZE, GUTTER + point.y as u32 * CELL_SIZE);
+
+        match cell {
+            BoardCell::Tile(tile, square) => {
+                // The square shows around the edge of the tile, and its
+                // name in the corner
+                let (fill, label) = premium_style(&BoardCell::from(*square));
+                write!(
+                    svg,
+                    r##"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}" stroke="#fdfbf5"/>"##,
+                    x,
+                    y,
+                    fill,
+                    s = CELL_SIZE
+                )
+                .unwrap();
+                let highlighted = options.highlight.contains(&point);
+                write_tile(&mut svg, x, y, *tile, highlighted, options.language);
+                if !label.is_empty() {
+                    write!(
+                        svg,
+                        r##"<text x="{}" y="{}" font-size="8" fill="{}">{}</text>"##,
+                        x + 5,
+                        y + 12,
+                        fill,
+                        label
+                    )
+                    .unwrap();
+                }
+            }
+            cell => {
+                let (fill, label) = premium_style(cell);
+                write!(
+                    svg,
+                    r##"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}" stroke="#fdfbf5"/>"##,
+                    x,
+                    y,
+                    fill,
+                    s = CELL_SIZE
+                )
+                .unwrap();
+                if *cell == BoardCell::StartingSpot {
+                    write_star(&mut svg, x, y);
+                } else if !label.is_empty() {
+                    write!(
+                        svg,
+                        r##"<text x="{}" y="{}" font-size="11" text-anchor="middle" fill="#fff">{}</text>"##,
+                        x + CELL_SIZE / 2,
+                        y + CELL_SIZE / 2 + 4,
+                        label
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    let mut y = GUTTER + board_size + 16;
+    if let Some(ref rack) = options.rack {
+        for (idx, tile) in rack.iter().enumerate() {
+            write_tile(&mut svg, GUTTER + idx as u32 * CELL_SIZE, y, *tile, false, options.language);
+        }
+        y += rack_height;
+    }
+
+    for (name, score) in &options.scores {
+        write!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="14" fill="#222">{}: {}</text>"##,
+            GUTTER,
+            y + 14,
+            escape(name),
+            score
+        )
+        .unwrap();
+        y += 22;
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/**
+ * The bundled copy of the Bungee font
+ */
+#[cfg(feature = "png")]
+const BUNGEE_FONT: &[u8] = include_bytes!("../assets/fonts/Bungee-Regular.ttf");
+
+/**
+ * The board rasterized to a PNG
+ */
+#[cfg(feature = "png")]
+pub fn render_png(board: &Board, options: &ImageOptions) -> super::Result<Vec<u8>> {
+    use super::Error;
+    use resvg::{tiny_skia, usvg};
+
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb.load_font_data(BUNGEE_FONT.to_vec());
+    fontdb.set_sans_serif_family("Bungee");
+
+    let usvg_options = usvg::Options {
+        fontdb: std::sync::Arc::new(fontdb),
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(&render_svg(board, options), &usvg_options)
+        .map_err(|err| Error::ImageRendering(err.to_string()))?;
+
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| Error::ImageRendering("Image has no size".to_string()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|err| Box::new(Error::ImageRendering(err.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::models::Direction;
+
+    #[test]
+    fn svg_has_every_cell_and_tile() {
+        let mut game = Game::new(2);
+        game.players[0].hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+        game.play_word(Point::new(7, 7), Direction::right(), "ACTOR").unwrap();
+
+        let mut options = ImageOptions::for_game(&game, Some(1));
+        options.highlight = vec![Point::new(7, 7)];
+        options.scores[0].0 = "<Ann & Bo>".to_string();
+        let svg = render_svg(&game.board, &options);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>"));
+        // Board cells plus the tiles on the board and in the rack
+        let tiles = 5 + game.players[1].hand.len();
+        assert_eq!(svg.matches("<rect x=").count(), (BOARD_SIZE * BOARD_SIZE) as usize + tiles);
+        assert_eq!(svg.matches(r##"stroke="#2e7d32""##).count(), 1);
+        assert!(svg.contains("&lt;Ann &amp; Bo&gt;: "));
+        // The R of ACTOR covers a double letter
+        assert_eq!(svg.matches(r##"fill="#a8d8ef">DL<"##).count(), 1);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn png_has_the_svg_size() {
+        let png = render_png(&Board::new(), &ImageOptions::default()).unwrap();
+
+        assert_eq!(&png[1..4], b"PNG");
+        let width = u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+        assert_eq!(width, GUTTER + BOARD_SIZE * CELL_SIZE + 8);
+    }
+}
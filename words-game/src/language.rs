@@ -0,0 +1,528 @@
+//! Tile sets and dictionaries for the languages games can be played in.
+//!
+//! Every tile is still a single `char`, including the Spanish CH, LL and RR
+//! tiles which are stored as the private use characters in `DIGRAPHS`. Words
+//! typed by players are split into tiles by `TileSet::normalize_word`, so
+//! `chorro` becomes the four tiles CH, O, RR, O, and `spell` turns them back
+//! into text for display. Accents and ligatures a language has no tiles
+//! for are written out as plain letters, so `café` is CAFE in French and
+//! `straße` is STRASSE in German.
+//!
+//! Only the English word list is built in. The others have to be installed
+//! with `install_dictionary` before a game in that language can check its
+//! words. The move generator and the word finder only know English.
+
+use super::constants::DICTIONARY;
+use super::error::{Error, Result};
+use super::models::Tile;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/**
+ * Tiles that stand for two letters, and the character each one is stored as
+ */
+pub const DIGRAPHS: [(&str, char); 3] = [("CH", '\u{E000}'), ("LL", '\u{E001}'), ("RR", '\u{E002}')];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    French,
+    German,
+    Polish,
+}
+
+/**
+ * One kind of tile in a set: how it is written, how many are in the bag
+ * and how many points it is worth
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileSpec {
+    pub unit: &'static str,
+    pub letter: char,
+    pub count: u32,
+    pub value: u32,
+}
+
+/**
+ * The tiles of a language, in alphabetical order. Blanks are left out like
+ * in the English set since they cannot be played yet.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileSet {
+    pub language: Language,
+    pub specs: Vec<TileSpec>,
+}
+
+/**
+ * Accented capitals, by the plain letter they are played as when a tile set
+ * has no tile for them
+ */
+const ACCENTED: [(char, &str); 19] = [
+    ('A', "ÀÁÂÃÄÅĀĂĄ"),
+    ('C', "ÇĆĈĊČ"),
+    ('D', "Ď"),
+    ('E', "ÈÉÊËĒĔĖĘĚ"),
+    ('G', "ĜĞĠĢ"),
+    ('H', "Ĥ"),
+    ('I', "ÌÍÎÏĨĪĬĮİ"),
+    ('J', "Ĵ"),
+    ('K', "Ķ"),
+    ('L', "ĹĻĽ"),
+    ('N', "ÑŃŅŇ"),
+    ('O', "ÒÓÔÕÖŌŎŐ"),
+    ('R', "ŔŖŘ"),
+    ('S', "ŚŜŞŠ"),
+    ('T', "ŢŤ"),
+    ('U', "ÙÚÛÜŨŪŬŮŰŲ"),
+    ('W', "Ŵ"),
+    ('Y', "ÝŶŸ"),
+    ('Z', "ŹŻŽ"),
+];
+
+/**
+ * Capital ligatures and the letters they are played as. A lowercase `ß`
+ * already uppercases to SS.
+ */
+const LIGATURES: [(char, &str); 3] = [('Æ', "AE"), ('Œ', "OE"), ('ẞ', "SS")];
+
+type Distribution = &'static [(&'static str, u32, u32)];
+
+const ENGLISH: Distribution = &[
+    ("A", 9, 1), ("B", 2, 3), ("C", 2, 3), ("D", 4, 2), ("E", 12, 1), ("F", 2, 4),
+    ("G", 3, 2), ("H", 2, 4), ("I", 9, 1), ("J", 1, 8), ("K", 1, 5), ("L", 4, 1),
+    ("M", 2, 3), ("N", 6, 1), ("O", 8, 1), ("P", 2, 3), ("Q", 1, 10), ("R", 6, 1),
+    ("S", 4, 1), ("T", 6, 1), ("U", 4, 1), ("V", 2, 4), ("W", 2, 4), ("X", 1, 8),
+    ("Y", 2, 4), ("Z", 1, 10),
+];
+
+const SPANISH: Distribution = &[
+    ("A", 12, 1), ("B", 2, 3), ("C", 4, 3), ("CH", 1, 5), ("D", 5, 2), ("E", 12, 1),
+    ("F", 1, 4), ("G", 2, 2), ("H", 2, 4), ("I", 6, 1), ("J", 1, 8), ("L", 4, 1),
+    ("LL", 1, 8), ("M", 2, 3), ("N", 5, 1), ("Ñ", 1, 8), ("O", 9, 1), ("P", 2, 3),
+    ("Q", 1, 5), ("R", 5, 1), ("RR", 1, 8), ("S", 6, 1), ("T", 4, 1), ("U", 5, 1),
+    ("V", 1, 4), ("X", 1, 8), ("Y", 1, 4), ("Z", 1, 10),
+];
+
+const FRENCH: Distribution = &[
+    ("A", 9, 1), ("B", 2, 3), ("C", 2, 3), ("D", 3, 2), ("E", 15, 1), ("F", 2, 4),
+    ("G", 2, 2), ("H", 2, 4), ("I", 8, 1), ("J", 1, 8), ("K", 1, 10), ("L", 5, 1),
+    ("M", 3, 2), ("N", 6, 1), ("O", 6, 1), ("P", 2, 3), ("Q", 1, 8), ("R", 6, 1),
+    ("S", 6, 1), ("T", 6, 1), ("U", 6, 1), ("V", 2, 4), ("W", 1, 10), ("X", 1, 10),
+    ("Y", 1, 10), ("Z", 1, 10),
+];
+
+const GERMAN: Distribution = &[
+    ("A", 5, 1), ("Ä", 1, 6), ("B", 2, 3), ("C", 2, 4), ("D", 4, 1), ("E", 15, 1),
+    ("F", 2, 4), ("G", 3, 2), ("H", 4, 2), ("I", 6, 1), ("J", 1, 6), ("K", 2, 4),
+    ("L", 3, 2), ("M", 4, 3), ("N", 9, 1), ("O", 3, 2), ("Ö", 1, 8), ("P", 1, 4),
+    ("Q", 1, 10), ("R", 6, 1), ("S", 7, 1), ("T", 6, 1), ("U", 6, 1), ("Ü", 1, 6),
+    ("V", 1, 6), ("W", 1, 3), ("X", 1, 8), ("Y", 1, 10), ("Z", 1, 3),
+];
+
+const POLISH: Distribution = &[
+    ("A", 9, 1), ("Ą", 1, 5), ("B", 2, 3), ("C", 3, 2), ("Ć", 1, 6), ("D", 3, 2),
+    ("E", 7, 1), ("Ę", 1, 5), ("F", 1, 5), ("G", 2, 3), ("H", 2, 3), ("I", 8, 1),
+    ("J", 2, 3), ("K", 3, 2), ("L", 3, 2), ("Ł", 2, 3), ("M", 3, 2), ("N", 5, 1),
+    ("Ń", 1, 7), ("O", 6, 1), ("Ó", 1, 5), ("P", 3, 2), ("R", 4, 1), ("S", 4, 1),
+    ("Ś", 1, 5), ("T", 3, 2), ("U", 2, 3), ("W", 4, 1), ("Y", 4, 2), ("Z", 5, 1),
+    ("Ź", 1, 9), ("Ż", 1, 5),
+];
+
+lazy_static! {
+    static ref TILE_SETS: HashMap<Language, TileSet> = Language::ALL
+        .iter()
+        .map(|language| (*language, TileSet::new(*language)))
+        .collect();
+    static ref DICTIONARIES: RwLock<HashMap<Language, HashSet<String>>> =
+        RwLock::new(HashMap::new());
+}
+
+/**
+ * `letter` written without its accent, or a ligature as its letters
+ */
+fn plain_letters(letter: char) -> Vec<char> {
+    if let Some((_, letters)) = LIGATURES.iter().find(|(ligature, _)| *ligature == letter) {
+        return letters.chars().collect();
+    }
+
+    let plain = ACCENTED
+        .iter()
+        .find(|(_, accented)| accented.contains(letter))
+        .map_or(letter, |(plain, _)| *plain);
+    vec![plain]
+}
+
+/**
+ * The character a tile written as `unit` is stored as
+ */
+fn unit_letter(unit: &str) -> char {
+    DIGRAPHS
+        .iter()
+        .find(|(digraph, _)| *digraph == unit)
+        .map(|(_, letter)| *letter)
+        .unwrap_or_else(|| unit.chars().next().unwrap())
+}
+
+impl Language {
+    pub const ALL: [Language; 5] = [
+        Language::English,
+        Language::Spanish,
+        Language::French,
+        Language::German,
+        Language::Polish,
+    ];
+
+    /**
+     * The ISO 639-1 code, e.g. `es` for Spanish
+     */
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Polish => "pl",
+        }
+    }
+
+    pub fn tile_set(self) -> &'static TileSet {
+        &TILE_SETS[&self]
+    }
+
+    fn distribution(self) -> Distribution {
+        match self {
+            Language::English => ENGLISH,
+            Language::Spanish => SPANISH,
+            Language::French => FRENCH,
+            Language::German => GERMAN,
+            Language::Polish => POLISH,
+        }
+    }
+
+    /**
+     * Whether the already normalized `word` is in this language's
+     * dictionary, failing when no dictionary has been installed for it
+     */
+    pub fn contains_word(self, word: &str) -> Result<bool> {
+        if self == Language::English {
+            return Ok(DICTIONARY.contains(word));
+        }
+
+        DICTIONARIES
+            .read()
+            .unwrap()
+            .get(&self)
+            .map(|words| words.contains(word))
+            .ok_or_else(|| Error::MissingDictionary(self.to_string()).into())
+    }
+
+    /**
+     * Checks a word typed by a player, anything that can't be spelled with
+     * this language's tiles is not a word
+     */
+    pub fn check_word(self, text: &str) -> bool {
+        self.tile_set()
+            .normalize_word(text)
+            .and_then(|word| self.contains_word(&word))
+            .unwrap_or(false)
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for Language {
+    type Err = Box<Error>;
+
+    /**
+     * Parses a language code or an English name, in any case
+     */
+    fn from_str(s: &str) -> Result<Language> {
+        Language::ALL
+            .iter()
+            .find(|language| {
+                language.code().eq_ignore_ascii_case(s) || language.to_string().eq_ignore_ascii_case(s)
+            })
+            .cloned()
+            .ok_or_else(|| Error::BadAction(format!("{} is not a supported language", s)).into())
+    }
+}
+
+impl TileSet {
+    fn new(language: Language) -> TileSet {
+        let specs = language
+            .distribution()
+            .iter()
+            .map(|(unit, count, value)| TileSpec {
+                unit,
+                letter: unit_letter(unit),
+                count: *count,
+                value: *value,
+            })
+            .collect();
+
+        TileSet { language, specs }
+    }
+
+    fn spec(&self, letter: char) -> Option<&TileSpec> {
+        self.specs.iter().find(|spec| spec.letter == letter)
+    }
+
+    pub fn contains(&self, letter: char) -> bool {
+        self.spec(letter).is_some()
+    }
+
+    /**
+     * The points a tile is worth, blanks and letters from other languages
+     * are worth nothing
+     */
+    pub fn point_value(&self, tile: Tile) -> u32 {
+        match tile {
+            Tile::Blank => 0,
+            Tile::Letter(letter) => self.spec(letter).map_or(0, |spec| spec.value),
+        }
+    }
+
+    /**
+     * Every tile of a full bag, in alphabetical order
+     */
+    pub fn tiles(&self) -> Vec<Tile> {
+        self.specs
+            .iter()
+            .flat_map(|spec| std::iter::repeat_n(Tile::Letter(spec.letter), spec.count as usize))
+            .collect()
+    }
+
+    /**
+     * Case folds `text` and splits it into this set's tiles, preferring the
+     * two letter tiles where they fit. Accents the set has no tiles for are
+     * dropped. Fails on anything that isn't one of the tiles.
+     */
+    pub fn normalize_word(&self, text: &str) -> Result<String> {
+        let text = text.trim();
+
+        if text.is_empty() {
+            return Err(Error::EmptyWord.into());
+        }
+
+        self.split(text, false).map(|tiles| tiles.into_iter().map(char::from).collect())
+    }
+
+    /**
+     * Like `normalize_word` for a rack, where `?` or a space is a blank
+     */
+    pub fn normalize_tiles(&self, text: &str) -> Result<Vec<Tile>> {
+        self.split(text, true)
+    }
+
+    /**
+     * The uppercase letters `c` is played as, written out plainly when the
+     * set has no tile for it as it is
+     */
+    fn fold(&self, c: char) -> Vec<char> {
+        let upper: Vec<char> = c.to_uppercase().collect();
+        if upper.iter().all(|letter| self.contains(*letter)) {
+            return upper;
+        }
+
+        let plain: Vec<char> = upper.iter().flat_map(|letter| plain_letters(*letter)).collect();
+        if plain.iter().all(|letter| self.contains(*letter)) {
+            plain
+        } else {
+            upper
+        }
+    }
+
+    fn split(&self, text: &str, allow_blanks: bool) -> Result<Vec<Tile>> {
+        let mut chars = Vec::with_capacity(text.len());
+        for c in text.chars() {
+            chars.extend(self.fold(c).into_iter().map(|upper| (c, upper)));
+        }
+
+        let mut tiles = Vec::with_capacity(chars.len());
+        let mut idx = 0;
+        while idx < chars.len() {
+            let (original, upper) = chars[idx];
+
+            let digraph = chars.get(idx + 1).and_then(|(_, next)| {
+                self.specs.iter().find(|spec| {
+                    let mut unit = spec.unit.chars();
+                    unit.next() == Some(upper) && unit.next() == Some(*next) && unit.next().is_none()
+                })
+            });
+
+            if let Some(spec) = digraph {
+                tiles.push(Tile::Letter(spec.letter));
+                idx += 2;
+                continue;
+            }
+
+            if allow_blanks && super::normalize::BLANK_CHARS.contains(&original) {
+                tiles.push(Tile::Blank);
+            } else if self.contains(upper) {
+                tiles.push(Tile::Letter(upper));
+            } else {
+                return Err(Error::InvalidCharacter(original).into());
+            }
+            idx += 1;
+        }
+
+        Ok(tiles)
+    }
+}
+
+/**
+ * Whether `letter` is a tile in any of the languages
+ */
+pub fn is_letter(letter: char) -> bool {
+    Language::ALL.iter().any(|language| language.tile_set().contains(letter))
+}
+
+/**
+ * How the tile stored as `letter` is written
+ */
+pub fn unit(letter: char) -> String {
+    DIGRAPHS
+        .iter()
+        .find(|(_, code)| *code == letter)
+        .map_or_else(|| letter.to_string(), |(digraph, _)| digraph.to_string())
+}
+
+/**
+ * Writes out a normalized word, turning two letter tiles back into their
+ * letters
+ */
+pub fn spell(word: &str) -> String {
+    word.chars().map(unit).collect()
+}
+
+/**
+ * Makes `contents`, one word per line, the dictionary for `language`. Lines
+ * that are empty or start with `#` are skipped. Returns how many words were
+ * installed.
+ */
+pub fn install_dictionary(language: Language, contents: &str) -> Result<usize> {
+    if language == Language::English {
+        return Err(Error::InvalidDictionary("English uses the built in word list".to_string()).into());
+    }
+
+    let tile_set = language.tile_set();
+    let mut words = HashSet::new();
+
+    for (line_no, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let word = tile_set.normalize_word(line).map_err(|err| {
+            Error::InvalidDictionary(format!("bad word on line {}: {}", line_no + 1, err))
+        })?;
+        words.insert(word);
+    }
+
+    let count = words.len();
+    DICTIONARIES.write().unwrap().insert(language, words);
+    Ok(count)
+}
+
+/**
+ * Reads a word list from `path` and installs it for `language`
+ */
+pub fn load_dictionary<P: AsRef<Path>>(language: Language, path: P) -> Result<usize> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::InvalidDictionary(err.to_string()))?;
+
+    install_dictionary(language, &contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_sets_have_the_standard_sizes() {
+        let sizes: Vec<usize> = Language::ALL.iter().map(|l| l.tile_set().tiles().len()).collect();
+        assert_eq!(sizes, vec![98, 98, 100, 100, 98]);
+
+        let spanish = Language::Spanish.tile_set();
+        assert_eq!(spanish.point_value(Tile::Letter('Ñ')), 8);
+        assert_eq!(spanish.point_value(Tile::Letter(unit_letter("CH"))), 5);
+        assert_eq!(Language::English.tile_set().point_value(Tile::Letter('Ñ')), 0);
+    }
+
+    #[test]
+    fn splits_words_into_tiles() {
+        let spanish = Language::Spanish.tile_set();
+        let chorro = spanish.normalize_word(" chorro ").unwrap();
+        assert_eq!(chorro.chars().count(), 4);
+        assert_eq!(spell(&chorro), "CHORRO");
+        assert_eq!(spanish.normalize_word("niño").unwrap(), "NIÑO");
+        assert_eq!(spanish.normalize_word(&chorro).unwrap(), chorro);
+        assert!(matches!(*spanish.normalize_word("kilo").unwrap_err(), Error::InvalidCharacter('k')));
+
+        let german = Language::German.tile_set();
+        assert_eq!(german.normalize_word("Bär").unwrap(), "BÄR");
+        assert_eq!(german.normalize_word("straße").unwrap(), "STRASSE");
+        assert_eq!(german.normalize_word("STRAẞE").unwrap(), "STRASSE");
+        assert_eq!(german.normalize_word("café").unwrap(), "CAFE");
+        assert_eq!(
+            german.normalize_tiles("ü?").unwrap(),
+            vec![Tile::Letter('Ü'), Tile::Blank]
+        );
+
+        assert_eq!(Language::Polish.tile_set().normalize_word("żółw").unwrap(), "ŻÓŁW");
+    }
+
+    #[test]
+    fn folds_accents_the_set_lacks() {
+        let french = Language::French.tile_set();
+        assert_eq!(french.normalize_word("été").unwrap(), "ETE");
+        assert_eq!(french.normalize_word("Café").unwrap(), "CAFE");
+        assert_eq!(french.normalize_word("cœur").unwrap(), "COEUR");
+        assert_eq!(french.normalize_tiles("ç?").unwrap(), vec![Tile::Letter('C'), Tile::Blank]);
+
+        let spanish = Language::Spanish.tile_set();
+        assert_eq!(spanish.normalize_word("pingüino").unwrap(), "PINGUINO");
+        assert_eq!(spanish.normalize_word("café").unwrap(), "CAFE");
+        assert_eq!(spanish.normalize_word("año").unwrap(), "AÑO");
+
+        assert_eq!(Language::German.tile_set().normalize_word("Übel").unwrap(), "ÜBEL");
+        assert!(matches!(
+            *Language::Polish.tile_set().normalize_word("qué").unwrap_err(),
+            Error::InvalidCharacter('q')
+        ));
+    }
+
+    #[test]
+    fn parses_languages() {
+        assert_eq!("es".parse::<Language>().unwrap(), Language::Spanish);
+        assert_eq!("german".parse::<Language>().unwrap(), Language::German);
+        assert!("xx".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn installed_dictionaries() {
+        assert!(matches!(
+            *Language::French.contains_word("CHAT").unwrap_err(),
+            Error::MissingDictionary(_)
+        ));
+        assert!(install_dictionary(Language::English, "CAT").is_err());
+        assert!(install_dictionary(Language::Polish, "kot\npies1").is_err());
+
+        assert_eq!(install_dictionary(Language::Spanish, "# words\nchorro\nniño\n").unwrap(), 2);
+        assert!(Language::Spanish.check_word("CHORRO"));
+        assert!(Language::Spanish.check_word("Niño"));
+        assert!(!Language::Spanish.check_word("nino"));
+        assert!(Language::English.check_word("actor"));
+    }
+}
@@ -0,0 +1,253 @@
+use super::error::{Error, Result};
+use super::models::{remove_tiles, Tile};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/**
+ * Values used for leaves that are not listed in a table. The numbers are in
+ * points, roughly what a tile left on the rack is worth on the next turn.
+ */
+fn default_tile_value(tile: Tile) -> f64 {
+    match tile {
+        Tile::Blank => 25.0,
+        Tile::Letter(letter) => match letter {
+            'S' => 8.0,
+            'Z' => 2.5,
+            'X' => 3.5,
+            'E' | 'R' => 1.5,
+            'H' | 'N' | 'T' | 'D' | 'L' | 'C' | 'M' | 'A' => 0.5,
+            'I' | 'O' | 'P' | 'K' => -0.5,
+            'G' | 'B' | 'F' | 'Y' => -2.0,
+            'J' => -2.5,
+            'U' | 'W' => -3.5,
+            'V' => -5.5,
+            'Q' => -7.0,
+            _ => 0.0,
+        },
+    }
+}
+
+const DUPLICATE_PENALTY: f64 = 3.0;
+const VOWEL_IMBALANCE_PENALTY: f64 = 2.0;
+const Q_WITHOUT_U_PENALTY: f64 = 5.0;
+
+fn is_vowel(tile: Tile) -> bool {
+    match tile {
+        Tile::Letter(letter) => "AEIOU".contains(letter),
+        Tile::Blank => false,
+    }
+}
+
+/**
+ * The canonical form of a leave, its letters sorted with '?' for blanks
+ */
+fn leave_key(leave: &[Tile]) -> String {
+    let mut chars: Vec<char> = leave
+        .iter()
+        .map(|tile| match tile {
+            Tile::Blank => '?',
+            Tile::Letter(letter) => *letter,
+        })
+        .collect();
+    chars.sort_unstable();
+    chars.into_iter().collect()
+}
+
+/**
+ * Scores the tiles left on a rack after a play.
+ *
+ * A table file has one leave per line, the letters of the leave (using '?'
+ * for a blank) followed by its value, e.g. `?S 28.5`. Single letter entries
+ * replace the value of that tile, longer entries are exact values for that
+ * whole leave. Leaves that are not listed are valued from their tiles, with
+ * penalties for duplicates, an unbalanced vowel/consonant mix and a Q
+ * without a U.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct LeaveTable {
+    tile_values: HashMap<char, f64>,
+    leaves: HashMap<String, f64>,
+}
+
+impl LeaveTable {
+    pub fn new() -> LeaveTable {
+        LeaveTable::default()
+    }
+
+    pub fn parse(contents: &str) -> Result<LeaveTable> {
+        let mut table = LeaveTable::new();
+
+        for (line_no, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let bad_line = || Error::InvalidLeaveTable(format!("bad entry on line {}", line_no + 1));
+
+            let mut parts = line.split_whitespace();
+            let (leave, value) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(leave), Some(value), None) => (leave, value),
+                _ => return Err(bad_line().into()),
+            };
+
+            let value: f64 = value.parse().map_err(|_| bad_line())?;
+            if !value.is_finite() {
+                return Err(bad_line().into());
+            }
+            if !leave.chars().all(|c| c == '?' || c.is_ascii_uppercase()) {
+                return Err(bad_line().into());
+            }
+
+            let tiles: Vec<Tile> = leave
+                .chars()
+                .map(|c| if c == '?' { Tile::Blank } else { Tile::Letter(c) })
+                .collect();
+
+            if tiles.len() == 1 {
+                let key = leave.chars().next().unwrap();
+                table.tile_values.insert(key, value);
+            } else {
+                table.leaves.insert(leave_key(&tiles), value);
+            }
+        }
+
+        Ok(table)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<LeaveTable> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| Error::InvalidLeaveTable(err.to_string()))?;
+
+        LeaveTable::parse(&contents)
+    }
+
+    fn tile_value(&self, tile: Tile) -> f64 {
+        let key = match tile {
+            Tile::Blank => '?',
+            Tile::Letter(letter) => letter,
+        };
+
+        self.tile_values
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| default_tile_value(tile))
+    }
+
+    /**
+     * The value of keeping `leave` on the rack, higher is better
+     */
+    pub fn value(&self, leave: &[Tile]) -> f64 {
+        if leave.is_empty() {
+            return 0.0;
+        }
+
+        if let Some(value) = self.leaves.get(&leave_key(leave)) {
+            return *value;
+        }
+
+        let mut value: f64 = leave.iter().map(|tile| self.tile_value(*tile)).sum();
+
+        // Every extra copy of a letter makes the rack less flexible, and a
+        // second copy of a good tile is worth less than the first
+        let mut counts = HashMap::<Tile, u32>::new();
+        for tile in leave.iter().filter(|tile| **tile != Tile::Blank) {
+            *counts.entry(*tile).or_insert(0) += 1;
+        }
+        for (tile, count) in counts.iter() {
+            let extra = f64::from(count - 1);
+            value -= extra * (DUPLICATE_PENALTY + self.tile_value(*tile).max(0.0) / 2.0);
+        }
+
+        // Aim for a little under half vowels, blanks can go either way
+        let letters = leave.iter().filter(|tile| **tile != Tile::Blank).count() as f64;
+        let vowels = leave.iter().filter(|tile| is_vowel(**tile)).count() as f64;
+        let imbalance = (vowels - letters * 0.4).abs();
+        if imbalance > 1.0 {
+            value -= (imbalance - 1.0) * VOWEL_IMBALANCE_PENALTY;
+        }
+
+        let has_q = leave.contains(&Tile::Letter('Q'));
+        let has_u = leave.contains(&Tile::Letter('U'));
+        let has_blank = leave.contains(&Tile::Blank);
+        if has_q && !has_u && !has_blank {
+            value -= Q_WITHOUT_U_PENALTY;
+        }
+
+        value
+    }
+}
+
+/**
+ * The tiles left over in `hand` after playing `played`
+ */
+pub fn leave_after(hand: &[Tile], played: &[Tile]) -> Result<Vec<Tile>> {
+    remove_tiles(hand, played)
+}
+
+/**
+ * The equity of a play, its score plus the value of the tiles it keeps
+ */
+pub fn equity(score: u32, leave: &[Tile], table: &LeaveTable) -> f64 {
+    f64::from(score) + table.value(leave)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        s.chars().map(Tile::from).collect()
+    }
+
+    #[test]
+    fn heuristic_values() {
+        let table = LeaveTable::new();
+
+        assert!(table.value(&tiles("ERS")) > table.value(&tiles("VWU")));
+        assert!(table.value(&tiles("QU")) > table.value(&tiles("QT")));
+        assert!(table.value(&tiles("AEIOU")) < table.value(&tiles("AERST")));
+        assert!(table.value(&tiles("EST")) > table.value(&tiles("SST")));
+        assert!(table.value(&tiles(" S")) > table.value(&tiles("ES")));
+        assert_eq!(table.value(&[]), 0.0);
+    }
+
+    #[test]
+    fn table_overrides() -> Result<()> {
+        let table = LeaveTable::parse(
+            "# superleaves\n\
+             Z 0.0\n\
+             SE 30\n\
+             ?S 40.5\n",
+        )?;
+
+        assert_eq!(table.value(&tiles("Z")), 0.0);
+        assert_eq!(table.value(&tiles("ES")), 30.0);
+        assert_eq!(table.value(&tiles("S ")), 40.5);
+        assert_eq!(table.value(&tiles("V")), -5.5);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_table() {
+        assert!(LeaveTable::parse("QU").is_err());
+        assert!(LeaveTable::parse("QU abc").is_err());
+        assert!(LeaveTable::parse("qu 1.0").is_err());
+        assert!(LeaveTable::parse("QU NaN").is_err());
+        assert!(LeaveTable::parse("S inf").is_err());
+        assert!(LeaveTable::parse("S -infinity").is_err());
+        assert!(LeaveTable::from_file("/does/not/exist").is_err());
+    }
+
+    #[test]
+    fn equity_of_play() -> Result<()> {
+        let table = LeaveTable::new();
+        let leave = leave_after(&tiles("ACTORSEOYK"), &tiles("ACTOR"))?;
+
+        assert_eq!(leave, tiles("SEOYK"));
+        assert_eq!(equity(8, &leave, &table), 8.0 + table.value(&leave));
+        assert!(leave_after(&tiles("ACT"), &tiles("Q")).is_err());
+        Ok(())
+    }
+}
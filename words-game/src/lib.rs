@@ -2,10 +2,23 @@
 extern crate lazy_static;
 
 mod constants;
+pub mod definitions;
+mod encoding;
+pub mod endgame;
 pub mod error;
+pub mod finder;
+pub mod image;
+pub mod language;
+pub mod leave;
 pub mod models;
+pub mod movegen;
+pub mod normalize;
+pub mod render;
+pub mod simulation;
+mod trie;
 
 pub use models::*;
 pub use error::*;
+pub use language::Language;
 
-pub use constants::{BOARD_SIZE, BOARD, check_dictionary};
+pub use constants::{BOARD_SIZE, BOARD, HAND_SIZE, check_dictionary};
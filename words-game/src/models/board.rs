@@ -1,9 +1,14 @@
 use serde::{Serialize, Deserialize};
-use super::super::constants::{BOARD, BOARD_SIZE, DICTIONARY};
+use super::super::constants::{BOARD, BOARD_SIZE};
+use super::super::language::{is_letter, spell, Language};
 use super::super::error::{Error, Result};
+use super::coordinates::{points, row};
+use super::cross_check::CrossCheck;
+use super::score::{LetterScore, WordScore};
 use super::tile::Tile;
 use super::{Direction, Point, Strip};
 use std::fmt;
+use std::sync::OnceLock;
 
 /**
  * Represents how the cell on the board affects the scoring of the final word
@@ -21,9 +26,38 @@ impl BoardCellMultiplier {
 }
 
 /**
- * Represents the state of a cell on the board
+ * The kind of square a cell is, which stays the same once a tile covers it
  */
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Square {
+    StartingSpot,
+    #[default]
+    Empty,
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+}
+
+impl Square {
+    /**
+     * Whether the square changes the score of a word placed on it
+     */
+    pub fn is_premium(self) -> bool {
+        BoardCell::from(self).is_premium()
+    }
+}
+
+/**
+ * The characters of the squares in `BOARD` and the cell text encoding
+ */
+const SQUARE_CHARS: &str = ".32@#+";
+
+/**
+ * Represents the state of a cell on the board. A tile remembers the square
+ * it covers, so the board can still tell where the premiums were.
+ */
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum BoardCell {
     StartingSpot,
     Empty,
@@ -31,10 +65,114 @@ pub enum BoardCell {
     TripleLetter,
     DoubleWord,
     TripleWord,
-    Tile(Tile),
+    #[serde(deserialize_with = "deserialize_tile_cell")]
+    Tile(Tile, Square),
+}
+
+/**
+ * Boards saved before tiles kept their square have only the tile. A cell
+ * read on its own gets a plain square, a whole board gets the square of
+ * the default layout.
+ */
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedTileCell {
+    Covering(Tile, Square),
+    Bare(Tile),
+}
+
+fn deserialize_tile_cell<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<(Tile, Square), D::Error> {
+    Ok(match SavedTileCell::deserialize(deserializer)? {
+        SavedTileCell::Covering(tile, square) => (tile, square),
+        SavedTileCell::Bare(tile) => (tile, Square::Empty),
+    })
 }
 
 impl BoardCell {
+    /**
+     * Whether the cell changes the score of a word placed on it, covered
+     * premium squares have already been used up
+     */
+    pub fn is_premium(&self) -> bool {
+        matches!(
+            self,
+            Self::DoubleLetter | Self::TripleLetter | Self::DoubleWord | Self::TripleWord
+        )
+    }
+
+    pub fn tile(&self) -> Option<Tile> {
+        match *self {
+            Self::Tile(tile, _) => Some(tile),
+            _ => None,
+        }
+    }
+
+    /**
+     * The square of the cell, under the tile if there is one
+     */
+    pub fn square(&self) -> Square {
+        match *self {
+            Self::StartingSpot => Square::StartingSpot,
+            Self::Empty => Square::Empty,
+            Self::DoubleLetter => Square::DoubleLetter,
+            Self::TripleLetter => Square::TripleLetter,
+            Self::DoubleWord => Square::DoubleWord,
+            Self::TripleWord => Square::TripleWord,
+            Self::Tile(_, square) => square,
+        }
+    }
+
+    /**
+     * The cell in the board's text encoding. Squares are a single character
+     * and tiles are followed by the square they cover, like `E3` for an E
+     * on a triple word or `E.` for one on a plain square.
+     */
+    pub fn encode(&self) -> String {
+        match *self {
+            Self::Tile(_, square) => {
+                format!("{}{}", Into::<char>::into(self), Into::<char>::into(&Self::from(square)))
+            }
+            _ => Into::<char>::into(self).to_string(),
+        }
+    }
+
+    /**
+     * Parses cells written by `BoardCell::encode`, one after the other
+     */
+    pub fn decode_all(text: &str) -> Result<Vec<BoardCell>> {
+        let mut cells = Vec::with_capacity(text.len());
+        let mut chars = text.chars().filter(|c| !c.is_whitespace());
+
+        while let Some(c) = chars.next() {
+            let cell = match c {
+                c if is_letter(c) => match chars.next() {
+                    Some(square) if SQUARE_CHARS.contains(square) => {
+                        BoardCell::Tile(Tile::Letter(c), BoardCell::from(square).square())
+                    }
+                    _ => {
+                        let msg = format!("tile {:?} is not followed by its square", c);
+                        return Err(Error::InvalidBoardLayout(msg).into());
+                    }
+                },
+                c if SQUARE_CHARS.contains(c) => BoardCell::from(c),
+                c => return Err(Error::InvalidBoardLayout(format!("unknown cell {:?}", c)).into()),
+            };
+            cells.push(cell);
+        }
+
+        Ok(cells)
+    }
+
+    pub(crate) fn word_multiplier(&self) -> u32 {
+        self.get_multiplier().word
+    }
+
+    pub(crate) fn letter_multiplier(&self) -> u32 {
+        self.get_multiplier().letter
+    }
+
     fn get_multiplier(&self) -> BoardCellMultiplier {
         match self {
             Self::DoubleLetter => BoardCellMultiplier::new(1, 2),
@@ -46,6 +184,19 @@ impl BoardCell {
     }
 }
 
+impl From<Square> for BoardCell {
+    fn from(square: Square) -> Self {
+        match square {
+            Square::StartingSpot => Self::StartingSpot,
+            Square::Empty => Self::Empty,
+            Square::DoubleLetter => Self::DoubleLetter,
+            Square::TripleLetter => Self::TripleLetter,
+            Square::DoubleWord => Self::DoubleWord,
+            Square::TripleWord => Self::TripleWord,
+        }
+    }
+}
+
 impl From<char> for BoardCell {
     fn from(c: char) -> Self {
         match c {
@@ -55,12 +206,16 @@ impl From<char> for BoardCell {
             '@' => Self::DoubleLetter,
             '#' => Self::TripleLetter,
             '+' => Self::StartingSpot,
-            'A'..='Z' => Self::Tile(Tile::Letter(c)),
+            c if is_letter(c) => Self::Tile(Tile::Letter(c), Square::Empty),
             _ => unreachable!("BoardCell:from Parsing invalid tile character {}", c),
         }
     }
 }
 
+/**
+ * The character shown for the cell, the tile's letter when it has one
+ */
+#[allow(clippy::from_over_into)]
 impl Into<char> for &BoardCell {
     fn into(self) -> char {
         match *self {
@@ -70,8 +225,8 @@ impl Into<char> for &BoardCell {
             BoardCell::TripleLetter => '#',
             BoardCell::DoubleWord => '2',
             BoardCell::TripleWord => '3',
-            BoardCell::Tile(Tile::Letter(letter)) => letter,
-            BoardCell::Tile(Tile::Blank) => unreachable!()
+            BoardCell::Tile(Tile::Letter(letter), _) => letter,
+            BoardCell::Tile(Tile::Blank, _) => unreachable!()
         }
     }
 }
@@ -87,24 +242,102 @@ impl fmt::Display for BoardCell {
  */
 trait ReadableBoard {
     fn is_in_bounds(&self, point: Point) -> bool;
-    fn get(&self, point: Point) -> Option<&BoardCell>;
-}
-
-#[inline]
-fn xy_to_idx(width: u32, point: Point) -> usize {
-    (point.y * width as i32 + point.x) as usize
+    fn get(&self, point: Point) -> Option<BoardCell>;
 }
 
+/**
+ * The board is kept in layers: the premium squares, which never change, and
+ * the tiles placed over them. On top of those it caches what move
+ * generation keeps asking about each empty square, its cross checks and
+ * whether a play can connect there. The caches are updated around each tile
+ * as it is placed, so they never need a full rescan.
+ *
+ * Cross checks come from the English word list, so they are only worked out
+ * the first time the move generator asks for one. Games in other languages
+ * never build them.
+ */
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "SavedBoard", into = "SavedBoard")]
 pub struct Board {
-    pub cells: Vec<BoardCell>,
+    squares: Vec<Square>,
+    tiles: Vec<Option<Tile>>,
+    /// Per square, the cross check for plays going across then down
+    cross_checks: OnceLock<Vec<[CrossCheck; 2]>>,
+    /// Empty squares that have a tile beside them
+    anchors: Vec<bool>,
+}
+
+/**
+ * How boards are saved, one cell per square like they always have been
+ */
+#[derive(Serialize, Deserialize)]
+struct SavedBoard {
+    #[serde(deserialize_with = "deserialize_saved_cells")]
+    cells: Vec<BoardCell>,
+}
+
+/**
+ * A saved cell, where the tiles may be missing their square
+ */
+#[derive(Deserialize)]
+enum SavedCell {
+    StartingSpot,
+    Empty,
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+    Tile(SavedTileCell),
+}
+
+fn deserialize_saved_cells<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<BoardCell>, D::Error> {
+    let cells = Vec::<SavedCell>::deserialize(deserializer)?;
+
+    Ok(cells
+        .into_iter()
+        .enumerate()
+        .map(|(idx, cell)| match cell {
+            SavedCell::StartingSpot => BoardCell::StartingSpot,
+            SavedCell::Empty => BoardCell::Empty,
+            SavedCell::DoubleLetter => BoardCell::DoubleLetter,
+            SavedCell::TripleLetter => BoardCell::TripleLetter,
+            SavedCell::DoubleWord => BoardCell::DoubleWord,
+            SavedCell::TripleWord => BoardCell::TripleWord,
+            SavedCell::Tile(SavedTileCell::Covering(tile, square)) => BoardCell::Tile(tile, square),
+            SavedCell::Tile(SavedTileCell::Bare(tile)) => BoardCell::Tile(tile, default_square(idx)),
+        })
+        .collect())
+}
+
+/**
+ * The square at `idx` of the default layout, `Square::Empty` off the board
+ */
+fn default_square(idx: usize) -> Square {
+    BOARD
+        .chars()
+        .nth(idx)
+        .map_or(Square::Empty, |c| BoardCell::from(c).square())
+}
+
+impl From<SavedBoard> for Board {
+    fn from(saved: SavedBoard) -> Board {
+        Board::from_cells(saved.cells)
+    }
+}
+
+impl From<Board> for SavedBoard {
+    fn from(board: Board) -> SavedBoard {
+        SavedBoard { cells: board.cells() }
+    }
 }
 
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for y in 0..BOARD_SIZE {
-            for x in 0..BOARD_SIZE {
-                self.get(Point::new(x as i32, y as i32)).unwrap().fmt(f)?;
+        for y in 0..BOARD_SIZE as i32 {
+            for point in row(y) {
+                self.get(point).unwrap().fmt(f)?;
             }
             writeln!(f)?
         }
@@ -115,55 +348,316 @@ impl fmt::Display for Board {
 impl ReadableBoard for Board {
     #[inline]
     fn is_in_bounds(&self, point: Point) -> bool {
-        (0..BOARD_SIZE).contains(&(point.x as u32)) && (0..BOARD_SIZE).contains(&(point.y as u32))
+        point.is_on_board()
     }
 
-    fn get(&self, point: Point) -> Option<&BoardCell> {
-        if !self.is_in_bounds(point) {
-            return None;
-        }
+    fn get(&self, point: Point) -> Option<BoardCell> {
+        Board::get(self, point)
+    }
+}
 
-        self.cells.get(xy_to_idx(BOARD_SIZE, point))
+fn check_slot(dir: Direction) -> usize {
+    if dir.is_horizontal() {
+        0
+    } else {
+        1
     }
 }
 
 impl Board {
+    #[allow(clippy::redundant_closure)]
     pub fn new() -> Board {
-        let cells = BOARD.chars().map(|x| BoardCell::from(x)).collect();
-        Board { cells }
+        Board::from_cells(BOARD.chars().map(|x| BoardCell::from(x)).collect())
+    }
+
+    /**
+     * A board from a layout in the same format as `BOARD`, one character per
+     * cell with whitespace ignored. It needs a starting spot for the first
+     * word to cover.
+     */
+    pub fn from_layout(layout: &str) -> Result<Board> {
+        let cells: Vec<char> = layout.chars().filter(|c| !c.is_whitespace()).collect();
+        let expected = (BOARD_SIZE * BOARD_SIZE) as usize;
+
+        if cells.len() != expected {
+            return Err(Error::InvalidBoardLayout(format!(
+                "expected {} cells but found {}",
+                expected,
+                cells.len()
+            ))
+            .into());
+        }
+
+        if let Some(c) = cells.iter().find(|c| !".32@#+".contains(**c)) {
+            return Err(Error::InvalidBoardLayout(format!("unknown cell {:?}", c)).into());
+        }
+
+        if !cells.contains(&'+') {
+            return Err(Error::InvalidBoardLayout("there is no starting spot".to_string()).into());
+        }
+
+        Ok(Board::from_cells(cells.into_iter().map(BoardCell::from).collect()))
+    }
+
+    /**
+     * A board from its cells, row by row
+     */
+    pub fn from_cells(cells: Vec<BoardCell>) -> Board {
+        let mut board = Board {
+            squares: Vec::with_capacity(cells.len()),
+            tiles: Vec::with_capacity(cells.len()),
+            cross_checks: OnceLock::new(),
+            anchors: vec![false; cells.len()],
+        };
+
+        for cell in cells {
+            board.squares.push(cell.square());
+            board.tiles.push(cell.tile());
+        }
+
+        for idx in 0..board.tiles.len() {
+            board.refresh(Point::from_index(idx));
+        }
+
+        board
+    }
+
+    /**
+     * Every cell of the board, row by row
+     */
+    pub fn cells(&self) -> Vec<BoardCell> {
+        (0..self.tiles.len()).map(|idx| self.cell(idx)).collect()
+    }
+
+    fn cell(&self, idx: usize) -> BoardCell {
+        match self.tiles[idx] {
+            Some(tile) => BoardCell::Tile(tile, self.squares[idx]),
+            None => self.squares[idx].into(),
+        }
+    }
+
+    /**
+     * The tile at `point`, or the square when there is no tile on it
+     */
+    pub fn get(&self, point: Point) -> Option<BoardCell> {
+        point
+            .index()
+            .filter(|idx| *idx < self.tiles.len())
+            .map(|idx| self.cell(idx))
+    }
+
+    pub fn tile_at(&self, point: Point) -> Option<Tile> {
+        point.index().and_then(|idx| self.tiles.get(idx).copied().flatten())
+    }
+
+    /**
+     * The square under `point`, whether or not a tile covers it
+     */
+    pub fn square_at(&self, point: Point) -> Option<Square> {
+        point.index().and_then(|idx| self.squares.get(idx).copied())
+    }
+
+    /**
+     * Whether `point` is empty with a tile next to it, so a play through it
+     * connects to the rest of the board
+     */
+    pub fn is_anchor(&self, point: Point) -> bool {
+        point.index().is_some_and(|idx| self.anchors[idx])
+    }
+
+    /**
+     * The letters a play going in `dir` can put on `point`, and what they
+     * would score in the other direction, checked against the English word
+     * list
+     */
+    pub fn cross_check(&self, point: Point, dir: Direction) -> CrossCheck {
+        let cross_checks = self.cross_checks.get_or_init(|| {
+            (0..self.tiles.len())
+                .map(|idx| self.checks_at(Point::from_index(idx)))
+                .collect()
+        });
+
+        point
+            .index()
+            .map_or(CrossCheck::COVERED, |idx| cross_checks[idx][check_slot(dir)])
+    }
+
+    /**
+     * Puts tiles on the board, updating the caches around each of them
+     */
+    pub(crate) fn place_tiles(&mut self, placements: &[(Point, Tile)]) {
+        for (point, tile) in placements {
+            if let Some(idx) = point.index() {
+                self.tiles[idx] = Some(*tile);
+                self.refresh_around(*point);
+            }
+        }
     }
 
-    #[allow(dead_code)]
     fn set(&mut self, point: Point, bc: BoardCell) -> Result<()> {
-        if !self.is_in_bounds(point) {
-            return Err(Error::BadAction("Out of bounds".to_string()).into());
+        let idx = point
+            .index()
+            .ok_or_else(|| Error::BadAction("Out of bounds".to_string()))?;
+
+        // Tiles keep the square that is already on the board
+        match bc {
+            BoardCell::Tile(tile, _) => self.tiles[idx] = Some(tile),
+            square => {
+                self.squares[idx] = square.square();
+                self.tiles[idx] = None;
+            }
         }
-        self.cells[xy_to_idx(BOARD_SIZE, point)] = bc;
+
+        self.refresh_around(point);
         Ok(())
     }
 
-    fn get_mut(&mut self, point: Point) -> Option<&mut BoardCell> {
-        if !self.is_in_bounds(point) {
-            return None;
+    /**
+     * The letters next to `point` going in `dir` until an empty square
+     */
+    fn letters_from(&self, point: Point, dir: Direction) -> String {
+        let mut letters = String::new();
+        let mut curr = point + dir;
+
+        while let Some(tile) = self.tile_at(curr) {
+            letters.push(tile.into());
+            curr += dir;
         }
 
-        self.cells.get_mut(xy_to_idx(BOARD_SIZE, point))
+        letters
     }
 
-    fn for_each_mut(&mut self, strip: &Strip, f: &mut dyn FnMut(Point, &mut BoardCell) -> bool) {
-        let mut loc = strip.start;
+    /**
+     * Recomputes the caches for `point` and for the empty squares at the
+     * ends of the lines of tiles running through it, which are the only
+     * ones a tile there can affect
+     */
+    fn refresh_around(&mut self, point: Point) {
+        self.refresh(point);
 
-        for _ in 0..strip.len {
-            if let Some(bc) = self.get_mut(loc) {
-                if !f(loc, bc) {
-                    return;
+        for dir in [Direction::up(), Direction::down(), Direction::left(), Direction::right()] {
+            let mut curr = point + dir;
+            while self.tile_at(curr).is_some() {
+                curr += dir;
+            }
+
+            if curr.is_on_board() {
+                self.refresh(curr);
+            }
+        }
+    }
+
+    fn refresh(&mut self, point: Point) {
+        let idx = point.index().unwrap();
+
+        if self.cross_checks.get().is_some() {
+            let checks = self.checks_at(point);
+            self.cross_checks.get_mut().unwrap()[idx] = checks;
+        }
+
+        self.anchors[idx] =
+            self.tiles[idx].is_none() && point.neighbors().any(|next| self.tile_at(next).is_some());
+    }
+
+    fn checks_at(&self, point: Point) -> [CrossCheck; 2] {
+        if self.tile_at(point).is_some() {
+            return [CrossCheck::COVERED; 2];
+        }
+
+        [Direction::right(), Direction::down()].map(|dir| {
+            let cross_dir = dir.transpose();
+            let before: String = self.letters_from(point, cross_dir * -1).chars().rev().collect();
+            CrossCheck::between(&before, &self.letters_from(point, cross_dir))
+        })
+    }
+
+    /**
+     * The board in the text encoding of `BoardCell::encode`, row by row
+     */
+    pub fn encode_cells(&self) -> String {
+        self.cells().iter().map(BoardCell::encode).collect()
+    }
+
+    /**
+     * Parses a board written by `Board::encode_cells`. Boards encoded before
+     * tiles kept their squares, with one character for every cell, parse
+     * too and get the squares of the default layout under their tiles.
+     */
+    pub fn decode_cells(text: &str) -> Result<Board> {
+        let expected = (BOARD_SIZE * BOARD_SIZE) as usize;
+        let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+        if chars.len() == expected {
+            let cells = chars
+                .into_iter()
+                .enumerate()
+                .map(|(idx, c)| match c {
+                    c if is_letter(c) => Ok(BoardCell::Tile(Tile::Letter(c), default_square(idx))),
+                    c if SQUARE_CHARS.contains(c) => Ok(BoardCell::from(c)),
+                    c => Err(Error::InvalidBoardLayout(format!("unknown cell {:?}", c)).into()),
+                })
+                .collect::<Result<Vec<BoardCell>>>()?;
+            return Ok(Board::from_cells(cells));
+        }
+
+        let cells = BoardCell::decode_all(text)?;
+        if cells.len() != expected {
+            return Err(Error::InvalidBoardLayout(format!(
+                "expected {} cells but found {}",
+                expected,
+                cells.len()
+            ))
+            .into());
+        }
+
+        Ok(Board::from_cells(cells))
+    }
+
+    /**
+     * The tiles sitting on premium squares, along with the square each one
+     * used
+     */
+    pub fn covered_premiums(&self) -> Vec<(Point, Tile, Square)> {
+        points()
+            .filter_map(|point| match self.get(point)? {
+                BoardCell::Tile(tile, square) if square.is_premium() => Some((point, tile, square)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /**
+     * Lists the cells that need to change to turn this board into `other`,
+     * along with the value they take on `other`
+     */
+    pub fn diff(&self, other: &Board) -> Vec<(Point, BoardCell)> {
+        let mut changes = Vec::new();
+
+        for point in points() {
+            if let Some(cell) = other.get(point) {
+                if self.get(point) != Some(cell) {
+                    changes.push((point, cell));
                 }
-            } else {
-                return;
             }
+        }
 
-            loc += strip.dir;
+        changes
+    }
+
+    /**
+     * Applies changes produced by `Board::diff`. Nothing is applied if any
+     * of the points are off of the board
+     */
+    pub fn apply_patch(&mut self, patch: &[(Point, BoardCell)]) -> Result<()> {
+        if patch.iter().any(|(point, _)| !self.is_in_bounds(*point)) {
+            return Err(Error::BadAction("Out of bounds".to_string()).into());
         }
+
+        for (point, cell) in patch.iter() {
+            self.set(*point, *cell)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -171,8 +665,8 @@ impl Board {
  * A decorator for a Board that places an "uncommitted" line of pieces above
  * a line on the original board
  */
-pub struct BoardWithOverlay {
-    board: Board,
+pub struct BoardWithOverlay<'a> {
+    board: &'a Board,
     strip: Strip,
     board_cells: Vec<Option<BoardCell>>,
 }
@@ -188,42 +682,54 @@ impl std::ops::Deref for OverlaidWord {
 }
 
 impl OverlaidWord {
-    pub fn calculate_word_and_score(&self) -> Result<(String, u32)> {
+    /**
+     * Scores the word letter by letter, premium squares only count for the
+     * letters that are being placed
+     */
+    pub fn calculate_score(&self, language: Language) -> Result<WordScore> {
         let mut aggregate_word = Vec::<char>::with_capacity(self.len());
+        let mut letters = Vec::<LetterScore>::with_capacity(self.len());
 
-        let mut letter_score = 0;
         let mut word_multiplier = 1;
 
         for (bc, bottom_bc) in self.iter() {
-            let curr_letter_val = match bc {
-                BoardCell::Tile(tile) => {
-                    match tile {
-                        Tile::Letter(letter) => aggregate_word.push(*letter),
-                        _ => unreachable!(),
-                    }
-                    tile.point_value()
-                }
+            let (letter, base_value) = match bc {
+                BoardCell::Tile(tile, _) => match tile {
+                    Tile::Letter(letter) => (*letter, language.tile_set().point_value(*tile)),
+                    _ => unreachable!(),
+                },
                 _ => unreachable!(),
             };
+            aggregate_word.push(letter);
 
-            if let Some(under_board_cell) = bottom_bc {
-                let BoardCellMultiplier {
-                    word: word_mult,
-                    letter: letter_mult,
-                } = under_board_cell.get_multiplier();
+            let premium = match bottom_bc {
+                Some(under_board_cell) if under_board_cell.is_premium() => {
+                    Some(under_board_cell.square())
+                }
+                _ => None,
+            };
 
-                letter_score += curr_letter_val * letter_mult;
-                word_multiplier *= word_mult;
-            } else {
-                letter_score += curr_letter_val;
-            }
+            let BoardCellMultiplier {
+                word: word_mult,
+                letter: letter_mult,
+            } = premium.map_or(BoardCellMultiplier::new(1, 1), |square| {
+                BoardCell::from(square).get_multiplier()
+            });
+
+            word_multiplier *= word_mult;
+            letters.push(LetterScore {
+                letter,
+                base_value,
+                premium,
+                letter_multiplier: letter_mult,
+            });
         }
 
         let word = aggregate_word.into_iter().collect::<String>();
-        if !DICTIONARY.contains(&word[..]) {
-            Err(Error::InvalidWord(word).into())
+        if !language.contains_word(&word)? {
+            Err(Error::InvalidWord(spell(&word)).into())
         } else {
-            Ok((word, letter_score * word_multiplier))
+            Ok(WordScore::new(spell(&word), letters, word_multiplier))
         }
     }
 
@@ -238,7 +744,7 @@ impl OverlaidWord {
     }
 }
 
-impl BoardWithOverlay {
+impl<'a> BoardWithOverlay<'a> {
     fn get_overlay_mask(
         board: &Board,
         strip: &Strip,
@@ -254,14 +760,14 @@ impl BoardWithOverlay {
             })?;
 
             match board_cell {
-                BoardCell::Tile(Tile::Letter(letter)) => {
-                    if letter == &curr_letter {
+                BoardCell::Tile(Tile::Letter(letter), _) => {
+                    if letter == curr_letter {
                         mask.push(None);
                     } else {
                         return Err(Error::BadAction("Pieces do not fit".to_string()).into());
                     }
                 }
-                _ => mask.push(Some(BoardCell::Tile(Tile::Letter(curr_letter)))),
+                square => mask.push(Some(BoardCell::Tile(Tile::Letter(curr_letter), square.square()))),
             }
 
             curr_point += strip.dir;
@@ -271,14 +777,14 @@ impl BoardWithOverlay {
     }
 
     pub fn try_overlay(
-        board: Board,
+        board: &'a Board,
         point: Point,
         dir: Direction,
         word: &str,
-    ) -> Result<BoardWithOverlay> {
-        let strip = Strip::new(point, dir, word.len() as i32);
+    ) -> Result<BoardWithOverlay<'a>> {
+        let strip = Strip::new(point, dir, word.chars().count() as i32);
 
-        let overlay_mask = Self::get_overlay_mask(&board, &strip, word)?;
+        let overlay_mask = Self::get_overlay_mask(board, &strip, word)?;
 
         let bwo = BoardWithOverlay {
             board,
@@ -294,7 +800,7 @@ impl BoardWithOverlay {
             .iter()
             .filter(|w| w.is_some())
             .map(|w| match *w {
-                Some(BoardCell::Tile(tile)) => tile,
+                Some(BoardCell::Tile(tile, _)) => tile,
                 _ => unreachable!(),
             })
             .collect()
@@ -321,11 +827,11 @@ impl BoardWithOverlay {
         let mut accum_vec = Vec::<(BoardCell, Option<BoardCell>)>::new();
 
         self.for_each_until(start, dir, &mut |point, board_cell| match *board_cell {
-            BoardCell::Tile(_) => {
+            BoardCell::Tile(..) => {
                 accum_vec.push((
-                    (*board_cell).clone(),
+                    *board_cell,
                     if self.strip.contains(point) {
-                        Some((*self.board.get(point).unwrap()).clone())
+                        self.board.get(point)
                     } else {
                         None
                     },
@@ -375,28 +881,30 @@ impl BoardWithOverlay {
         (main_line_word, branching_words)
     }
 
-    pub fn apply_to_board(mut self) -> Board {
-        let mut i = 0usize;
-        let board_cells = &self.board_cells;
+    /**
+     * The tiles the play puts on the board and where they go, for
+     * `Board::place_tiles`
+     */
+    pub fn placements(&self) -> Vec<(Point, Tile)> {
+        let mut placements = Vec::new();
 
-        self.board.for_each_mut(&self.strip, &mut |_, board_cell| {
-            if let Some(ref new_board_cell) = board_cells[i] {
-                *board_cell = new_board_cell.clone();
+        self.for_each(&self.strip, &mut |point, _| {
+            if let Some(BoardCell::Tile(tile, _)) = self.get_overlay_at(point) {
+                placements.push((point, *tile));
             }
-            i += 1;
             true
         });
 
-        self.board
+        placements
     }
 }
 
-impl ReadableBoard for BoardWithOverlay {
+impl<'a> ReadableBoard for BoardWithOverlay<'a> {
     fn is_in_bounds(&self, point: Point) -> bool {
         self.board.is_in_bounds(point)
     }
 
-    fn get(&self, point: Point) -> Option<&BoardCell> {
+    fn get(&self, point: Point) -> Option<BoardCell> {
         if !self.is_in_bounds(point) {
             return None;
         }
@@ -409,8 +917,8 @@ impl ReadableBoard for BoardWithOverlay {
         // If we are looking at a cell that is actually being overlayed,
         // and not just a part of the strip, then we return the cell
         // otherwise, we return the underlying piece
-        if let Some(ref cell) = self.get_overlay_at(point) {
-            Some(cell)
+        if let Some(cell) = self.get_overlay_at(point) {
+            Some(*cell)
         } else {
             self.board.get(point)
         }
@@ -445,7 +953,7 @@ impl<T: ReadableBoard> IterableBoard for T {
 
         loop {
             if let Some(bc) = self.get(loc) {
-                if !f(loc, bc) {
+                if !f(loc, &bc) {
                     return;
                 }
             } else {
@@ -461,7 +969,7 @@ impl<T: ReadableBoard> IterableBoard for T {
 
         for _ in 0..strip.len {
             if let Some(bc) = self.get(loc) {
-                if !f(loc, bc) {
+                if !f(loc, &bc) {
                     return;
                 }
             } else {
@@ -476,20 +984,34 @@ impl<T: ReadableBoard> IterableBoard for T {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::Game;
+
+    #[test]
+    fn layouts() {
+        assert_eq!(Board::from_layout(BOARD).unwrap().cells(), Board::new().cells());
+
+        let plain = format!("{}+{}", ".".repeat(112), ".".repeat(112));
+        let board = Board::from_layout(&plain).unwrap();
+        assert_eq!(board.get(Point::new(7, 7)).unwrap(), BoardCell::StartingSpot);
+
+        assert!(Board::from_layout("3..").is_err());
+        assert!(Board::from_layout(&".".repeat(225)).is_err());
+        assert!(Board::from_layout(&plain.replace('+', "A")).is_err());
+    }
 
     #[test]
     fn at_no_tile_test() {
         let board = Board::new();
 
-        assert_eq!(board.get(Point::new(0, 0)).unwrap(), &BoardCell::TripleWord);
+        assert_eq!(board.get(Point::new(0, 0)).unwrap(), BoardCell::TripleWord);
         assert_eq!(
             board.get(Point::new(3, 0)).unwrap(),
-            &BoardCell::DoubleLetter
+            BoardCell::DoubleLetter
         );
-        assert_eq!(board.get(Point::new(1, 1)).unwrap(), &BoardCell::DoubleWord);
+        assert_eq!(board.get(Point::new(1, 1)).unwrap(), BoardCell::DoubleWord);
         assert_eq!(
             board.get(Point::new(7, 7)).unwrap(),
-            &BoardCell::StartingSpot
+            BoardCell::StartingSpot
         );
         assert_eq!(
             board.get(Point::new(BOARD_SIZE as i32, BOARD_SIZE as i32)),
@@ -498,18 +1020,19 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn set_and_get_tiles() {
         let mut board = Board::new();
 
         assert_eq!(
             board
-                .set(Point::new(0, 0), BoardCell::Tile(Tile::Letter('A')))
+                .set(Point::new(0, 0), BoardCell::Tile(Tile::Letter('A'), Square::Empty))
                 .is_ok(),
             true
         );
         assert_eq!(
             board.get(Point::new(0, 0)).unwrap(),
-            &BoardCell::Tile(Tile::Letter('A'))
+            BoardCell::Tile(Tile::Letter('A'), Square::TripleWord)
         );
 
         assert_eq!(
@@ -523,11 +1046,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn diff_and_patch() -> Result<()> {
+        let mut board = Board::new();
+        let mut other = Board::new();
+        other.set(Point::new(7, 7), BoardCell::Tile(Tile::from('H'), Square::Empty))?;
+        other.set(Point::new(8, 7), BoardCell::Tile(Tile::from('I'), Square::Empty))?;
+
+        let patch = board.diff(&other);
+
+        assert_eq!(
+            patch,
+            vec![
+                (Point::new(7, 7), BoardCell::Tile(Tile::from('H'), Square::StartingSpot)),
+                (Point::new(8, 7), BoardCell::Tile(Tile::from('I'), Square::Empty)),
+            ]
+        );
+        assert!(other.diff(&other).is_empty());
+
+        board.apply_patch(&patch)?;
+        assert_eq!(board.cells(), other.cells());
+
+        assert!(board
+            .apply_patch(&[(Point::new(-1, 0), BoardCell::Empty)])
+            .is_err());
+        Ok(())
+    }
+
     #[test]
     fn pieces_for_place() {
         let mut board = Board::new();
-        let mut board_with_overlay =
-            BoardWithOverlay::try_overlay(board, Point::new(0, 0), Direction::new(1, 0), "HI")
+        let board_with_overlay =
+            BoardWithOverlay::try_overlay(&board, Point::new(0, 0), Direction::new(1, 0), "HI")
                 .unwrap();
 
         assert_eq!(
@@ -535,15 +1085,14 @@ mod tests {
             vec![Tile::Letter('H'), Tile::Letter('I')]
         );
 
-        board = Board::new();
         board
-            .set(Point::new(1, 0), BoardCell::Tile(Tile::Letter('E')))
+            .set(Point::new(1, 0), BoardCell::Tile(Tile::Letter('E'), Square::Empty))
             .unwrap();
         board
-            .set(Point::new(3, 0), BoardCell::Tile(Tile::Letter('L')))
+            .set(Point::new(3, 0), BoardCell::Tile(Tile::Letter('L'), Square::Empty))
             .unwrap();
-        board_with_overlay =
-            BoardWithOverlay::try_overlay(board, Point::new(0, 0), Direction::new(1, 0), "HELLO")
+        let board_with_overlay =
+            BoardWithOverlay::try_overlay(&board, Point::new(0, 0), Direction::new(1, 0), "HELLO")
                 .unwrap();
 
         assert_eq!(
@@ -553,10 +1102,12 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn pieces_for_place_err() {
-        let mut board = Board::new();
-        let mut board_with_overlay = BoardWithOverlay::try_overlay(
-            board,
+        let board = Board::new();
+
+        let board_with_overlay = BoardWithOverlay::try_overlay(
+            &board,
             Point::new(0, 0),
             Direction::new(1, 0),
             "REALLY LONG WORD THAT OVERFLOWS THE ENTIRE BOARD",
@@ -564,9 +1115,8 @@ mod tests {
 
         assert_eq!(board_with_overlay.is_err(), true);
 
-        board = Board::new();
-        board_with_overlay = BoardWithOverlay::try_overlay(
-            board,
+        let board_with_overlay = BoardWithOverlay::try_overlay(
+            &board,
             Point::new(10, 0),
             Direction::new(1, 0),
             "LONGWORD",
@@ -575,7 +1125,7 @@ mod tests {
         assert_eq!(board_with_overlay.is_err(), true);
     }
 
-    fn make_board_with_overlay() -> Result<BoardWithOverlay> {
+    fn make_board() -> Result<Board> {
         /*
          * We construct a board that looks like this (in the top left corner)
          * .P.C..
@@ -585,43 +1135,45 @@ mod tests {
          * .E.K..
          */
         let mut board = Board::new();
-        board.set(Point::new(1, 0), BoardCell::Tile(Tile::from('P')))?;
-        board.set(Point::new(1, 1), BoardCell::Tile(Tile::from('R')))?;
-        // board.set(Point::new(1, 2), BoardCell::Tile(Tile::from('I')))?;
-        board.set(Point::new(1, 3), BoardCell::Tile(Tile::from('M')))?;
-        board.set(Point::new(1, 4), BoardCell::Tile(Tile::from('E')))?;
+        board.set(Point::new(1, 0), BoardCell::Tile(Tile::from('P'), Square::Empty))?;
+        board.set(Point::new(1, 1), BoardCell::Tile(Tile::from('R'), Square::Empty))?;
+        // board.set(Point::new(1, 2), BoardCell::Tile(Tile::from('I'), Square::Empty))?;
+        board.set(Point::new(1, 3), BoardCell::Tile(Tile::from('M'), Square::Empty))?;
+        board.set(Point::new(1, 4), BoardCell::Tile(Tile::from('E'), Square::Empty))?;
 
-        board.set(Point::new(3, 0), BoardCell::Tile(Tile::from('C')))?;
-        board.set(Point::new(3, 1), BoardCell::Tile(Tile::from('R')))?;
-        // board.set(Point::new(3, 2), BoardCell::Tile(Tile::from('E')))?;
-        board.set(Point::new(3, 3), BoardCell::Tile(Tile::from('E')))?;
-        board.set(Point::new(3, 4), BoardCell::Tile(Tile::from('K')))?;
+        board.set(Point::new(3, 0), BoardCell::Tile(Tile::from('C'), Square::Empty))?;
+        board.set(Point::new(3, 1), BoardCell::Tile(Tile::from('R'), Square::Empty))?;
+        // board.set(Point::new(3, 2), BoardCell::Tile(Tile::from('E'), Square::Empty))?;
+        board.set(Point::new(3, 3), BoardCell::Tile(Tile::from('E'), Square::Empty))?;
+        board.set(Point::new(3, 4), BoardCell::Tile(Tile::from('K'), Square::Empty))?;
 
-        board.set(Point::new(4, 2), BoardCell::Tile(Tile::from('D')))?;
+        board.set(Point::new(4, 2), BoardCell::Tile(Tile::from('D'), Square::Empty))?;
 
-        let board_overlay =
-            BoardWithOverlay::try_overlay(board, Point::new(0, 2), Direction::right(), "MINED")?;
+        Ok(board)
+    }
 
-        Ok(board_overlay)
+    fn overlay_mined(board: &Board) -> Result<BoardWithOverlay<'_>> {
+        BoardWithOverlay::try_overlay(board, Point::new(0, 2), Direction::right(), "MINED")
     }
 
     #[test]
     fn get_overlay_at() -> Result<()> {
-        let board = make_board_with_overlay()?;
+        let board = make_board()?;
+        let board = overlay_mined(&board)?;
 
         assert_eq!(
             board.get_overlay_at(Point::new(0, 2)),
-            Some(&BoardCell::Tile(Tile::from('M')))
+            Some(&BoardCell::Tile(Tile::from('M'), Square::Empty))
         );
 
         assert_eq!(
             board.get_overlay_at(Point::new(1, 2)),
-            Some(&BoardCell::Tile(Tile::from('I')))
+            Some(&BoardCell::Tile(Tile::from('I'), Square::Empty))
         );
 
         assert_eq!(
             board.get_overlay_at(Point::new(3, 2)),
-            Some(&BoardCell::Tile(Tile::from('E')))
+            Some(&BoardCell::Tile(Tile::from('E'), Square::Empty))
         );
 
         assert_eq!(board.get_overlay_at(Point::new(0, 1)), None);
@@ -632,7 +1184,8 @@ mod tests {
 
     #[test]
     fn full_overlay_test() -> Result<()> {
-        let board_overlay = make_board_with_overlay()?;
+        let board = make_board()?;
+        let board_overlay = overlay_mined(&board)?;
 
         let (main_word, perp_words) = board_overlay.get_formed_words();
 
@@ -643,32 +1196,156 @@ mod tests {
     }
 
     #[test]
-    fn apply_to_board() -> Result<()> {
-        let board_overlay = make_board_with_overlay()?;
+    fn place_overlay() -> Result<()> {
+        let mut board = make_board()?;
+        let placements = overlay_mined(&board)?.placements();
 
-        let board = board_overlay.apply_to_board();
+        assert_eq!(placements.len(), 4);
+        board.place_tiles(&placements);
 
-        assert_eq!(
-            board.get(Point::new(0, 2)).unwrap(),
-            &BoardCell::Tile(Tile::from('M'))
-        );
-        assert_eq!(
-            board.get(Point::new(1, 2)).unwrap(),
-            &BoardCell::Tile(Tile::from('I'))
-        );
+        for (x, letter) in "MINED".chars().enumerate() {
+            assert_eq!(board.tile_at(Point::new(x as i32, 2)), Some(Tile::from(letter)));
+        }
         assert_eq!(
             board.get(Point::new(2, 2)).unwrap(),
-            &BoardCell::Tile(Tile::from('N'))
-        );
-        assert_eq!(
-            board.get(Point::new(3, 2)).unwrap(),
-            &BoardCell::Tile(Tile::from('E'))
+            BoardCell::Tile(Tile::from('N'), Square::DoubleWord)
         );
+
+        Ok(())
+    }
+
+    #[test]
+    fn caches_follow_placed_tiles() -> Result<()> {
+        let mut board = make_board()?;
+        // Build the cross checks first so the placements have to update them
+        board.cross_check(Point::new(0, 0), Direction::right());
+        board.place_tiles(&overlay_mined(&board)?.placements());
+        let rebuilt = Board::from_cells(board.cells());
+
+        for point in points() {
+            assert_eq!(board.is_anchor(point), rebuilt.is_anchor(point), "{:?}", point);
+            for dir in [Direction::right(), Direction::down()].iter() {
+                assert_eq!(board.cross_check(point, *dir), rebuilt.cross_check(point, *dir));
+            }
+        }
+
+        // Squares with a tile on them take nothing more
+        let check = board.cross_check(Point::new(3, 3), Direction::right());
+        assert_eq!(check, CrossCheck::COVERED);
+        assert!(board.is_anchor(Point::new(5, 2)));
+        assert!(!board.is_anchor(Point::new(7, 7)));
+        // Right after MINED, going across only extends it
+        let across = board.cross_check(Point::new(5, 2), Direction::right());
+        assert_eq!(across, CrossCheck::OPEN);
+        assert!(board.cross_check(Point::new(5, 2), Direction::down()).forms_cross_word());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cross_checks_wait_for_the_move_generator() -> Result<()> {
+        let mut board = make_board()?;
+        board.place_tiles(&overlay_mined(&board)?.placements());
+        assert!(board.cross_checks.get().is_none());
+
+        let mut game = Game::with_language(2, Language::Polish);
+        game.board.place_tiles(&[(Point::new(7, 7), Tile::from('Ł'))]);
+        assert!(game.board.cross_checks.get().is_none());
+        assert!(game.board.is_anchor(Point::new(8, 7)));
+
+        // A cross word with letters outside the word list allows nothing
+        assert_eq!(game.board.cross_check(Point::new(8, 7), Direction::down()).allowed, 0);
+        assert!(game.board.cross_checks.get().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn premiums_stay_under_tiles() -> Result<()> {
+        let mut board = Board::new();
+        board.place_tiles(&[(Point::new(0, 0), Tile::from('A'))]);
+
+        assert_eq!(board.get(Point::new(0, 0)), Some(BoardCell::Tile(Tile::from('A'), Square::TripleWord)));
+        assert_eq!(board.square_at(Point::new(0, 0)), Some(Square::TripleWord));
+        assert_eq!(board.tile_at(Point::new(1, 0)), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cell_encoding_keeps_squares() -> Result<()> {
+        let mut board = Board::new();
+        board.place_tiles(&[
+            (Point::new(0, 0), Tile::from('A')),
+            (Point::new(1, 0), Tile::from('T')),
+        ]);
+
+        let encoded = board.encode_cells();
+        assert!(encoded.starts_with("A3T..@"));
+        assert_eq!(Board::decode_cells(&encoded)?.cells(), board.cells());
         assert_eq!(
-            board.get(Point::new(4, 2)).unwrap(),
-            &BoardCell::Tile(Tile::from('D'))
+            board.covered_premiums(),
+            vec![(Point::new(0, 0), Tile::from('A'), Square::TripleWord)]
         );
 
+        // Boards encoded before tiles kept their squares
+        let old = Board::decode_cells(&format!("AT{}", &BOARD[2..]))?;
+        assert_eq!(old.cells(), board.cells());
+        assert_eq!(old.covered_premiums(), board.covered_premiums());
+        assert!(Board::decode_cells("A3T").is_err());
+        assert!(Board::decode_cells(&BOARD.replace('3', "!")).is_err());
+        assert!(Board::decode_cells(&format!("AT{}", &BOARD[1..])).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn cell_encoding_before_premiums() -> Result<()> {
+        // The S lands right before the uncovered double letter at (11, 7)
+        let mut game = Game::new(2);
+        game.players[0].hand = "ACTSEOYKRB".chars().map(Tile::from).collect();
+        game.play_word(Point::new(7, 7), Direction::right(), "ACTS")?;
+
+        let encoded = game.board.encode_cells();
+        assert!(encoded.contains("A+C.T.S.@"));
+        assert_eq!(Board::decode_cells(&encoded)?.cells(), game.board.cells());
+
         Ok(())
     }
+
+    #[test]
+    fn reads_cells_saved_without_squares() {
+        let cell: BoardCell = serde_json::from_str(r#"{"Tile":"A"}"#).unwrap();
+        assert_eq!(cell, BoardCell::Tile(Tile::from('A'), Square::Empty));
+
+        let cell = BoardCell::Tile(Tile::from('A'), Square::DoubleWord);
+        let json = serde_json::to_string(&cell).unwrap();
+        assert_eq!(serde_json::from_str::<BoardCell>(&json).unwrap(), cell);
+    }
+
+    #[test]
+    fn reads_boards_saved_without_squares() {
+        let mut board = Board::new();
+        board.place_tiles(&[
+            (Point::new(0, 0), Tile::from('A')),
+            (Point::new(1, 0), Tile::from('T')),
+        ]);
+
+        let json = serde_json::to_string(&board).unwrap();
+        let old = json
+            .replace(r#"{"Tile":["A","TripleWord"]}"#, r#"{"Tile":"A"}"#)
+            .replace(r#"{"Tile":["T","Empty"]}"#, r#"{"Tile":"T"}"#);
+        assert_ne!(old, json);
+
+        let restored: Board = serde_json::from_str(&old).unwrap();
+        assert_eq!(restored.cells(), board.cells());
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap().cells(), board.cells());
+
+        // A board with its own layout keeps the squares it saved
+        let plain = format!("{}+{}", ".".repeat(112), ".".repeat(112));
+        let mut custom = Board::from_layout(&plain).unwrap();
+        custom.place_tiles(&[(Point::new(0, 0), Tile::from('A'))]);
+        let json = serde_json::to_string(&custom).unwrap();
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap().cells(), custom.cells());
+    }
 }
@@ -0,0 +1,258 @@
+use serde::{Serialize, Deserialize};
+use super::super::error::{Error, Result};
+use super::direction::{Direction, Point};
+use super::game::{Game, PlayWordResult};
+use super::moves::Move;
+use super::tile::Tile;
+
+const MS_PER_MINUTE: i64 = 60 * 1000;
+
+/**
+ * Tournament style time control. Every player starts with the same time
+ * budget, gets `increment_ms` added after each of their turns, and loses
+ * `overtime_penalty_per_minute` points for every started minute they go
+ * over their budget.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeControl {
+    pub initial_ms: u64,
+    pub increment_ms: u64,
+    pub overtime_penalty_per_minute: u32,
+}
+
+impl TimeControl {
+    pub fn new(initial_ms: u64, increment_ms: u64, overtime_penalty_per_minute: u32) -> Self {
+        TimeControl {
+            initial_ms,
+            increment_ms,
+            overtime_penalty_per_minute,
+        }
+    }
+
+    /**
+     * The points lost for ending the game with `remaining_ms` left on the
+     * clock
+     */
+    pub fn penalty_for(&self, remaining_ms: i64) -> u32 {
+        if remaining_ms >= 0 {
+            return 0;
+        }
+
+        let started_minutes = (-remaining_ms + MS_PER_MINUTE - 1) / MS_PER_MINUTE;
+        (started_minutes as u32).saturating_mul(self.overtime_penalty_per_minute)
+    }
+}
+
+impl Game {
+    /**
+     * Starts every player's clock at the time control's budget, the current
+     * player's time starts running at `now_ms`
+     */
+    pub fn set_time_control(&mut self, time_control: TimeControl, now_ms: u64) {
+        for player in self.players.iter_mut() {
+            player.time_remaining_ms = Some(time_control.initial_ms as i64);
+        }
+
+        self.time_control = Some(time_control);
+        self.clock_updated_at = Some(now_ms);
+    }
+
+    /**
+     * Charges the time since the last update to the current player. Front-ends
+     * call this with the time of an action before performing it, the engine
+     * then adds the increment once the turn is over.
+     */
+    pub fn tick(&mut self, now_ms: u64) {
+        let updated_at = match self.clock_updated_at {
+            Some(updated_at) => updated_at,
+            None => return,
+        };
+
+        let elapsed = now_ms.saturating_sub(updated_at) as i64;
+        let player = self.get_current_player();
+        if let Some(ref mut remaining) = player.time_remaining_ms {
+            *remaining -= elapsed;
+        }
+
+        self.clock_updated_at = Some(now_ms.max(updated_at));
+    }
+
+    /**
+     * How much time a player has left at `now_ms`, negative once they are in
+     * overtime. `None` if the game is not timed.
+     */
+    pub fn time_remaining(&self, player_idx: usize, now_ms: u64) -> Option<i64> {
+        let remaining = self.players.get(player_idx)?.time_remaining_ms?;
+
+        match self.clock_updated_at {
+            Some(updated_at) if player_idx == self.get_current_player_idx() => {
+                Some(remaining - now_ms.saturating_sub(updated_at) as i64)
+            }
+            _ => Some(remaining),
+        }
+    }
+
+    /**
+     * Adds the increment to the clock of the player who just finished their
+     * turn
+     */
+    pub(super) fn add_clock_increment(&mut self, player_idx: usize) {
+        if let Some(time_control) = self.time_control {
+            if let Some(ref mut remaining) = self.players[player_idx].time_remaining_ms {
+                *remaining += time_control.increment_ms as i64;
+            }
+        }
+    }
+
+    pub fn play_word_at(
+        &mut self,
+        start: Point,
+        dir: Direction,
+        word: &str,
+        now_ms: u64,
+    ) -> Result<PlayWordResult> {
+        self.tick(now_ms);
+        self.play_word(start, dir, word)
+    }
+
+    pub fn pass_at(&mut self, now_ms: u64) {
+        self.tick(now_ms);
+        self.pass()
+    }
+
+    pub fn exchange_at(&mut self, tiles: &[Tile], now_ms: u64) -> Result<()> {
+        self.tick(now_ms);
+        self.exchange(tiles)
+    }
+
+    /**
+     * Makes `mv` at `now_ms`, see `Game::apply_move`
+     */
+    pub fn apply_move_at(&mut self, mv: &Move, now_ms: u64) -> Result<u32> {
+        self.tick(now_ms);
+        self.apply_move(mv)
+    }
+
+    /**
+     * Resigns `player_idx` at `now_ms`. When it is their turn, the time they
+     * spent on it is charged to them before the next player's clock starts.
+     */
+    pub fn resign_at(&mut self, player_idx: usize, now_ms: u64) -> Result<()> {
+        if player_idx == self.get_current_player_idx() {
+            self.tick(now_ms);
+        }
+        self.resign(player_idx)
+    }
+
+    /**
+     * Stops the clocks at the end of the game and takes the overtime
+     * penalties off of the players' scores. Returns the penalty of each
+     * player.
+     */
+    pub fn apply_time_penalties(&mut self, now_ms: u64) -> Result<Vec<u32>> {
+        let time_control = self
+            .time_control
+            .ok_or_else(|| Error::BadAction("The game is not timed".to_string()))?;
+
+        if self.clock_updated_at.is_none() {
+            return Err(Error::BadAction("The clocks have already been stopped".to_string()).into());
+        }
+
+        self.tick(now_ms);
+        self.clock_updated_at = None;
+
+        let penalties = self
+            .players
+            .iter_mut()
+            .map(|player| {
+                let penalty = time_control.penalty_for(player.time_remaining_ms.unwrap_or(0));
+                player.score = player.score.saturating_sub(penalty);
+                penalty
+            })
+            .collect();
+
+        Ok(penalties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: u64 = 60 * 1000;
+
+    fn timed_game() -> Game {
+        let mut game = Game::new(2);
+        game.set_time_control(TimeControl::new(10 * MINUTE, MINUTE, 10), 0);
+        game
+    }
+
+    #[test]
+    fn penalty_per_started_minute() {
+        let time_control = TimeControl::new(0, 0, 10);
+
+        assert_eq!(time_control.penalty_for(5000), 0);
+        assert_eq!(time_control.penalty_for(0), 0);
+        assert_eq!(time_control.penalty_for(-1), 10);
+        assert_eq!(time_control.penalty_for(-(MINUTE as i64)), 10);
+        assert_eq!(time_control.penalty_for(-(MINUTE as i64) - 1), 20);
+    }
+
+    #[test]
+    fn clock_runs_for_current_player() {
+        let mut game = timed_game();
+        game.players[0].hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+
+        assert_eq!(game.time_remaining(0, 2 * MINUTE), Some(8 * MINUTE as i64));
+        assert_eq!(game.time_remaining(1, 2 * MINUTE), Some(10 * MINUTE as i64));
+
+        game.play_word_at(Point::new(7, 7), Direction::right(), "ACTOR", 2 * MINUTE)
+            .unwrap();
+
+        // Player 0 used two minutes and got one back
+        assert_eq!(game.time_remaining(0, 5 * MINUTE), Some(9 * MINUTE as i64));
+        assert_eq!(game.time_remaining(1, 5 * MINUTE), Some(7 * MINUTE as i64));
+    }
+
+    #[test]
+    fn every_action_stops_the_clock() {
+        let mut game = timed_game();
+        game.players.push(game.players[1].clone());
+
+        game.pass_at(MINUTE);
+        let hand = game.players[1].hand[..1].to_vec();
+        game.exchange_at(&hand, 3 * MINUTE).unwrap();
+        game.resign_at(2, 4 * MINUTE).unwrap();
+        game.apply_move_at(&Move::Pass, 6 * MINUTE).unwrap();
+
+        assert_eq!(game.time_remaining(0, 6 * MINUTE), Some(9 * MINUTE as i64));
+        assert_eq!(game.time_remaining(1, 6 * MINUTE), Some(9 * MINUTE as i64));
+        assert_eq!(game.time_remaining(2, 6 * MINUTE), Some(9 * MINUTE as i64));
+        assert_eq!(game.get_current_player_idx(), 1);
+    }
+
+    #[test]
+    fn overtime_penalties_at_game_end() {
+        let mut game = timed_game();
+        game.players[0].score = 50;
+        game.players[1].score = 5;
+        game.tick(MINUTE);
+        game.players[1].time_remaining_ms = Some(-(90 * 1000));
+
+        let penalties = game.apply_time_penalties(MINUTE).unwrap();
+
+        assert_eq!(penalties, vec![0, 20]);
+        assert_eq!(game.players[0].score, 50);
+        assert_eq!(game.players[1].score, 0);
+        assert!(game.apply_time_penalties(2 * MINUTE).is_err());
+    }
+
+    #[test]
+    fn untimed_games_have_no_clock() {
+        let mut game = Game::new(2);
+        game.tick(MINUTE);
+
+        assert_eq!(game.time_remaining(0, MINUTE), None);
+        assert!(game.apply_time_penalties(MINUTE).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! Board coordinates and the notation players write them in.
+//!
+//! Points are 0-indexed with `x` as the column and `y` as the row. Scrabble
+//! notation uses letters for columns and rows numbered from 1, so the centre
+//! square (7, 7) is H8. A play puts the row first when it goes across (8H)
+//! and the column first when it goes down (H8).
+
+use super::super::constants::BOARD_SIZE;
+use super::super::error::{Error, Result};
+use super::board::Board;
+use super::direction::{Direction, Point};
+use super::moves::Move;
+
+fn coordinate_error(text: &str) -> Box<Error> {
+    Error::InvalidCoordinate(text.to_string()).into()
+}
+
+fn column_letter(x: i32) -> char {
+    (b'A' + x as u8) as char
+}
+
+/**
+ * Splits `H8` or `8H` into the column and row, along with whether the
+ * column came first
+ */
+fn parse_parts(text: &str) -> Result<(i32, i32, bool)> {
+    let text = text.trim();
+    let column_first = text.starts_with(|c: char| c.is_ascii_alphabetic());
+
+    let (column, row) = if column_first {
+        text.split_at(1)
+    } else {
+        let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+        let (row, column) = text.split_at(split);
+        (column, row)
+    };
+
+    let mut letters = column.chars();
+    let x = match (letters.next(), letters.next()) {
+        (Some(letter), None) if letter.is_ascii_alphabetic() => {
+            letter.to_ascii_uppercase() as i32 - 'A' as i32
+        }
+        _ => return Err(coordinate_error(text)),
+    };
+    // Rows like 08 are not accepted, so that there is one way to write each
+    let y = match row.parse::<i32>() {
+        Ok(number) if number.to_string() == row => number - 1,
+        _ => return Err(coordinate_error(text)),
+    };
+
+    let point = Point::new(x, y);
+    if point.is_on_board() {
+        Ok((x, y, column_first))
+    } else {
+        Err(coordinate_error(text))
+    }
+}
+
+impl Point {
+    pub fn is_on_board(self) -> bool {
+        (0..BOARD_SIZE as i32).contains(&self.x) && (0..BOARD_SIZE as i32).contains(&self.y)
+    }
+
+    /**
+     * Where the point is in `Board::cells`, `None` when it is off the board
+     */
+    pub fn index(self) -> Option<usize> {
+        if self.is_on_board() {
+            Some((self.y * BOARD_SIZE as i32 + self.x) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn from_index(idx: usize) -> Point {
+        Point::new((idx % BOARD_SIZE as usize) as i32, (idx / BOARD_SIZE as usize) as i32)
+    }
+
+    /**
+     * The point mirrored across the diagonal from the top left corner
+     */
+    pub fn transpose(self) -> Point {
+        Point::new(self.y, self.x)
+    }
+
+    /**
+     * The points above, below, left and right of this one that are on the
+     * board
+     */
+    pub fn neighbors(self) -> impl Iterator<Item = Point> {
+        vec![Direction::up(), Direction::down(), Direction::left(), Direction::right()]
+            .into_iter()
+            .map(move |dir| self + dir)
+            .filter(|point| point.is_on_board())
+    }
+
+    /**
+     * The point in Scrabble notation, e.g. `H8`
+     */
+    pub fn to_notation(self) -> String {
+        format!("{}{}", column_letter(self.x), self.y + 1)
+    }
+
+    /**
+     * Parses `H8` or `8H` in any case
+     */
+    pub fn from_notation(text: &str) -> Result<Point> {
+        parse_parts(text).map(|(x, y, _)| Point::new(x, y))
+    }
+}
+
+impl Direction {
+    /**
+     * Across becomes down and down becomes across
+     */
+    pub fn transpose(self) -> Direction {
+        Direction::new(self.y, self.x)
+    }
+}
+
+impl Board {
+    /**
+     * The board mirrored across the diagonal from the top left corner, so
+     * rows become columns
+     */
+    pub fn transpose(&self) -> Board {
+        Board::from_cells(points().map(|point| self.get(point.transpose()).unwrap()).collect())
+    }
+}
+
+impl Move {
+    /**
+     * The same move on a transposed board
+     */
+    pub fn transpose(&self) -> Move {
+        match self {
+            Move::Play { start, dir, word } => Move::Play {
+                start: start.transpose(),
+                dir: dir.transpose(),
+                word: word.clone(),
+            },
+            mv => mv.clone(),
+        }
+    }
+}
+
+/**
+ * A play's start and direction in Scrabble notation, `8H` for across and
+ * `H8` for down
+ */
+pub fn play_notation(start: Point, dir: Direction) -> String {
+    if dir.is_horizontal() {
+        format!("{}{}", start.y + 1, column_letter(start.x))
+    } else {
+        start.to_notation()
+    }
+}
+
+/**
+ * Parses a play's start and direction written like `8H` or `H8`
+ */
+pub fn parse_play_notation(text: &str) -> Result<(Point, Direction)> {
+    let (x, y, column_first) = parse_parts(text)?;
+    let dir = if column_first { Direction::down() } else { Direction::right() };
+
+    Ok((Point::new(x, y), dir))
+}
+
+/**
+ * Every point on the board, row by row
+ */
+pub fn points() -> impl Iterator<Item = Point> {
+    (0..(BOARD_SIZE * BOARD_SIZE) as usize).map(Point::from_index)
+}
+
+/**
+ * The points of row `y`, from left to right
+ */
+pub fn row(y: i32) -> impl Iterator<Item = Point> {
+    (0..BOARD_SIZE as i32).map(move |x| Point::new(x, y))
+}
+
+/**
+ * The points of column `x`, from top to bottom
+ */
+pub fn column(x: i32) -> impl Iterator<Item = Point> {
+    (0..BOARD_SIZE as i32).map(move |y| Point::new(x, y))
+}
+
+/**
+ * Row `idx` for plays going across, or column `idx` for plays going down
+ */
+pub fn line(idx: i32, dir: Direction) -> Box<dyn Iterator<Item = Point>> {
+    if dir.is_horizontal() {
+        Box::new(row(idx))
+    } else {
+        Box::new(column(idx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn notation_round_trip() {
+        let centre = Point::new(7, 7);
+        assert_eq!(centre.to_notation(), "H8");
+        assert_eq!(Point::from_notation("h8").unwrap(), centre);
+        assert_eq!(Point::from_notation("8H").unwrap(), centre);
+        assert_eq!(Point::from_notation("O15").unwrap(), Point::new(14, 14));
+
+        for text in ["P1", "A0", "A16", "8", "HH", "A08", "", "1A2"].iter() {
+            assert!(Point::from_notation(text).is_err(), "{} should not parse", text);
+        }
+    }
+
+    #[test]
+    fn play_notation_gives_the_direction() {
+        assert_eq!(play_notation(Point::new(7, 7), Direction::right()), "8H");
+        assert_eq!(play_notation(Point::new(0, 3), Direction::down()), "A4");
+
+        assert_eq!(parse_play_notation("8h").unwrap(), (Point::new(7, 7), Direction::right()));
+        assert_eq!(parse_play_notation("A4").unwrap(), (Point::new(0, 3), Direction::down()));
+    }
+
+    #[test]
+    fn transposes_boards_and_moves() {
+        let mut game = super::super::Game::new(1);
+        game.players[0].hand = "ACTORSEOYK".chars().map(super::super::Tile::from).collect();
+        let mv = Move::Play { start: Point::new(7, 7), dir: Direction::right(), word: "ACTOR".to_string() };
+        let mut transposed = game.clone();
+
+        game.apply_move(&mv).unwrap();
+        transposed.apply_move(&mv.transpose()).unwrap();
+
+        assert_eq!(transposed.board.cells(), game.board.transpose().cells());
+        assert_eq!(mv.transpose().transpose(), mv);
+    }
+
+    #[test]
+    fn iterates_over_the_board() {
+        assert_eq!(points().count(), 225);
+        assert_eq!(points().nth(16), Some(Point::new(1, 1)));
+        assert_eq!(row(3).last(), Some(Point::new(14, 3)));
+        assert_eq!(column(3).last(), Some(Point::new(3, 14)));
+        assert_eq!(line(3, Direction::down()).nth(1), Some(Point::new(3, 1)));
+        assert!(points().all(|point| Point::from_index(point.index().unwrap()) == point));
+
+        let corner: HashSet<Point> = Point::new(0, 0).neighbors().collect();
+        assert_eq!(corner, [Point::new(1, 0), Point::new(0, 1)].iter().cloned().collect());
+        assert_eq!(Point::new(7, 7).neighbors().count(), 4);
+    }
+}
@@ -0,0 +1,108 @@
+use super::super::trie::TRIE;
+use super::tile::Tile;
+
+const ALL_LETTERS: u32 = (1 << 26) - 1;
+
+/**
+ * What an empty square requires of a tile placed on it, given the tiles
+ * above and below it for plays going across (or left and right of it for
+ * plays going down). The board keeps one of these per square and direction
+ * once the move generator needs them, checked against the built in English
+ * word list.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrossCheck {
+    /// Bit set of the letters A to Z that form a valid cross word
+    pub allowed: u32,
+    /// Sum of the letter values of the cross word's existing tiles, `None`
+    /// if placing a tile here forms no cross word
+    pub cross_sum: Option<u32>,
+}
+
+impl CrossCheck {
+    /**
+     * A square with no tiles beside it, anything goes
+     */
+    pub const OPEN: CrossCheck = CrossCheck {
+        allowed: ALL_LETTERS,
+        cross_sum: None,
+    };
+
+    /**
+     * A square that already has a tile on it
+     */
+    pub const COVERED: CrossCheck = CrossCheck {
+        allowed: 0,
+        cross_sum: None,
+    };
+
+    /**
+     * The check for a square between the letters `before` and `after` of
+     * the cross word
+     */
+    pub fn between(before: &str, after: &str) -> CrossCheck {
+        if before.is_empty() && after.is_empty() {
+            return CrossCheck::OPEN;
+        }
+
+        let mut allowed = 0;
+        if let Some(node) = TRIE.walk(before) {
+            for (letter, child) in TRIE.children(node) {
+                let valid = after
+                    .chars()
+                    .try_fold(child, |node, c| TRIE.child(node, c))
+                    .is_some_and(|node| TRIE.is_terminal(node));
+
+                if valid {
+                    allowed |= 1 << (letter as u8 - b'A');
+                }
+            }
+        }
+
+        let cross_sum = before
+            .chars()
+            .chain(after.chars())
+            .map(|letter| Tile::Letter(letter).point_value())
+            .sum();
+
+        CrossCheck {
+            allowed,
+            cross_sum: Some(cross_sum),
+        }
+    }
+
+    /**
+     * Whether `letter` can go on the square
+     */
+    pub fn allows(self, letter: char) -> bool {
+        letter.is_ascii_uppercase() && self.allowed & (1 << (letter as u8 - b'A')) != 0
+    }
+
+    /**
+     * Whether a tile here would also form a word the other way
+     */
+    pub fn forms_cross_word(self) -> bool {
+        self.cross_sum.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_between_fragments() {
+        assert_eq!(CrossCheck::between("", ""), CrossCheck::OPEN);
+
+        let check = CrossCheck::between("C", "T");
+        assert!(check.allows('A'));
+        assert!(check.allows('U'));
+        assert!(!check.allows('Z'));
+        assert!(!check.allows('é'));
+        assert_eq!(check.cross_sum, Some(4));
+
+        let check = CrossCheck::between("QX", "");
+        assert_eq!(check.allowed, 0);
+        assert!(check.forms_cross_word());
+    }
+}
@@ -0,0 +1,334 @@
+use serde::{Serialize, Deserialize};
+use super::super::error::{Error, Result};
+use super::super::language::Language;
+use super::board::BoardCell;
+use super::clock::TimeControl;
+use super::direction::Point;
+use super::game::Game;
+use super::player::{Player, PlayerKind};
+use super::tile::Tile;
+
+/**
+ * Describes how a single player changed between two game states
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerDiff {
+    pub idx: usize,
+    pub previous_score: u32,
+    pub score: u32,
+    /// The new hand, only present if the hand changed
+    pub hand: Option<Vec<Tile>>,
+    pub id: Option<String>,
+    pub display_name: Option<String>,
+    pub kind: PlayerKind,
+    pub resigned: bool,
+    pub time_remaining_ms: Option<i64>,
+}
+
+impl PlayerDiff {
+    pub fn score_change(&self) -> i64 {
+        i64::from(self.score) - i64::from(self.previous_score)
+    }
+}
+
+/**
+ * The parts of a game outside of the board, the players and the bag
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameState {
+    pub turn: u32,
+    pub seat_offset: u32,
+    pub language: Language,
+    pub player_count: usize,
+    pub has_word_been_played: bool,
+    pub scoreless_turns: u32,
+    pub time_control: Option<TimeControl>,
+    pub clock_updated_at: Option<u64>,
+}
+
+impl GameState {
+    fn of(game: &Game) -> GameState {
+        GameState {
+            turn: game.turn,
+            seat_offset: game.seat_offset,
+            language: game.language,
+            player_count: game.players.len(),
+            has_word_been_played: game.has_word_been_played,
+            scoreless_turns: game.scoreless_turns,
+            time_control: game.time_control,
+            clock_updated_at: game.clock_updated_at,
+        }
+    }
+}
+
+/**
+ * Everything that changed between two game states. Applying it to the
+ * first state with `Game::apply_patch` yields the second state.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameDiff {
+    pub board: Vec<(Point, BoardCell)>,
+    pub players: Vec<PlayerDiff>,
+    pub previous_state: GameState,
+    pub state: GameState,
+    /// The new contents of the tile bag, only present if the bag changed
+    pub tile_bag: Option<Vec<Tile>>,
+}
+
+impl GameDiff {
+    /**
+     * The points where tiles were placed, useful for highlighting the
+     * last move
+     */
+    pub fn placed_tiles(&self) -> Vec<(Point, Tile)> {
+        self.board
+            .iter()
+            .filter_map(|(point, cell)| match cell {
+                BoardCell::Tile(tile, _) => Some((*point, *tile)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.board.is_empty()
+            && self.players.is_empty()
+            && self.tile_bag.is_none()
+            && self.previous_state == self.state
+    }
+}
+
+impl Game {
+    /**
+     * Describes the changes needed to turn this game into `other`. Players
+     * only present in `other` show up with their full hand.
+     */
+    pub fn diff(&self, other: &Game) -> GameDiff {
+        let mut players = Vec::new();
+
+        for (idx, new_player) in other.players.iter().enumerate() {
+            let old_player = self.players.get(idx);
+            let previous_score = old_player.map_or(0, |p| p.score);
+            let hand_changed = old_player.is_none_or(|p| p.hand != new_player.hand);
+            let seat_changed = old_player.is_none_or(|p| {
+                p.id != new_player.id
+                    || p.display_name != new_player.display_name
+                    || p.kind != new_player.kind
+                    || p.resigned != new_player.resigned
+                    || p.time_remaining_ms != new_player.time_remaining_ms
+            });
+
+            if previous_score != new_player.score || hand_changed || seat_changed {
+                players.push(PlayerDiff {
+                    idx,
+                    previous_score,
+                    score: new_player.score,
+                    hand: if hand_changed { Some(new_player.hand.clone()) } else { None },
+                    id: new_player.id.clone(),
+                    display_name: new_player.display_name.clone(),
+                    kind: new_player.kind,
+                    resigned: new_player.resigned,
+                    time_remaining_ms: new_player.time_remaining_ms,
+                });
+            }
+        }
+
+        GameDiff {
+            board: self.board.diff(&other.board),
+            players,
+            previous_state: GameState::of(self),
+            state: GameState::of(other),
+            tile_bag: if self.tile_bag.tiles != other.tile_bag.tiles {
+                Some(other.tile_bag.tiles.clone())
+            } else {
+                None
+            },
+        }
+    }
+
+    /**
+     * Applies a diff created by `Game::diff`. The game is left untouched if
+     * the diff does not fit this game.
+     */
+    pub fn apply_patch(&mut self, patch: &GameDiff) -> Result<()> {
+        let mut game = self.clone();
+
+        game.board.apply_patch(&patch.board)?;
+
+        for player_diff in patch.players.iter() {
+            if player_diff.idx == game.players.len() {
+                game.players.push(Player::default());
+            }
+
+            let player = game.players.get_mut(player_diff.idx).ok_or_else(|| {
+                Error::BadAction(format!("Player {} does not exist", player_diff.idx))
+            })?;
+
+            player.score = player_diff.score;
+            if let Some(ref hand) = player_diff.hand {
+                player.hand = hand.clone();
+            }
+            player.id = player_diff.id.clone();
+            player.display_name = player_diff.display_name.clone();
+            player.kind = player_diff.kind;
+            player.resigned = player_diff.resigned;
+            player.time_remaining_ms = player_diff.time_remaining_ms;
+        }
+
+        let state = &patch.state;
+        if state.player_count > game.players.len() {
+            return Err(Error::BadAction(format!(
+                "The patch is missing players {} to {}",
+                game.players.len(),
+                state.player_count - 1
+            ))
+            .into());
+        }
+        game.players.truncate(state.player_count);
+
+        if let Some(ref tiles) = patch.tile_bag {
+            game.tile_bag.tiles = tiles.clone();
+        }
+
+        game.turn = state.turn;
+        game.seat_offset = state.seat_offset;
+        game.language = state.language;
+        game.has_word_been_played = state.has_word_been_played;
+        game.scoreless_turns = state.scoreless_turns;
+        game.time_control = state.time_control;
+        game.clock_updated_at = state.clock_updated_at;
+
+        *self = game;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::direction::Direction;
+    use super::super::board::Square;
+
+    #[test]
+    fn diff_after_play() -> Result<()> {
+        let before = Game::new(2);
+        let mut after = before.clone();
+        after.players[0].hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+        after.play_word(Point::new(7, 7), Direction::right(), "ACTOR")?;
+
+        let diff = before.diff(&after);
+
+        assert_eq!(diff.placed_tiles().len(), 5);
+        assert_eq!(diff.placed_tiles()[0], (Point::new(7, 7), Tile::from('A')));
+        assert_eq!(diff.players.len(), 1);
+        assert_eq!(diff.players[0].score_change(), 8);
+        assert_eq!(diff.state.turn, 1);
+        assert!(diff.tile_bag.is_some());
+
+        let mut patched = before.clone();
+        patched.apply_patch(&diff)?;
+        assert_eq!(patched.serialize(), after.serialize());
+
+        assert!(after.diff(&after).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn patch_follows_joins_and_resignations() -> Result<()> {
+        let before = Game::with_players(&[
+            ("U1", "Ann", PlayerKind::Human),
+            ("U2", "Bo", PlayerKind::Bot),
+            ("U3", "Cy", PlayerKind::Human),
+        ]);
+        let mut after = before.clone();
+        let idx = after.add_player()?;
+        after.players[idx].id = Some("U4".to_string());
+        after.players[idx].display_name = Some("Di".to_string());
+        after.resign(0)?;
+
+        let mut patched = before.clone();
+        patched.apply_patch(&before.diff(&after))?;
+        assert_eq!(patched.serialize(), after.serialize());
+        assert_eq!(patched.get_current_player_idx(), after.get_current_player_idx());
+
+        // Going back drops the seat that joined
+        let mut reverted = after.clone();
+        reverted.apply_patch(&after.diff(&before))?;
+        assert_eq!(reverted.serialize(), before.serialize());
+        Ok(())
+    }
+
+    #[test]
+    fn patch_keeps_the_clocks() -> Result<()> {
+        let mut before = Game::new(2);
+        before.set_time_control(TimeControl::new(60_000, 1000, 10), 0);
+        let mut after = before.clone();
+        after.pass_at(5000);
+
+        let mut patched = before.clone();
+        patched.apply_patch(&before.diff(&after))?;
+        assert_eq!(patched.serialize(), after.serialize());
+        assert_eq!(patched.time_remaining(0, 5000), Some(56_000));
+        Ok(())
+    }
+
+    #[test]
+    fn passing_is_a_change() -> Result<()> {
+        let before = Game::new(2);
+        let mut after = before.clone();
+        after.pass();
+
+        let diff = before.diff(&after);
+        assert!(!diff.is_empty());
+        assert!(diff.board.is_empty() && diff.players.is_empty() && diff.tile_bag.is_none());
+
+        let mut patched = before.clone();
+        patched.apply_patch(&diff)?;
+        assert_eq!(patched.serialize(), after.serialize());
+
+        let mut moved = before.clone();
+        moved.seat_offset = 1;
+        assert!(!before.diff(&moved).is_empty());
+
+        let mut spanish = before.clone();
+        spanish.language = Language::Spanish;
+        let diff = before.diff(&spanish);
+        assert!(!diff.is_empty());
+        let mut patched = before.clone();
+        patched.apply_patch(&diff)?;
+        assert_eq!(patched.language, Language::Spanish);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bad_patch_leaves_game_untouched() {
+        let mut game = Game::new(2);
+        let serialized = game.serialize();
+
+        let patch = GameDiff {
+            board: vec![(Point::new(7, 7), BoardCell::Tile(Tile::from('A'), Square::StartingSpot))],
+            players: vec![PlayerDiff {
+                idx: 5,
+                previous_score: 0,
+                score: 10,
+                hand: None,
+                id: None,
+                display_name: None,
+                kind: PlayerKind::Human,
+                resigned: false,
+                time_remaining_ms: None,
+            }],
+            previous_state: GameState::of(&game),
+            state: GameState {
+                turn: 1,
+                has_word_been_played: true,
+                ..GameState::of(&game)
+            },
+            tile_bag: None,
+        };
+
+        assert!(game.apply_patch(&patch).is_err());
+        assert_eq!(game.serialize(), serialized);
+    }
+}
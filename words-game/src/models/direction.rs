@@ -1,6 +1,7 @@
+use serde::{Serialize, Deserialize};
 use std::ops;
 
-#[derive(Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Serialize, Deserialize)]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -30,7 +31,7 @@ impl ops::AddAssign<Direction> for Point {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Direction {
     pub x: i32,
     pub y: i32,
@@ -106,6 +107,7 @@ impl Strip {
     /**
      * Get the distance of the point from the start of the strip
      */
+    #[allow(clippy::unnecessary_cast)]
     pub fn distance_in(&self, point: Point) -> Option<i32> {
         if !self.contains(point) {
             None
@@ -122,6 +124,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn strip_contains_horizontal() {
         let s = Strip::new(
             Point::new(5, 5),
@@ -141,6 +144,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn strip_contains_vertical_negative() {
         let s = Strip::new(
             Point::new(5, 5),
@@ -1,7 +1,11 @@
 use serde::{Serialize, Deserialize};
+use super::super::constants::HAND_SIZE;
 use super::super::error::*;
+use super::super::language::Language;
 use super::board::{Board, OverlaidWord, BoardWithOverlay};
-use super::player::Player;
+use super::clock::TimeControl;
+use super::score::WordScore;
+use super::player::{remove_tiles, Player, PlayerKind};
 use super::direction::*;
 use super::tile::{TileBag, Tile};
 use std::fmt;
@@ -10,6 +14,8 @@ use std::fmt;
 pub struct PlayWordResult {
     pub words: Vec<String>,
     pub score: u32,
+    /// How each of the formed words was scored, in the same order as `words`
+    pub breakdown: Vec<WordScore>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +25,24 @@ pub struct Game {
     pub turn: u32,
     pub tile_bag: TileBag,
     pub has_word_been_played: bool,
+    /// Shifts the seat that `turn` points at, it changes when players join
+    /// or resign so that turn order stays correct
+    #[serde(default)]
+    pub seat_offset: u32,
+    #[serde(default)]
+    pub time_control: Option<TimeControl>,
+    /// When the current player's clock last got charged, `None` while the
+    /// clocks are not running
+    #[serde(default)]
+    pub clock_updated_at: Option<u64>,
+    /// Picks the tile set and the dictionary, games saved before languages
+    /// were added are English
+    #[serde(default)]
+    pub language: Language,
+    /// Turns in a row where nobody scored, the game ends once everyone has
+    /// had two of them
+    #[serde(default)]
+    pub scoreless_turns: u32,
 }
 
 impl fmt::Display for Game {
@@ -27,11 +51,11 @@ impl fmt::Display for Game {
         self.board.fmt(f)?;
 
         writeln!(f, "Players:")?;
-        for (idx, ref player) in self.players.iter().enumerate() {
+        for (idx, player) in self.players.iter().enumerate() {
             writeln!(
                 f,
-                "{}: score: {}, pieces: {:?}",
-                idx, player.score, player.hand
+                "{} ({}): score: {}, pieces: {:?}",
+                idx, player.name(idx), player.score, player.hand
             )?;
         }
 
@@ -54,9 +78,9 @@ fn ensure_play_builds_on_other_words(
 ) -> Result<()> {
     if used_tiles.is_empty() {
         Err(Error::NoLettersUsed.into())
-    } else if main_line_word.len() == original_word.len()
+    } else if main_line_word.len() == original_word.chars().count()
         && branching_words.is_empty()
-        && used_tiles.len() == original_word.len()
+        && used_tiles.len() == original_word.chars().count()
     {
         Err(Error::WordDoesNotIntersect.into())
     } else {
@@ -67,10 +91,92 @@ fn ensure_play_builds_on_other_words(
 impl Game {
     fn increment_turn(&mut self) {
         self.turn += 1;
+        self.skip_resigned_players();
+    }
+
+    /**
+     * Finishes the current player's turn, in which they scored `score`, and
+     * moves on to the next player
+     */
+    pub(super) fn end_turn(&mut self, score: u32) {
+        let player_idx = self.get_current_player_idx();
+        self.add_clock_increment(player_idx);
+        self.scoreless_turns = if score == 0 { self.scoreless_turns + 1 } else { 0 };
+        self.increment_turn();
+    }
+
+    /**
+     * Moves the current seat forward until it reaches a player that has not
+     * resigned, without counting as a turn
+     */
+    fn skip_resigned_players(&mut self) {
+        for _ in 0..self.players.len() {
+            if !self.players[self.get_current_player_idx()].resigned {
+                return;
+            }
+            self.seat_offset += 1;
+        }
     }
 
     pub fn get_current_player_idx(&self) -> usize {
-        (self.turn as usize) % self.players.len()
+        (self.turn as usize + self.seat_offset as usize) % self.players.len()
+    }
+
+    /**
+     * The number of players that have not resigned
+     */
+    pub fn active_player_count(&self) -> usize {
+        self.players.iter().filter(|player| !player.resigned).count()
+    }
+
+    /**
+     * Adds a new player at the end of the turn order with a fresh hand drawn
+     * from the tile bag. The current player stays the same, the new player
+     * gets their turn once everyone before them has played. Returns the
+     * index of the new player.
+     */
+    pub fn add_player(&mut self) -> Result<usize> {
+        if self.tile_bag.tiles.is_empty() {
+            return Err(Error::NotEnoughTiles.into());
+        }
+
+        let current_idx = self.get_current_player_idx();
+        let hand = self.tile_bag.draw_upto(HAND_SIZE);
+
+        self.players.push(Player {
+            hand,
+            time_remaining_ms: self.time_control.map(|tc| tc.initial_ms as i64),
+            ..Player::default()
+        });
+
+        // Pick the offset that keeps the same player up with the new count
+        let player_count = self.players.len();
+        let turn_seat = self.turn as usize % player_count;
+        self.seat_offset = ((current_idx + player_count - turn_seat) % player_count) as u32;
+
+        Ok(player_count - 1)
+    }
+
+    /**
+     * Takes a player out of the game. Their hand goes back into the tile bag
+     * and their score is frozen, they are skipped in the turn order from now
+     * on. If it was their turn, the next player is up.
+     */
+    pub fn resign(&mut self, player_idx: usize) -> Result<()> {
+        let player = self.players.get_mut(player_idx).ok_or_else(|| {
+            Error::BadAction(format!("Player {} does not exist", player_idx))
+        })?;
+
+        if player.resigned {
+            return Err(Error::BadAction("Player has already resigned".to_string()).into());
+        }
+
+        player.resigned = true;
+        let hand = std::mem::take(&mut player.hand);
+        self.tile_bag.return_to(&hand);
+
+        self.skip_resigned_players();
+        Ok(())
     }
 
     pub fn get_current_player(&mut self) -> &mut Player {
@@ -78,9 +184,26 @@ impl Game {
         &mut self.players[idx]
     }
 
+    pub fn player_idx_by_id(&self, id: &str) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|player| player.id.as_deref() == Some(id))
+    }
+
+    pub fn player_by_id(&self, id: &str) -> Option<&Player> {
+        self.player_idx_by_id(id).map(|idx| &self.players[idx])
+    }
+
     pub fn new(player_count: usize) -> Game {
+        Game::with_language(player_count, Language::English)
+    }
+
+    /**
+     * A game played with the tiles and dictionary of `language`
+     */
+    pub fn with_language(player_count: usize, language: Language) -> Game {
         let board = Board::new();
-        let mut tile_bag = TileBag::new();
+        let mut tile_bag = TileBag::for_language(language);
         let mut players = Vec::with_capacity(player_count);
 
         for _ in 0..player_count {
@@ -93,22 +216,52 @@ impl Game {
             turn: 0,
             tile_bag,
             has_word_been_played: false,
+            seat_offset: 0,
+            time_control: None,
+            clock_updated_at: None,
+            language,
+            scoreless_turns: 0,
         }
     }
 
-    pub fn play_word(&mut self, start: Point, dir: Direction, word: &str) -> Result<PlayWordResult> {
-        let mut game = self.clone();
+    /**
+     * Creates a game with one seat per `(id, display_name, kind)` entry, in
+     * the order they are passed in
+     */
+    pub fn with_players(seats: &[(&str, &str, PlayerKind)]) -> Game {
+        Game::with_players_in(seats, Language::English)
+    }
+
+    /**
+     * Like `with_players`, for a game played in `language`
+     */
+    pub fn with_players_in(seats: &[(&str, &str, PlayerKind)], language: Language) -> Game {
+        let mut game = Game::with_language(seats.len(), language);
+
+        for (player, (id, display_name, kind)) in game.players.iter_mut().zip(seats.iter()) {
+            player.id = Some(id.to_string());
+            player.display_name = Some(display_name.to_string());
+            player.kind = *kind;
+        }
+
+        game
+    }
 
-        let board_overlay =
-            BoardWithOverlay::try_overlay(game.board.clone(), start, dir, word)?;
+    /**
+     * Plays `word` from `start` for the current player. The play is checked
+     * against the board as it is, the game only changes once it has passed
+     * every check.
+     */
+    pub fn play_word(&mut self, start: Point, dir: Direction, word: &str) -> Result<PlayWordResult> {
+        let word = &self.language.tile_set().normalize_word(word)?;
+        let board_overlay = BoardWithOverlay::try_overlay(&self.board, start, dir, word)?;
 
         // Check validity of formed words, and add up the score
         let (main_line_word, branching_words) = board_overlay.get_formed_words();
         let needed_tiles = board_overlay.get_overlaid_letters();
 
-        if !game.has_word_been_played {
+        if !self.has_word_been_played {
             main_line_word.ensure_word_covering_starting_spot()?;
-            game.has_word_been_played = true;
         } else {
             ensure_play_builds_on_other_words(
                 word,
@@ -118,36 +271,35 @@ impl Game {
             )?;
         }
 
-        let mut total_score = 0u32;
-        let mut total_formed_words = Vec::<String>::new();
-
-        let (word, score) = main_line_word.calculate_word_and_score()?;
-        total_formed_words.push(word);
-        total_score += score;
+        let mut breakdown = vec![main_line_word.calculate_score(self.language)?];
 
         for branching_word in branching_words.iter() {
-            let (word, score) = branching_word.calculate_word_and_score()?;
-            total_formed_words.push(word);
-            total_score += score;
+            breakdown.push(branching_word.calculate_score(self.language)?);
         }
 
+        let total_score = breakdown.iter().map(|word_score| word_score.score).sum();
+        let total_formed_words = breakdown.iter().map(|word_score| word_score.word.clone()).collect();
+
         // Check to make sure the player has the letters to make this play
-        let new_tiles = game.tile_bag.draw_upto(needed_tiles.len());
-        let player = game.get_current_player();
+        let hand = remove_tiles(&self.players[self.get_current_player_idx()].hand, &needed_tiles)?;
+        let placements = board_overlay.placements();
+
+        // Apply new board state to the game
+        self.board.place_tiles(&placements);
+        self.has_word_been_played = true;
+
+        let new_tiles = self.tile_bag.draw_upto(needed_tiles.len());
+        let player = self.get_current_player();
 
-        player.remove_tiles_from_hand(&needed_tiles)?;
+        player.hand = hand;
         player.add_tiles_to_hand(new_tiles);
 
         // Apply new score to the player
         player.add_score(total_score);
 
-        // Apply new board state to the game
-        game.board = board_overlay.apply_to_board();
-
-        game.increment_turn();
+        self.end_turn(total_score);
 
-        *self = game;
-        Ok(PlayWordResult{words: total_formed_words, score: total_score})
+        Ok(PlayWordResult{words: total_formed_words, score: total_score, breakdown})
     }
 
     pub fn serialize(&self) -> String {
@@ -162,6 +314,7 @@ impl Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::super::language::install_dictionary;
 
     #[test]
     fn player_count() {
@@ -171,7 +324,7 @@ mod tests {
 
     #[test]
     fn board_size() {
-        assert_eq!(Game::new(1).board.cells.len(), 225);
+        assert_eq!(Game::new(1).board.cells().len(), 225);
     }
 
     #[test]
@@ -188,4 +341,108 @@ mod tests {
 
         assert_eq!(game.players.len(), deserialized.players.len());
     }
+
+    fn play_first_word(game: &mut Game) {
+        game.get_current_player().hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+        game.play_word(Point::new(7, 7), Direction::right(), "ACTOR").unwrap();
+    }
+
+    #[test]
+    fn play_word_normalizes_input() {
+        let mut game = Game::new(2);
+        game.get_current_player().hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+
+        let err = game.play_word(Point::new(7, 7), Direction::right(), "act0r").unwrap_err();
+        assert!(matches!(*err, Error::InvalidCharacter('0')));
+
+        let result = game.play_word(Point::new(7, 7), Direction::right(), " Actor ").unwrap();
+        assert_eq!(result.words, vec!["ACTOR".to_string()]);
+    }
+
+    #[test]
+    fn plays_in_other_languages() {
+        let mut game = Game::with_language(2, Language::Polish);
+        game.get_current_player().hand = "KOTABCDEFG".chars().map(Tile::from).collect();
+        let err = game.play_word(Point::new(7, 7), Direction::right(), "kot").unwrap_err();
+        assert!(matches!(*err, Error::MissingDictionary(_)));
+
+        install_dictionary(Language::Spanish, "CHORRO\nNIÑO").unwrap();
+        let mut game = Game::with_language(2, Language::Spanish);
+        assert_eq!(game.tile_bag.tiles.len() + 2 * HAND_SIZE, 98);
+
+        let hand = Language::Spanish.tile_set().normalize_tiles("chorroniñoae").unwrap();
+        game.get_current_player().hand = hand;
+        let result = game.play_word(Point::new(7, 7), Direction::right(), "Chorro").unwrap();
+        assert_eq!(result.words, vec!["CHORRO".to_string()]);
+        assert_eq!(result.score, 15);
+        assert_eq!(game.players[0].hand.len(), HAND_SIZE);
+
+        let err = Game::new(2).play_word(Point::new(7, 7), Direction::right(), "łódź").unwrap_err();
+        assert!(matches!(*err, Error::InvalidCharacter('ł')));
+    }
+
+    #[test]
+    fn add_player_keeps_turn_order() {
+        let mut game = Game::new(2);
+        play_first_word(&mut game);
+        assert_eq!(game.get_current_player_idx(), 1);
+
+        let bag_size = game.tile_bag.tiles.len();
+        assert_eq!(game.add_player().unwrap(), 2);
+        assert_eq!(game.players[2].hand.len(), HAND_SIZE);
+        assert_eq!(game.tile_bag.tiles.len(), bag_size - HAND_SIZE);
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            order.push(game.get_current_player_idx());
+            game.increment_turn();
+        }
+        assert_eq!(order, vec![1, 2, 0, 1]);
+    }
+
+    #[test]
+    fn resigned_players_are_skipped() {
+        let mut game = Game::new(3);
+        let bag_size = game.tile_bag.tiles.len();
+
+        game.resign(1).unwrap();
+        assert!(game.players[1].hand.is_empty());
+        assert_eq!(game.tile_bag.tiles.len(), bag_size + HAND_SIZE);
+        assert_eq!(game.active_player_count(), 2);
+        assert!(game.resign(1).is_err());
+
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            order.push(game.get_current_player_idx());
+            game.increment_turn();
+        }
+        assert_eq!(order, vec![0, 2, 0, 2]);
+    }
+
+    #[test]
+    fn resigning_on_your_turn_passes_it_on() {
+        let mut game = Game::new(2);
+        game.players[0].score = 12;
+
+        game.resign(0).unwrap();
+
+        assert_eq!(game.get_current_player_idx(), 1);
+        assert_eq!(game.players[0].score, 12);
+        assert_eq!(game.turn, 0);
+    }
+
+    #[test]
+    fn player_lookup_by_id() {
+        let game = Game::with_players(&[
+            ("U1", "alice", PlayerKind::Human),
+            ("U2", "bot", PlayerKind::Bot),
+        ]);
+
+        assert_eq!(game.player_idx_by_id("U2"), Some(1));
+        assert_eq!(game.player_by_id("U1").unwrap().name(0), "alice");
+        assert!(game.player_by_id("U3").is_none());
+
+        let deserialized = Game::from_serialized(&game.serialize()).unwrap();
+        assert_eq!(deserialized.player_by_id("U2").unwrap().kind, PlayerKind::Bot);
+    }
 }
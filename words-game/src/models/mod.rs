@@ -1,11 +1,27 @@
 mod board;
+mod clock;
+pub mod coordinates;
+mod cross_check;
+mod diff;
 mod direction;
 mod game;
+mod moves;
 mod player;
+mod score;
 mod tile;
+mod unseen;
+mod view;
 
-pub use board::{Board, BoardCell};
+pub use board::{Board, BoardCell, Square};
+pub use clock::TimeControl;
+pub use cross_check::CrossCheck;
+pub use diff::{GameDiff, GameState, PlayerDiff};
 pub use direction::*;
 pub use game::{Game, PlayWordResult};
-pub use player::{Player};
+pub use moves::Move;
+pub use player::{Player, PlayerKind};
+pub(crate) use player::remove_tiles;
+pub use score::{LetterScore, WordScore};
 pub use tile::{Tile, TileBag};
+pub use unseen::UnseenTiles;
+pub use view::{PlayerView, PublicPlayer};
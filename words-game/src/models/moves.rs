@@ -0,0 +1,210 @@
+use serde::{Serialize, Deserialize};
+use super::super::error::{Error, Result};
+use super::direction::{Direction, Point};
+use super::game::Game;
+use super::tile::Tile;
+
+/**
+ * Any of the actions a player can take on their turn
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Move {
+    Play {
+        start: Point,
+        dir: Direction,
+        word: String,
+    },
+    Pass,
+    Exchange(Vec<Tile>),
+}
+
+impl Game {
+    /**
+     * The current player gives up their turn
+     */
+    pub fn pass(&mut self) {
+        self.end_turn(0);
+    }
+
+    /**
+     * The current player swaps `tiles` from their hand for new ones from the
+     * tile bag, which uses up their turn
+     */
+    pub fn exchange(&mut self, tiles: &[Tile]) -> Result<()> {
+        if tiles.is_empty() {
+            return Err(Error::NoLettersUsed.into());
+        }
+
+        if self.tile_bag.tiles.len() < tiles.len() {
+            return Err(Error::NotEnoughTiles.into());
+        }
+
+        let mut game = self.clone();
+
+        let new_tiles = game.tile_bag.draw(tiles.len())?;
+        let player = game.get_current_player();
+        player.remove_tiles_from_hand(tiles)?;
+        player.add_tiles_to_hand(new_tiles);
+
+        game.tile_bag.return_to(tiles);
+        game.end_turn(0);
+
+        *self = game;
+        Ok(())
+    }
+
+    /**
+     * Makes `mv` for the current player, returning the points it scored
+     */
+    pub fn apply_move(&mut self, mv: &Move) -> Result<u32> {
+        match mv {
+            Move::Play { start, dir, word } => Ok(self.play_word(*start, *dir, word)?.score),
+            Move::Pass => {
+                self.pass();
+                Ok(0)
+            }
+            Move::Exchange(tiles) => {
+                self.exchange(tiles)?;
+                Ok(0)
+            }
+        }
+    }
+
+    /**
+     * The game ends when someone uses their last tile with the bag empty, or
+     * when every player has gone twice without scoring
+     */
+    pub fn is_over(&self) -> bool {
+        let went_out = self.tile_bag.tiles.is_empty()
+            && self.players.iter().any(|player| !player.resigned && player.hand.is_empty());
+
+        went_out || self.scoreless_turns as usize >= 2 * self.active_player_count()
+    }
+
+    /**
+     * The scores once the game is over: everyone loses the value of the tiles
+     * left on their rack, and whoever went out with the bag empty gains the
+     * value of everyone else's
+     */
+    pub fn final_scores(&self) -> Vec<u32> {
+        let tile_set = self.language.tile_set();
+        let rack_values: Vec<u32> = self
+            .players
+            .iter()
+            .map(|player| player.hand.iter().map(|tile| tile_set.point_value(*tile)).sum())
+            .collect();
+        let went_out = self
+            .players
+            .iter()
+            .position(|player| !player.resigned && player.hand.is_empty())
+            .filter(|_| self.tile_bag.tiles.is_empty());
+        let left_on_racks: u32 = rack_values.iter().sum();
+
+        self.players
+            .iter()
+            .zip(rack_values)
+            .enumerate()
+            .map(|(idx, (player, rack_value))| {
+                if went_out == Some(idx) {
+                    player.score + left_on_racks
+                } else {
+                    player.score.saturating_sub(rack_value)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pass_moves_to_next_player() {
+        let mut game = Game::new(2);
+
+        assert_eq!(game.apply_move(&Move::Pass).unwrap(), 0);
+        assert_eq!(game.get_current_player_idx(), 1);
+        assert_eq!(game.turn, 1);
+    }
+
+    #[test]
+    fn exchange_swaps_tiles() {
+        let mut game = Game::new(2);
+        game.players[0].hand = "AAAAAEEEEE".chars().map(Tile::from).collect();
+        game.tile_bag.tiles = "XZQ".chars().map(Tile::from).collect();
+
+        game.exchange(&[Tile::from('A'), Tile::from('A')]).unwrap();
+
+        let hand = &game.players[0].hand;
+        assert_eq!(hand.len(), 10);
+        assert_eq!(hand.iter().filter(|tile| **tile == Tile::from('A')).count(), 3);
+        assert!(hand.contains(&Tile::from('X')) && hand.contains(&Tile::from('Z')));
+        assert_eq!(game.tile_bag.tiles.len(), 3);
+        assert_eq!(game.get_current_player_idx(), 1);
+    }
+
+    #[test]
+    fn exchange_needs_tiles_in_hand() {
+        let mut game = Game::new(2);
+        game.players[0].hand = "AAAAAEEEEE".chars().map(Tile::from).collect();
+
+        assert!(game.exchange(&[Tile::from('Z')]).is_err());
+        assert!(game.exchange(&[]).is_err());
+        assert_eq!(game.turn, 0);
+
+        game.tile_bag.tiles.clear();
+        assert!(game.exchange(&[Tile::from('A')]).is_err());
+    }
+
+    #[test]
+    fn ends_after_everyone_passes_twice() {
+        let mut game = Game::new(2);
+
+        for _ in 0..3 {
+            game.pass();
+        }
+        assert!(!game.is_over());
+        game.pass();
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn scoring_resets_the_scoreless_turns() {
+        let mut game = Game::new(2);
+        game.pass();
+        game.players[1].hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+
+        let score = game.apply_move(&Move::Play {
+            start: Point::new(7, 7),
+            dir: Direction::right(),
+            word: "ACTOR".to_string(),
+        });
+        assert_eq!(score.unwrap(), 8);
+        assert_eq!(game.scoreless_turns, 0);
+    }
+
+    #[test]
+    fn ends_when_someone_goes_out() {
+        let mut game = Game::new(2);
+        game.tile_bag.tiles.clear();
+        assert!(!game.is_over());
+
+        game.players[1].hand.clear();
+        assert!(game.is_over());
+    }
+
+    #[test]
+    fn racks_count_at_the_end() {
+        let mut game = Game::new(3);
+        game.tile_bag.tiles.clear();
+        for (player, (score, hand)) in game.players.iter_mut().zip([(50, ""), (40, "QZ"), (5, "AEX")]) {
+            player.score = score;
+            player.hand = hand.chars().map(Tile::from).collect();
+        }
+        assert_eq!(game.final_scores(), vec![80, 20, 0]);
+
+        game.tile_bag.tiles.push(Tile::from('E'));
+        assert_eq!(game.final_scores(), vec![50, 20, 0]);
+    }
+}
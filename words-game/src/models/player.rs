@@ -3,7 +3,7 @@ use super::super::constants::HAND_SIZE;
 use super::super::error::{Error, Result};
 use super::tile::{Tile, TileBag};
 
-fn remove_tiles(src: &[Tile], tiles: &[Tile]) -> Result<Vec<Tile>> {
+pub(crate) fn remove_tiles(src: &[Tile], tiles: &[Tile]) -> Result<Vec<Tile>> {
     let mut rtn_tiles = src.to_vec();
 
     for tile in tiles {
@@ -19,20 +19,66 @@ fn remove_tiles(src: &[Tile], tiles: &[Tile]) -> Result<Vec<Tile>> {
 }
 
 /**
- * Represents a player in the game, a player does not have a unique id
- * and instead is kept track based on its index within the players vector
+ * Whether a seat is controlled by a person or by the computer
  */
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayerKind {
+    #[default]
+    Human,
+    Bot,
+}
+
+/**
+ * Represents a player in the game. Players are kept track of based on their
+ * index within the players vector, front-ends can additionally attach their
+ * own id and a display name to look players up by
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Player {
     pub score: u32,
     pub hand: Vec<Tile>,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub kind: PlayerKind,
+    /// Resigned players keep their score but no longer take turns
+    #[serde(default)]
+    pub resigned: bool,
+    /// Time left on this player's clock in timed games, negative once they
+    /// are in overtime
+    #[serde(default)]
+    pub time_remaining_ms: Option<i64>,
 }
 
 impl Player {
     pub fn new(tile_bag: &mut TileBag) -> Player {
         Player {
-            score: 0,
             hand: tile_bag.draw(HAND_SIZE).unwrap(),
+            ..Player::default()
+        }
+    }
+
+    pub fn with_identity(mut self, id: &str, display_name: &str) -> Player {
+        self.id = Some(id.to_string());
+        self.display_name = Some(display_name.to_string());
+        self
+    }
+
+    pub fn with_kind(mut self, kind: PlayerKind) -> Player {
+        self.kind = kind;
+        self
+    }
+
+    /**
+     * The name to show for this player, falls back to the seat number
+     */
+    pub fn name(&self, idx: usize) -> String {
+        match self.display_name {
+            Some(ref name) => name.clone(),
+            None => format!("Player {}", idx + 1),
         }
     }
 
@@ -63,4 +109,17 @@ mod tests {
 
         assert_eq!(player.hand.len(), HAND_SIZE);
     }
+
+    #[test]
+    fn identity_defaults_when_missing() {
+        let player: Player = serde_json::from_str(r#"{"score":3,"hand":["A"]}"#).unwrap();
+
+        assert_eq!(player.id, None);
+        assert_eq!(player.kind, PlayerKind::Human);
+        assert_eq!(player.name(1), "Player 2");
+
+        let player = player.with_identity("U123", "tyler").with_kind(PlayerKind::Bot);
+        assert_eq!(player.name(1), "tyler");
+        assert_eq!(player.kind, PlayerKind::Bot);
+    }
 }
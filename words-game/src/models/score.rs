@@ -0,0 +1,105 @@
+use serde::{Serialize, Deserialize};
+use super::board::Square;
+use super::super::language::unit;
+use std::fmt;
+
+/**
+ * How a single letter of a formed word was scored
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LetterScore {
+    pub letter: char,
+    pub base_value: u32,
+    /// The premium square under the letter, only present when the letter
+    /// was placed by this play, since premiums only count once
+    pub premium: Option<Square>,
+    pub letter_multiplier: u32,
+}
+
+impl LetterScore {
+    pub fn value(&self) -> u32 {
+        self.base_value * self.letter_multiplier
+    }
+}
+
+impl fmt::Display for LetterScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.letter_multiplier > 1 {
+            write!(f, "{}({}x{})", unit(self.letter), self.base_value, self.letter_multiplier)
+        } else {
+            write!(f, "{}({})", unit(self.letter), self.base_value)
+        }
+    }
+}
+
+/**
+ * The score of a single word formed by a play, broken down by letter
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordScore {
+    pub word: String,
+    pub letters: Vec<LetterScore>,
+    /// Sum of the letter values, before the word multiplier
+    pub subtotal: u32,
+    pub word_multiplier: u32,
+    pub score: u32,
+}
+
+impl WordScore {
+    pub fn new(word: String, letters: Vec<LetterScore>, word_multiplier: u32) -> Self {
+        let subtotal = letters.iter().map(LetterScore::value).sum();
+
+        WordScore {
+            word,
+            letters,
+            subtotal,
+            word_multiplier,
+            score: subtotal * word_multiplier,
+        }
+    }
+}
+
+/**
+ * Formats as `ACTOR: A(1) C(3) T(1) O(1) R(1x2) = 8`
+ */
+impl fmt::Display for WordScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:", self.word)?;
+        for letter in self.letters.iter() {
+            write!(f, " {}", letter)?;
+        }
+
+        if self.word_multiplier > 1 {
+            write!(f, " = {} x{} = {}", self.subtotal, self.word_multiplier, self.score)
+        } else {
+            write!(f, " = {}", self.score)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letter(letter: char, base_value: u32, letter_multiplier: u32) -> LetterScore {
+        LetterScore {
+            letter,
+            base_value,
+            premium: None,
+            letter_multiplier,
+        }
+    }
+
+    #[test]
+    fn display_breakdown() {
+        let score = WordScore::new(
+            "HI".to_string(),
+            vec![letter('H', 4, 3), letter('I', 1, 1)],
+            2,
+        );
+
+        assert_eq!(score.subtotal, 13);
+        assert_eq!(score.score, 26);
+        assert_eq!(score.to_string(), "HI: H(4x3) I(1) = 13 x2 = 26");
+    }
+}
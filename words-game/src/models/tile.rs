@@ -1,56 +1,38 @@
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
 use super::super::error::{Error, Result};
+use super::super::language::{is_letter, Language};
+use super::super::normalize::normalize_letter;
 use rand::prelude::*;
-use rand::rngs::{OsRng};
+use rand::rngs::{OsRng, StdRng};
 use std::fmt;
 
-/**
- * This macro is used to add some syntactic sugar to when we're pushing
- * the letter tiles into our tile_bag.
- *
- * Variables that start with c refer to the character,
- * Variables that start with n refer to the count (number of times we insert)
- */
-macro_rules! push_letter_tiles {
-  ($vec:expr, $c:literal * $n:literal) => {
-    push_repeat($vec, Tile::Letter($c), $n);
-  };
-
-  ($vec:expr, $c:literal * $n:literal, $($cr:literal * $nr:literal),+) => {{
-    push_letter_tiles! {$vec, $c * $n}
-    push_letter_tiles! {$vec, $($cr * $nr),+}
-  }}
-}
-
-fn push_repeat<T: Clone>(vec: &mut Vec<T>, t: T, count: u32) {
-    for _ in 0..count {
-        vec.push(t.clone());
-    }
-}
-
 /**
  * A tile is a piece that is in the player's hand
  */
-#[derive(Clone, PartialEq, Eq, Debug, Copy)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Copy)]
 pub enum Tile {
     Letter(char),
     Blank,
 }
 
 impl Tile {
+    /**
+     * What the tile is worth in English, see `TileSet::point_value` for the
+     * other languages
+     */
     pub fn point_value(self) -> u32 {
-        match self {
-            Self::Blank => 0,
-            Self::Letter(letter) => match letter {
-                'E' | 'A' | 'I' | 'O' | 'N' | 'R' | 'T' | 'L' | 'S' | 'U' => 1,
-                'D' | 'G' => 2,
-                'B' | 'C' | 'M' | 'P' => 3,
-                'F' | 'H' | 'V' | 'W' | 'Y' => 4,
-                'K' => 5,
-                'J' | 'X' => 8,
-                'Q' | 'Z' => 10,
-                c => unreachable!("Trying to get string for invalid char {}", c),
-            },
+        Language::English.tile_set().point_value(self)
+    }
+
+    /**
+     * The tile stored as `c`, a space being a blank, for characters that
+     * come from outside of the engine
+     */
+    pub fn from_char(c: char) -> Result<Tile> {
+        match c {
+            ' ' => Ok(Tile::Blank),
+            c if is_letter(c) => Ok(Tile::Letter(c)),
+            c => Err(Error::InvalidCharacter(c).into()),
         }
     }
 }
@@ -59,15 +41,15 @@ impl From<char> for Tile {
     fn from(c: char) -> Tile {
         match c {
             ' ' => Tile::Blank,
-            c if ('A'..='Z').contains(&c) => Tile::Letter(c),
+            c if is_letter(c) => Tile::Letter(c),
             c => unreachable!("Trying to make a tile with character {}", c),
         }
     }
 }
 
-impl Into<char> for Tile {
-    fn into(self) -> char {
-        match self {
+impl From<Tile> for char {
+    fn from(tile: Tile) -> char {
+        match tile {
             Tile::Letter(letter) => letter,
             Tile::Blank => ' ',
         }
@@ -82,14 +64,21 @@ impl Serialize for Tile {
 
 impl<'de> Deserialize<'de> for Tile {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Tile, D::Error> {
-        let c = char::deserialize(deserializer)?;
-        Ok(Self::from(c))
+        match char::deserialize(deserializer)? {
+            ' ' => Ok(Tile::Blank),
+            c if is_letter(c) => Ok(Tile::Letter(c)),
+            c => normalize_letter(c).map(Tile::Letter).map_err(serde::de::Error::custom),
+        }
     }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TileBag {
     pub tiles: Vec<Tile>,
+    /// Set for bags that are shuffled reproducibly, it is not saved with
+    /// the game so a loaded bag goes back to shuffling randomly
+    #[serde(skip)]
+    rng: Option<StdRng>,
 }
 
 impl fmt::Display for TileBag {
@@ -106,47 +95,64 @@ impl fmt::Display for TileBag {
 
 impl TileBag {
     pub fn new() -> TileBag {
-        let mut tiles = Vec::with_capacity(100);
-
-        // push_repeat(&mut tiles, Tile::Blank, 2);
-        push_letter_tiles! {
-          &mut tiles,
-          'E' * 12,
-          'A' * 9,
-          'I' * 9,
-          'O' * 8,
-          'N' * 6,
-          'R' * 6,
-          'T' * 6,
-          'L' * 4,
-          'S' * 4,
-          'U' * 4,
-          'D' * 4,
-          'G' * 3,
-          'B' * 2,
-          'C' * 2,
-          'M' * 2,
-          'P' * 2,
-          'F' * 2,
-          'H' * 2,
-          'V' * 2,
-          'W' * 2,
-          'Y' * 2,
-          'K' * 1,
-          'J' * 1,
-          'X' * 1,
-          'Q' * 1,
-          'Z' * 1
-        }
+        TileBag::for_language(Language::English)
+    }
+
+    /**
+     * The standard tiles shuffled by a generator seeded with `seed`, the bag
+     * gives out the same tiles in the same order every time
+     */
+    pub fn seeded(seed: u64) -> TileBag {
+        TileBag::from_tiles(TileBag::standard_tiles()).with_seed(seed)
+    }
 
-        let mut tile_bag = TileBag { tiles };
+    /**
+     * A bag with exactly `tiles`, in that order
+     */
+    pub fn from_tiles(tiles: Vec<Tile>) -> TileBag {
+        TileBag { tiles, rng: None }
+    }
+
+    /**
+     * Shuffles the bag with a generator seeded with `seed`, and keeps using
+     * it for tiles that are put back later
+     */
+    pub fn with_seed(mut self, seed: u64) -> TileBag {
+        self.rng = Some(StdRng::seed_from_u64(seed));
+        self.shuffle();
+        self
+    }
+
+    /**
+     * The tiles of a full English bag, in alphabetical order
+     */
+    pub fn standard_tiles() -> Vec<Tile> {
+        Language::English.tile_set().tiles()
+    }
+
+    /**
+     * A shuffled full bag of `language`'s tiles
+     */
+    pub fn for_language(language: Language) -> TileBag {
+        let mut tile_bag = TileBag::from_tiles(language.tile_set().tiles());
         tile_bag.shuffle();
 
         tile_bag
     }
 
     pub fn shuffle(&mut self) {
-        self.tiles.shuffle(&mut OsRng{});
+        match self.rng {
+            Some(ref mut rng) => self.tiles.shuffle(rng),
+            None => self.tiles.shuffle(&mut OsRng{}),
+        }
+    }
+
+    /**
+     * Shuffles using the given random number generator, so a seeded
+     * generator gives the same order every time
+     */
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.tiles.shuffle(rng);
     }
 
     pub fn return_to(&mut self, tiles: &[Tile]) {
@@ -172,3 +178,32 @@ impl TileBag {
         self.draw(std::cmp::min(count, self.tiles.len())).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_bags_repeat() {
+        let mut a = TileBag::seeded(7);
+        let mut b = TileBag::seeded(7);
+        assert_eq!(a.tiles, b.tiles);
+        assert_ne!(a.tiles, TileBag::seeded(8).tiles);
+
+        // Tiles that are put back are shuffled the same way too
+        for bag in [&mut a, &mut b].iter_mut() {
+            let drawn = bag.draw(5).unwrap();
+            bag.return_to(&drawn);
+        }
+        assert_eq!(a.tiles, b.tiles);
+        assert_eq!(a.tiles.len(), TileBag::standard_tiles().len());
+    }
+
+    #[test]
+    fn tiles_from_chars() {
+        assert_eq!(Tile::from_char('Ñ').unwrap(), Tile::Letter('Ñ'));
+        assert_eq!(Tile::from_char(' ').unwrap(), Tile::Blank);
+        assert!(matches!(*Tile::from_char('a').unwrap_err(), Error::InvalidCharacter('a')));
+        assert!(Tile::from_char('7').is_err());
+    }
+}
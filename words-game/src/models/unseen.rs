@@ -0,0 +1,113 @@
+use serde::{Serialize, Deserialize};
+use super::game::Game;
+use super::tile::Tile;
+use std::collections::BTreeMap;
+
+/**
+ * The tiles a player cannot see, the ones still in the bag and on their
+ * opponents' racks. Only the counts are kept so nothing gives away which
+ * opponent holds which tile.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UnseenTiles {
+    /// How many of each letter are unseen, blanks are counted under '?'
+    pub counts: BTreeMap<char, u32>,
+    /// How many tiles are left in the bag
+    pub in_bag: usize,
+}
+
+fn tile_key(tile: Tile) -> char {
+    match tile {
+        Tile::Blank => '?',
+        Tile::Letter(letter) => letter,
+    }
+}
+
+impl UnseenTiles {
+    pub fn count(&self, tile: Tile) -> u32 {
+        self.counts.get(&tile_key(tile)).cloned().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /**
+     * Every unseen tile, sorted by letter with blanks last
+     */
+    pub fn tiles(&self) -> Vec<Tile> {
+        let mut tiles = vec![];
+        for (key, count) in self.counts.iter() {
+            let tile = if *key == '?' { Tile::Blank } else { Tile::Letter(*key) };
+            tiles.extend(std::iter::repeat_n(tile, *count as usize));
+        }
+
+        tiles.sort_by_key(|tile| tile_key(*tile) == '?');
+        tiles
+    }
+}
+
+impl Game {
+    /**
+     * The tiles `player_idx` has not seen yet, everything that is not on the
+     * board or in their own hand
+     */
+    pub fn unseen_tiles(&self, player_idx: usize) -> UnseenTiles {
+        let opponent_tiles = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != player_idx)
+            .flat_map(|(_, player)| player.hand.iter());
+
+        let mut counts = BTreeMap::new();
+        for tile in self.tile_bag.tiles.iter().chain(opponent_tiles) {
+            *counts.entry(tile_key(*tile)).or_insert(0) += 1;
+        }
+
+        UnseenTiles {
+            counts,
+            in_bag: self.tile_bag.tiles.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::tile::TileBag;
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        s.chars().map(Tile::from).collect()
+    }
+
+    #[test]
+    fn counts_bag_and_opponents() {
+        let mut game = Game::new(3);
+        game.tile_bag.tiles = tiles("AAB");
+        game.players[0].hand = tiles("ZZZ");
+        game.players[1].hand = tiles("BC");
+        game.players[2].hand = tiles("A ");
+
+        let unseen = game.unseen_tiles(0);
+
+        assert_eq!(unseen.in_bag, 3);
+        assert_eq!(unseen.total(), 7);
+        assert_eq!(unseen.count(Tile::Letter('A')), 3);
+        assert_eq!(unseen.count(Tile::Letter('B')), 2);
+        assert_eq!(unseen.count(Tile::Blank), 1);
+        assert_eq!(unseen.count(Tile::Letter('Z')), 0);
+        assert_eq!(unseen.tiles(), tiles("AAABBC "));
+    }
+
+    #[test]
+    fn fresh_game_hides_only_own_hand() {
+        let game = Game::new(2);
+        let unseen = game.unseen_tiles(1);
+
+        assert_eq!(
+            unseen.total() as usize,
+            TileBag::new().tiles.len() - game.players[1].hand.len()
+        );
+    }
+}
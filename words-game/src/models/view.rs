@@ -0,0 +1,134 @@
+use serde::{Serialize, Deserialize};
+use super::super::error::{Error, Result};
+use super::super::language::Language;
+use super::board::Board;
+use super::clock::TimeControl;
+use super::game::Game;
+use super::player::{Player, PlayerKind};
+use super::tile::Tile;
+use super::unseen::UnseenTiles;
+
+/**
+ * What anyone at the table can see about a player, their rack is only
+ * given as a tile count
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublicPlayer {
+    pub score: u32,
+    pub tile_count: usize,
+    pub id: Option<String>,
+    pub display_name: Option<String>,
+    pub kind: PlayerKind,
+    pub resigned: bool,
+    pub time_remaining_ms: Option<i64>,
+}
+
+impl From<&Player> for PublicPlayer {
+    fn from(player: &Player) -> Self {
+        PublicPlayer {
+            score: player.score,
+            tile_count: player.hand.len(),
+            id: player.id.clone(),
+            display_name: player.display_name.clone(),
+            kind: player.kind,
+            resigned: player.resigned,
+            time_remaining_ms: player.time_remaining_ms,
+        }
+    }
+}
+
+/**
+ * The game as one player is allowed to see it. Opponents' racks and the
+ * order of the tile bag are left out, so a view can be sent to a client
+ * without giving anything away.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerView {
+    pub viewer_idx: usize,
+    /// The viewer's own rack
+    pub hand: Vec<Tile>,
+    pub board: Board,
+    pub players: Vec<PublicPlayer>,
+    pub turn: u32,
+    pub language: Language,
+    pub current_player_idx: usize,
+    pub has_word_been_played: bool,
+    pub unseen: UnseenTiles,
+    pub time_control: Option<TimeControl>,
+    /// When the current player's clock last got charged, so clients can run
+    /// it down locally
+    pub clock_updated_at: Option<u64>,
+}
+
+impl PlayerView {
+    pub fn serialize(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn from_serialized(serialized: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(serialized)
+    }
+}
+
+impl Game {
+    pub fn view_for(&self, player_idx: usize) -> Result<PlayerView> {
+        let viewer = self.players.get(player_idx).ok_or_else(|| {
+            Error::BadAction(format!("Player {} does not exist", player_idx))
+        })?;
+
+        Ok(PlayerView {
+            viewer_idx: player_idx,
+            hand: viewer.hand.clone(),
+            board: self.board.clone(),
+            players: self.players.iter().map(PublicPlayer::from).collect(),
+            turn: self.turn,
+            language: self.language,
+            current_player_idx: self.get_current_player_idx(),
+            has_word_been_played: self.has_word_been_played,
+            unseen: self.unseen_tiles(player_idx),
+            time_control: self.time_control,
+            clock_updated_at: self.clock_updated_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hides_other_hands() -> Result<()> {
+        let mut game = Game::new(2);
+        game.players[0].hand = "ACTORS".chars().map(Tile::from).collect();
+        game.players[1].hand = "QZXJ".chars().map(Tile::from).collect();
+
+        let view = game.view_for(0)?;
+        let serialized = view.serialize();
+
+        assert_eq!(view.hand, game.players[0].hand);
+        assert_eq!(view.players[1].tile_count, 4);
+        assert_eq!(view.unseen.in_bag, game.tile_bag.tiles.len());
+        let json: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+        assert!(json["players"][1].get("hand").is_none());
+        assert!(json.get("tile_bag").is_none());
+
+        let round_trip = PlayerView::from_serialized(&serialized).unwrap();
+        assert_eq!(round_trip.players, view.players);
+        assert_eq!(round_trip.language, Language::English);
+        assert_eq!(round_trip.clock_updated_at, None);
+        assert!(game.view_for(2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn shows_the_language_and_clock() -> Result<()> {
+        let mut game = Game::with_language(2, Language::Polish);
+        game.set_time_control(TimeControl::new(60_000, 1000, 10), 5000);
+
+        let view = game.view_for(1)?;
+        assert_eq!(view.language, Language::Polish);
+        assert_eq!(view.time_control, game.time_control);
+        assert_eq!(view.clock_updated_at, Some(5000));
+        Ok(())
+    }
+}
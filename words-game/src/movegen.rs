@@ -0,0 +1,370 @@
+use super::constants::BOARD_SIZE;
+use super::language::Language;
+use super::leave::{equity, LeaveTable};
+use super::models::coordinates::line;
+use super::models::{remove_tiles, Board, BoardCell, CrossCheck, Direction, Game, Move, Point, Tile};
+use super::trie::{Trie, TRIE};
+
+/**
+ * A legal play found by the move generator, along with what it scores
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub start: Point,
+    pub dir: Direction,
+    pub word: String,
+    /// The tiles from the hand that the play uses
+    pub tiles: Vec<Tile>,
+    pub score: u32,
+}
+
+impl Candidate {
+    pub fn to_move(&self) -> Move {
+        Move::Play {
+            start: self.start,
+            dir: self.dir,
+            word: self.word.clone(),
+        }
+    }
+}
+
+/**
+ * Where `letter` is counted in a rack, `None` for anything outside A to Z
+ */
+fn letter_idx(letter: char) -> Option<usize> {
+    if letter.is_ascii_uppercase() {
+        Some((letter as u8 - b'A') as usize)
+    } else {
+        None
+    }
+}
+
+fn letter_value(letter: char) -> u32 {
+    Tile::Letter(letter).point_value()
+}
+
+/**
+ * A line of the board (a row for horizontal plays, a column for vertical
+ * ones) with everything the generator needs to know about it
+ */
+struct Line {
+    cells: Vec<BoardCell>,
+    points: Vec<Point>,
+    checks: Vec<CrossCheck>,
+    /// The fewest tiles a play reaching each position still has to place to
+    /// connect to the rest of the board, `None` if it can not connect
+    tiles_to_connect: Vec<Option<usize>>,
+}
+
+fn build_line(board: &Board, idx: i32, dir: Direction, is_first_move: bool) -> Line {
+    let points: Vec<Point> = line(idx, dir).collect();
+    let cells: Vec<BoardCell> = points.iter().map(|point| board.get(*point).unwrap()).collect();
+    let checks: Vec<CrossCheck> = points.iter().map(|point| board.cross_check(*point, dir)).collect();
+
+    let is_anchor = |pos: usize| match cells[pos] {
+        BoardCell::Tile(..) => true,
+        BoardCell::StartingSpot if is_first_move => true,
+        _ => board.is_anchor(points[pos]),
+    };
+
+    let mut tiles_to_connect = vec![None; cells.len()];
+    let mut next: Option<usize> = None;
+    for pos in (0..cells.len()).rev() {
+        next = match cells[pos] {
+            BoardCell::Tile(..) => Some(0),
+            _ if is_anchor(pos) => Some(1),
+            _ => next.map(|tiles| tiles + 1),
+        };
+        tiles_to_connect[pos] = next;
+    }
+
+    Line {
+        cells,
+        points,
+        checks,
+        tiles_to_connect,
+    }
+}
+
+/**
+ * The running score of the play being built
+ */
+#[derive(Clone, Copy)]
+struct RunningScore {
+    main_sum: u32,
+    word_multiplier: u32,
+    cross_total: u32,
+}
+
+impl RunningScore {
+    fn total(self) -> u32 {
+        self.main_sum * self.word_multiplier + self.cross_total
+    }
+}
+
+/**
+ * State of the search along a line
+ */
+struct Search<'a> {
+    line: &'a Line,
+    dir: Direction,
+    is_first_move: bool,
+    rack: [u8; 26],
+    tiles_left: usize,
+    word: String,
+    placed: Vec<Tile>,
+    candidates: Vec<Candidate>,
+}
+
+impl<'a> Search<'a> {
+    fn extend(&mut self, start: usize, pos: usize, node: usize, score: RunningScore, connected: bool) {
+        let at_end = pos == self.line.cells.len();
+
+        if at_end || !matches!(self.line.cells[pos], BoardCell::Tile(..)) {
+            if TRIE.is_terminal(node) && connected && !self.placed.is_empty() && self.word.len() > 1 {
+                self.candidates.push(Candidate {
+                    start: self.line.points[start],
+                    dir: self.dir,
+                    word: self.word.clone(),
+                    tiles: self.placed.clone(),
+                    score: score.total(),
+                });
+            }
+
+            if at_end || self.tiles_left == 0 {
+                return;
+            }
+        }
+
+        if !connected {
+            match self.line.tiles_to_connect[pos] {
+                Some(tiles) if tiles <= self.tiles_left => (),
+                _ => return,
+            }
+        }
+
+        if let BoardCell::Tile(Tile::Letter(letter), _) = self.line.cells[pos] {
+            if let Some(child) = TRIE.child(node, letter) {
+                self.word.push(letter);
+                let score = RunningScore {
+                    main_sum: score.main_sum + letter_value(letter),
+                    ..score
+                };
+                self.extend(start, pos + 1, child, score, true);
+                self.word.pop();
+            }
+            return;
+        }
+
+        let cell = self.line.cells[pos];
+        let check = self.line.checks[pos];
+        let letter_multiplier = cell.letter_multiplier();
+        let cell_word_multiplier = cell.word_multiplier();
+
+        let children: Vec<(char, usize)> = TRIE.children(node).collect();
+        for (letter, child) in children {
+            let idx = match letter_idx(letter) {
+                Some(idx) if self.rack[idx] > 0 && check.allows(letter) => idx,
+                _ => continue,
+            };
+
+            let value = letter_value(letter) * letter_multiplier;
+            let cross_score = check
+                .cross_sum
+                .map_or(0, |sum| (sum + value) * cell_word_multiplier);
+            let touches = check.forms_cross_word()
+                || (self.is_first_move && cell == BoardCell::StartingSpot);
+
+            self.rack[idx] -= 1;
+            self.tiles_left -= 1;
+            self.word.push(letter);
+            self.placed.push(Tile::Letter(letter));
+
+            let score = RunningScore {
+                main_sum: score.main_sum + value,
+                word_multiplier: score.word_multiplier * cell_word_multiplier,
+                cross_total: score.cross_total + cross_score,
+            };
+            self.extend(start, pos + 1, child, score, connected || touches);
+
+            self.placed.pop();
+            self.word.pop();
+            self.tiles_left += 1;
+            self.rack[idx] += 1;
+        }
+    }
+}
+
+/**
+ * Finds every legal play of the tiles in `rack` on `board`. Blanks in the
+ * rack are ignored since they cannot be played yet. The search only knows
+ * English, a rack with letters outside A to Z has no plays.
+ */
+pub fn generate_moves(board: &Board, rack: &[Tile], is_first_move: bool) -> Vec<Candidate> {
+    let mut counts = [0u8; 26];
+    let mut tiles_left = 0;
+    for tile in rack {
+        if let Tile::Letter(letter) = tile {
+            match letter_idx(*letter) {
+                Some(idx) => counts[idx] += 1,
+                None => return vec![],
+            }
+            tiles_left += 1;
+        }
+    }
+
+    let mut candidates = Vec::new();
+
+    for dir in [Direction::right(), Direction::down()].iter() {
+        for idx in 0..BOARD_SIZE as i32 {
+            let line = build_line(board, idx, *dir, is_first_move);
+
+            let mut search = Search {
+                line: &line,
+                dir: *dir,
+                is_first_move,
+                rack: counts,
+                tiles_left,
+                word: String::new(),
+                placed: Vec::new(),
+                candidates: Vec::new(),
+            };
+
+            for start in 0..line.cells.len() {
+                // Words have to start right after an empty cell or the edge
+                if start > 0 && matches!(line.cells[start - 1], BoardCell::Tile(..)) {
+                    continue;
+                }
+
+                let score = RunningScore {
+                    main_sum: 0,
+                    word_multiplier: 1,
+                    cross_total: 0,
+                };
+                search.extend(start, start, Trie::ROOT, score, false);
+            }
+
+            candidates.append(&mut search.candidates);
+        }
+    }
+
+    candidates
+}
+
+/**
+ * Every legal play for the current player of `game`. Only English games
+ * have a word list to search, others have no plays.
+ */
+pub fn generate_game_moves(game: &Game) -> Vec<Candidate> {
+    if game.language != Language::English {
+        return vec![];
+    }
+
+    let player = &game.players[game.get_current_player_idx()];
+    generate_moves(&game.board, &player.hand, !game.has_word_been_played)
+}
+
+/**
+ * The play with the highest equity for the current player, `None` if they
+ * have no legal play
+ */
+pub fn best_move(game: &Game, leave_table: &LeaveTable) -> Option<Candidate> {
+    let hand = &game.players[game.get_current_player_idx()].hand;
+
+    generate_game_moves(game)
+        .into_iter()
+        .map(|candidate| {
+            let leave = remove_tiles(hand, &candidate.tiles).unwrap_or_default();
+            (equity(candidate.score, &leave, leave_table), candidate)
+        })
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate)
+}
+
+/**
+ * What a bot plays on its turn: the best play, or when there is none,
+ * swapping the whole hand while the bag can cover it and passing otherwise.
+ * Bots only know English, in other languages they always pass.
+ */
+pub fn bot_move(game: &Game, leave_table: &LeaveTable) -> Move {
+    if game.language != Language::English {
+        return Move::Pass;
+    }
+
+    match best_move(game, leave_table) {
+        Some(candidate) => candidate.to_move(),
+        None => {
+            let hand = &game.players[game.get_current_player_idx()].hand;
+            if !hand.is_empty() && game.tile_bag.tiles.len() >= hand.len() {
+                Move::Exchange(hand.clone())
+            } else {
+                Move::Pass
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        s.chars().map(Tile::from).collect()
+    }
+
+    #[test]
+    fn first_move_covers_start() {
+        let candidates = generate_moves(&Board::new(), &tiles("ACTOR"), true);
+
+        assert!(!candidates.is_empty());
+        assert!(candidates.iter().any(|c| c.word == "ACTOR"));
+        for candidate in candidates.iter() {
+            let len = candidate.word.len() as i32;
+            let covers = if candidate.dir.is_horizontal() {
+                candidate.start.y == 7 && (candidate.start.x..candidate.start.x + len).contains(&7)
+            } else {
+                candidate.start.x == 7 && (candidate.start.y..candidate.start.y + len).contains(&7)
+            };
+            assert!(covers, "{:?} does not cover the start", candidate);
+        }
+    }
+
+    #[test]
+    fn scores_match_the_engine() {
+        let mut game = Game::new(2);
+        game.players[0].hand = tiles("ACTORSEOYK");
+        game.play_word(Point::new(7, 7), Direction::right(), "ACTOR").unwrap();
+        game.players[1].hand = tiles("BOARSANALS");
+
+        let candidates = generate_game_moves(&game);
+        assert!(candidates.len() > 50);
+
+        for candidate in candidates.iter() {
+            let mut copy = game.clone();
+            let result = copy
+                .play_word(candidate.start, candidate.dir, &candidate.word)
+                .unwrap_or_else(|err| panic!("{:?} is not legal: {}", candidate, err));
+
+            assert_eq!(result.score, candidate.score, "{:?}", candidate);
+        }
+    }
+
+    #[test]
+    fn best_move_uses_leave() {
+        let mut game = Game::new(2);
+        game.players[0].hand = tiles("ACTORSEOYK");
+
+        let best = best_move(&game, &LeaveTable::new()).unwrap();
+        let mut copy = game.clone();
+
+        assert!(copy.apply_move(&best.to_move()).is_ok());
+    }
+
+    #[test]
+    fn no_moves_without_letters() {
+        assert!(generate_moves(&Board::new(), &[], true).is_empty());
+        assert!(generate_moves(&Board::new(), &tiles("QQ"), true).is_empty());
+        assert!(generate_moves(&Board::new(), &tiles("ACTORÑ"), true).is_empty());
+        assert!(generate_moves(&Board::new(), &tiles("ACTORŻ"), true).is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+//! Turns words and racks typed by players into the form the engine works
+//! with. Every public entry point that takes a word goes through here so
+//! that `actor`, ` Actor ` and `ACTOR` all mean the same thing, and so that
+//! anything outside of the alphabet is rejected with an error instead of
+//! reaching the board. These are for English, see `language` for the
+//! others.
+
+use super::error::{Error, Result};
+use super::language::Language;
+use super::models::Tile;
+
+/**
+ * Characters that stand for a blank tile in a rack
+ */
+pub const BLANK_CHARS: [char; 2] = ['?', ' '];
+
+/**
+ * Case folds `c` and checks that it is one of the letters A to Z
+ */
+pub fn normalize_letter(c: char) -> Result<char> {
+    let mut upper = c.to_uppercase();
+
+    match (upper.next(), upper.next()) {
+        (Some(letter), None) if letter.is_ascii_uppercase() => Ok(letter),
+        _ => Err(Error::InvalidCharacter(c).into()),
+    }
+}
+
+/**
+ * Trims and uppercases `text`, failing if it is empty or has anything other
+ * than letters in it. Games in other languages use their tile set's
+ * `normalize_word` instead.
+ */
+pub fn normalize_word(text: &str) -> Result<String> {
+    Language::English.tile_set().normalize_word(text)
+}
+
+/**
+ * Parses a rack of tiles like `ab?e`, where `?` or a space is a blank
+ */
+pub fn normalize_tiles(text: &str) -> Result<Vec<Tile>> {
+    Language::English.tile_set().normalize_tiles(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_and_whitespace() {
+        assert_eq!(normalize_word("actor").unwrap(), "ACTOR");
+        assert_eq!(normalize_word("  AcToR\n").unwrap(), "ACTOR");
+        assert_eq!(normalize_word("café").unwrap(), "CAFE");
+        assert_eq!(normalize_word("straße").unwrap(), "STRASSE");
+    }
+
+    #[test]
+    fn rejects_characters_outside_the_alphabet() {
+        for (text, bad) in &[("act0r", '0'), ("ac tor", ' '), ("łódź", 'ł'), ("日本", '日')] {
+            match *normalize_word(text).unwrap_err() {
+                Error::InvalidCharacter(c) => assert_eq!(c, *bad),
+                ref err => panic!("unexpected error {}", err),
+            }
+        }
+
+        assert!(matches!(*normalize_word("   ").unwrap_err(), Error::EmptyWord));
+    }
+
+    #[test]
+    fn parses_racks_with_blanks() {
+        assert_eq!(
+            normalize_tiles("a?B ").unwrap(),
+            vec![Tile::Letter('A'), Tile::Blank, Tile::Letter('B'), Tile::Blank]
+        );
+        assert!(normalize_tiles("a-b").is_err());
+    }
+}
@@ -0,0 +1,335 @@
+//! Text renderings of a board for the different front-ends.
+//!
+//! Each renderer takes the points to highlight, usually the tiles placed by
+//! the last move (see `GameDiff::placed_tiles`).
+
+use super::constants::BOARD_SIZE;
+use super::language::unit;
+use super::models::coordinates::row;
+use super::models::{Board, BoardCell, Point, Square, Tile};
+
+pub trait BoardRenderer {
+    fn render(&self, board: &Board, highlight: &[Point]) -> String;
+}
+
+/**
+ * Each row of the board, as the row number and the cells along with whether
+ * they are highlighted
+ */
+fn rows<'a>(
+    board: &'a Board,
+    highlight: &'a [Point],
+) -> impl Iterator<Item = (u32, Vec<(BoardCell, bool)>)> + 'a {
+    (0..BOARD_SIZE as i32).map(move |y| {
+        let cells = row(y)
+            .map(|point| (board.get(point).unwrap(), highlight.contains(&point)))
+            .collect();
+        (y as u32, cells)
+    })
+}
+
+/**
+ * How the tile on `cell` is written, two letter tiles like the Spanish CH
+ * take up two characters
+ */
+fn letter_of(cell: &BoardCell) -> Option<String> {
+    match cell {
+        BoardCell::Tile(Tile::Letter(letter), _) => Some(unit(*letter)),
+        BoardCell::Tile(Tile::Blank, _) => Some("?".to_string()),
+        _ => None,
+    }
+}
+
+/**
+ * Slack messages made of the custom emojis in `emojis.yml`. There are no
+ * highlighted versions of the tile emojis, so the coordinates of the
+ * highlighted rows and columns are replaced with arrows instead.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SlackEmojiRenderer;
+
+impl SlackEmojiRenderer {
+    /**
+     * The emoji for a cell or a tile in a player's hand. The pack only has
+     * the English letters, other tiles are written out as text.
+     */
+    pub fn cell_emoji(cell: &BoardCell) -> String {
+        let name = match cell {
+            BoardCell::Tile(Tile::Letter(letter @ 'A'..='Z'), _) => {
+                format!("scrabble-{}", letter.to_ascii_lowercase())
+            }
+            BoardCell::Tile(Tile::Letter(letter), _) => return unit(*letter),
+            BoardCell::Tile(Tile::Blank, _) => "scrabble-blank".to_string(),
+            BoardCell::DoubleWord => "scrabble-double-word".to_string(),
+            BoardCell::TripleWord => "scrabble-triple-word".to_string(),
+            BoardCell::DoubleLetter => "scrabble-double-letter".to_string(),
+            BoardCell::TripleLetter => "scrabble-triple-letter".to_string(),
+            BoardCell::StartingSpot => "scrabble-start".to_string(),
+            BoardCell::Empty => "scrabble-board".to_string(),
+        };
+
+        format!(":{}:", name)
+    }
+
+    pub fn tile_emojis(tiles: &[Tile]) -> String {
+        tiles
+            .iter()
+            .map(|tile| SlackEmojiRenderer::cell_emoji(&BoardCell::Tile(*tile, Square::Empty)))
+            .collect()
+    }
+
+    fn coordinate_emoji(num: u32) -> &'static str {
+        [
+            ":zero:", ":one:", ":two:", ":three:", ":four:", ":five:", ":six:", ":seven:", ":eight:", ":nine:",
+        ][(num % 10) as usize]
+    }
+}
+
+impl BoardRenderer for SlackEmojiRenderer {
+    fn render(&self, board: &Board, highlight: &[Point]) -> String {
+        let mut lines = vec![];
+
+        let header: String = (0..BOARD_SIZE)
+            .map(|x| {
+                if highlight.iter().any(|point| point.x == x as i32) {
+                    ":arrow_down:"
+                } else {
+                    SlackEmojiRenderer::coordinate_emoji(x)
+                }
+            })
+            .collect();
+        lines.push(format!(":scrabble-board:{}", header));
+
+        for (y, cells) in rows(board, highlight) {
+            let label = if cells.iter().any(|(_, highlighted)| *highlighted) {
+                ":arrow_right:"
+            } else {
+                SlackEmojiRenderer::coordinate_emoji(y)
+            };
+            let cells: String = cells.iter().map(|(cell, _)| SlackEmojiRenderer::cell_emoji(cell)).collect();
+            lines.push(format!("{}{}", label, cells));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/**
+ * A grid drawn with box drawing characters, for terminals and logs. Each
+ * cell is three characters wide, highlighted tiles are shown as `[A]`, or
+ * as `CH*` for two letter tiles.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnicodeRenderer;
+
+impl UnicodeRenderer {
+    fn cell_text(cell: &BoardCell, highlighted: bool) -> String {
+        match (letter_of(cell), highlighted) {
+            (Some(letter), true) if letter.chars().count() > 1 => format!("{}*", letter),
+            (Some(letter), true) => format!("[{}]", letter),
+            (Some(letter), false) => format!("{:^3}", letter),
+            (None, _) => match cell {
+                BoardCell::TripleWord => "3W ",
+                BoardCell::DoubleWord => "2W ",
+                BoardCell::TripleLetter => "3L ",
+                BoardCell::DoubleLetter => "2L ",
+                BoardCell::StartingSpot => " ★ ",
+                _ => "   ",
+            }
+            .to_string(),
+        }
+    }
+
+    fn border(left: char, middle: char, right: char) -> String {
+        let segments = vec!["───"; BOARD_SIZE as usize];
+        format!("   {}{}{}", left, segments.join(&middle.to_string()), right)
+    }
+}
+
+impl BoardRenderer for UnicodeRenderer {
+    fn render(&self, board: &Board, highlight: &[Point]) -> String {
+        let mut lines = vec![];
+
+        let header: Vec<String> = (0..BOARD_SIZE).map(|x| format!("{:^3}", x)).collect();
+        lines.push(format!("    {}", header.join(" ")));
+        lines.push(UnicodeRenderer::border('┌', '┬', '┐'));
+
+        for (y, cells) in rows(board, highlight) {
+            if y > 0 {
+                lines.push(UnicodeRenderer::border('├', '┼', '┤'));
+            }
+
+            let cells: Vec<String> = cells
+                .iter()
+                .map(|(cell, highlighted)| UnicodeRenderer::cell_text(cell, *highlighted))
+                .collect();
+            lines.push(format!("{:>2} │{}│", y, cells.join("│")));
+        }
+
+        lines.push(UnicodeRenderer::border('└', '┴', '┘'));
+        lines.join("\n")
+    }
+}
+
+/**
+ * Coloured with ANSI escape codes, premium squares use the colours of the
+ * physical board and highlighted tiles are green instead of beige. Tiles on
+ * premium squares have their letter in the square's colour.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnsiRenderer;
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+impl AnsiRenderer {
+    fn cell_text(cell: &BoardCell, highlighted: bool) -> String {
+        let (style, text) = match (letter_of(cell), cell) {
+            (Some(letter), _) if highlighted => ("\x1b[1;30;42m", format!("{:^3}", letter)),
+            (Some(letter), BoardCell::Tile(_, Square::TripleWord)) => ("\x1b[1;31;103m", format!("{:^3}", letter)),
+            (Some(letter), BoardCell::Tile(_, Square::DoubleWord)) => ("\x1b[1;35;103m", format!("{:^3}", letter)),
+            (Some(letter), BoardCell::Tile(_, Square::TripleLetter)) => ("\x1b[1;34;103m", format!("{:^3}", letter)),
+            (Some(letter), BoardCell::Tile(_, Square::DoubleLetter)) => ("\x1b[1;36;103m", format!("{:^3}", letter)),
+            (Some(letter), _) => ("\x1b[1;30;103m", format!("{:^3}", letter)),
+            (None, BoardCell::TripleWord) => ("\x1b[97;41m", "3W ".to_string()),
+            (None, BoardCell::DoubleWord) => ("\x1b[30;105m", "2W ".to_string()),
+            (None, BoardCell::TripleLetter) => ("\x1b[97;44m", "3L ".to_string()),
+            (None, BoardCell::DoubleLetter) => ("\x1b[30;106m", "2L ".to_string()),
+            (None, BoardCell::StartingSpot) => ("\x1b[30;105m", " ★ ".to_string()),
+            (None, _) => ("\x1b[37;100m", " · ".to_string()),
+        };
+
+        format!("{}{}{}", style, text, ANSI_RESET)
+    }
+}
+
+impl BoardRenderer for AnsiRenderer {
+    fn render(&self, board: &Board, highlight: &[Point]) -> String {
+        let header: String = (0..BOARD_SIZE).map(|x| format!("{:^3}", x)).collect();
+        let mut lines = vec![format!("   {}", header)];
+
+        for (y, cells) in rows(board, highlight) {
+            let cells: String = cells
+                .iter()
+                .map(|(cell, highlighted)| AnsiRenderer::cell_text(cell, *highlighted))
+                .collect();
+            lines.push(format!("{:>2} {}", y, cells));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/**
+ * A Markdown table, for chat apps and issue comments. Highlighted tiles are
+ * in bold.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownRenderer;
+
+impl MarkdownRenderer {
+    fn cell_text(cell: &BoardCell, highlighted: bool) -> String {
+        match (letter_of(cell), highlighted) {
+            (Some(letter), true) => format!("**{}**", letter),
+            (Some(letter), false) => letter,
+            (None, _) => match cell {
+                BoardCell::TripleWord => "TW",
+                BoardCell::DoubleWord => "DW",
+                BoardCell::TripleLetter => "TL",
+                BoardCell::DoubleLetter => "DL",
+                BoardCell::StartingSpot => "★",
+                _ => " ",
+            }
+            .to_string(),
+        }
+    }
+}
+
+impl BoardRenderer for MarkdownRenderer {
+    fn render(&self, board: &Board, highlight: &[Point]) -> String {
+        let header: Vec<String> = (0..BOARD_SIZE).map(|x| x.to_string()).collect();
+        let mut lines = vec![
+            format!("|   | {} |", header.join(" | ")),
+            format!("|---|{}", ":-:|".repeat(BOARD_SIZE as usize)),
+        ];
+
+        for (y, cells) in rows(board, highlight) {
+            let cells: Vec<String> = cells
+                .iter()
+                .map(|(cell, highlighted)| MarkdownRenderer::cell_text(cell, *highlighted))
+                .collect();
+            lines.push(format!("| {} | {} |", y, cells.join(" | ")));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::models::{Direction, Game};
+
+    /**
+     * A board with ACTOR played across the middle, and the points it covers
+     */
+    fn played_board() -> (Board, Vec<Point>) {
+        let mut game = Game::new(2);
+        game.players[0].hand = "ACTORSEOYK".chars().map(Tile::from).collect();
+        let before = game.clone();
+        game.play_word(Point::new(7, 7), Direction::right(), "ACTOR").unwrap();
+
+        let placed = before.diff(&game).placed_tiles().into_iter().map(|(point, _)| point).collect();
+        (game.board, placed)
+    }
+
+    #[test]
+    fn slack_matches_emoji_pack() {
+        let (board, placed) = played_board();
+        let rendered = SlackEmojiRenderer.render(&board, &[]);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), BOARD_SIZE as usize + 1);
+        assert!(lines[0].starts_with(":scrabble-board::zero::one:"));
+        assert!(lines[1].starts_with(":zero::scrabble-triple-word::scrabble-board:"));
+        assert!(lines[8].contains(":scrabble-a::scrabble-c::scrabble-t:"));
+
+        let highlighted = SlackEmojiRenderer.render(&board, &placed);
+        assert!(highlighted.lines().nth(8).unwrap().starts_with(":arrow_right:"));
+        assert_eq!(highlighted.lines().next().unwrap().matches(":arrow_down:").count(), 5);
+    }
+
+    #[test]
+    fn slack_writes_out_other_letters() {
+        let tiles = [Tile::from('A'), Tile::Letter('Ñ'), Tile::Letter('\u{E000}'), Tile::Blank];
+
+        assert_eq!(SlackEmojiRenderer::tile_emojis(&tiles), ":scrabble-a:ÑCH:scrabble-blank:");
+    }
+
+    #[test]
+    fn unicode_grid() {
+        let (board, placed) = played_board();
+        let rendered = UnicodeRenderer.render(&board, &placed[..1]);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 2 * BOARD_SIZE as usize + 2);
+        assert!(lines[1].starts_with("   ┌───┬"));
+        assert!(lines[2].starts_with(" 0 │3W │"));
+        assert!(lines[16].contains("│[A]│ C │ T │"));
+    }
+
+    #[test]
+    fn ansi_and_markdown() {
+        let (board, placed) = played_board();
+
+        let ansi = AnsiRenderer.render(&board, &placed);
+        assert!(ansi.contains("\x1b[1;30;42m A \x1b[0m"));
+        // The R covers a double letter
+        let ansi = AnsiRenderer.render(&board, &[]);
+        assert!(ansi.contains("\x1b[1;36;103m R \x1b[0m"));
+
+        let markdown = MarkdownRenderer.render(&board, &placed[1..]);
+        let row = markdown.lines().nth(9).unwrap();
+        assert!(row.starts_with("| 7 | "));
+        assert!(row.contains("| A | **C** | **T** |"));
+    }
+}
@@ -0,0 +1,233 @@
+use super::error::Result;
+use super::leave::LeaveTable;
+use super::models::{Game, Move};
+use super::movegen::best_move;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+/**
+ * How much work to put into simulating each candidate
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct SimulationConfig {
+    /// Turns played per iteration, counting the candidate itself
+    pub plies: u32,
+    pub iterations: u32,
+    /// Seeds the random racks so that runs can be repeated
+    pub seed: Option<u64>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig {
+            plies: 2,
+            iterations: 100,
+            seed: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    pub candidate: Move,
+    /// Average of the points the player gained over the points the
+    /// opponents gained, plus the value of the player's final leave
+    pub equity: f64,
+    pub iterations: u32,
+}
+
+/**
+ * Gives the opponents of `player_idx` random racks made from the tiles the
+ * player cannot see. Every opponent keeps the number of tiles they had, the
+ * rest go back in the bag.
+ */
+fn randomize_unseen_tiles<R: Rng>(game: &mut Game, player_idx: usize, rng: &mut R) {
+    let mut unseen = game.unseen_tiles(player_idx).tiles();
+    unseen.shuffle(rng);
+
+    for (idx, player) in game.players.iter_mut().enumerate() {
+        if idx != player_idx {
+            let hand_size = player.hand.len();
+            player.hand = unseen.drain(..hand_size).collect();
+        }
+    }
+
+    game.tile_bag.tiles = unseen;
+}
+
+/**
+ * Plays `candidate` and then lets every player make their best play for the
+ * rest of the plies, returning the equity for `player_idx`
+ */
+fn play_out(
+    mut game: Game,
+    player_idx: usize,
+    candidate: &Move,
+    config: &SimulationConfig,
+    leave_table: &LeaveTable,
+) -> Result<f64> {
+    let starting_scores: Vec<u32> = game.players.iter().map(|player| player.score).collect();
+
+    game.apply_move(candidate)?;
+
+    for _ in 1..config.plies {
+        if game.players.iter().any(|player| player.hand.is_empty()) {
+            break;
+        }
+
+        match best_move(&game, leave_table) {
+            Some(best) => game.apply_move(&best.to_move())?,
+            None => {
+                game.pass();
+                0
+            }
+        };
+    }
+
+    let gains: Vec<f64> = game
+        .players
+        .iter()
+        .zip(starting_scores.iter())
+        .map(|(player, start)| f64::from(player.score - start))
+        .collect();
+
+    let opponents: Vec<f64> = gains
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != player_idx && !game.players[*idx].resigned)
+        .map(|(_, gain)| *gain)
+        .collect();
+
+    let opponent_gain = if opponents.is_empty() {
+        0.0
+    } else {
+        opponents.iter().sum::<f64>() / opponents.len() as f64
+    };
+
+    let leave = leave_table.value(&game.players[player_idx].hand);
+
+    Ok(gains[player_idx] - opponent_gain + leave)
+}
+
+/**
+ * Ranks `candidates` for the current player by playing each one out several
+ * times against random opponent racks. Every candidate is played against
+ * the same racks in an iteration so they are compared fairly.
+ *
+ * Results are sorted from the highest equity down. Fails if any of the
+ * candidates is not a legal move.
+ */
+pub fn simulate(
+    game: &Game,
+    candidates: &[Move],
+    config: &SimulationConfig,
+    leave_table: &LeaveTable,
+) -> Result<Vec<SimulationResult>> {
+    for candidate in candidates.iter() {
+        game.clone().apply_move(candidate)?;
+    }
+
+    let player_idx = game.get_current_player_idx();
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut totals = vec![0.0; candidates.len()];
+    for _ in 0..config.iterations {
+        let mut sample = game.clone();
+        randomize_unseen_tiles(&mut sample, player_idx, &mut rng);
+
+        for (total, candidate) in totals.iter_mut().zip(candidates.iter()) {
+            *total += play_out(sample.clone(), player_idx, candidate, config, leave_table)?;
+        }
+    }
+
+    let iterations = config.iterations.max(1);
+    let mut results: Vec<SimulationResult> = candidates
+        .iter()
+        .zip(totals)
+        .map(|(candidate, total)| SimulationResult {
+            candidate: candidate.clone(),
+            equity: total / f64::from(iterations),
+            iterations: config.iterations,
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::models::{Direction, Point, Tile};
+
+    fn tiles(s: &str) -> Vec<Tile> {
+        s.chars().map(Tile::from).collect()
+    }
+
+    fn config(seed: u64) -> SimulationConfig {
+        SimulationConfig {
+            plies: 2,
+            iterations: 4,
+            seed: Some(seed),
+        }
+    }
+
+    #[test]
+    fn unseen_tiles_are_redistributed() {
+        let mut game = Game::new(2);
+        game.players[0].hand = tiles("ACTORSE");
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let hand_size = game.players[1].hand.len();
+        let before = game.tile_bag.tiles.len() + hand_size;
+        randomize_unseen_tiles(&mut game, 0, &mut rng);
+
+        assert_eq!(game.players[0].hand, tiles("ACTORSE"));
+        assert_eq!(game.players[1].hand.len(), hand_size);
+        assert_eq!(game.tile_bag.tiles.len() + game.players[1].hand.len(), before);
+    }
+
+    #[test]
+    fn seeded_runs_repeat() -> Result<()> {
+        let mut game = Game::new(2);
+        game.players[0].hand = tiles("ACTORSE");
+        let candidates = vec![
+            Move::Play {
+                start: Point::new(7, 7),
+                dir: Direction::right(),
+                word: "ACTORS".to_string(),
+            },
+            Move::Play {
+                start: Point::new(7, 7),
+                dir: Direction::right(),
+                word: "ACT".to_string(),
+            },
+        ];
+
+        let first = simulate(&game, &candidates, &config(7), &LeaveTable::new())?;
+        let second = simulate(&game, &candidates, &config(7), &LeaveTable::new())?;
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].candidate, second[0].candidate);
+        assert_eq!(first[0].equity, second[0].equity);
+        assert!(first[0].equity >= first[1].equity);
+        assert_eq!(first[0].iterations, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn illegal_candidates_are_rejected() {
+        let mut game = Game::new(2);
+        game.players[0].hand = tiles("ACTORSE");
+        let candidates = vec![Move::Play {
+            start: Point::new(0, 0),
+            dir: Direction::right(),
+            word: "ACT".to_string(),
+        }];
+
+        assert!(simulate(&game, &candidates, &config(1), &LeaveTable::new()).is_err());
+    }
+}
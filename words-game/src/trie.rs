@@ -0,0 +1,155 @@
+use super::constants::DICTIONARY_WORDS;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy)]
+struct TrieNode {
+    letter: u8,
+    terminal: bool,
+    child_count: u8,
+    first_child: u32,
+}
+
+/**
+ * A prefix tree over the dictionary, used to walk words letter by letter.
+ *
+ * Nodes are stored in breadth first order so the children of a node are
+ * next to each other, sorted by letter. Node 0 is the root (the empty
+ * prefix).
+ */
+pub struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+lazy_static! {
+    pub static ref TRIE: Trie = Trie::from_sorted_words(DICTIONARY_WORDS.lines());
+}
+
+impl Trie {
+    pub const ROOT: usize = 0;
+
+    /**
+     * Builds the trie from words in sorted order
+     */
+    pub fn from_sorted_words<'a, I: Iterator<Item = &'a str>>(words: I) -> Trie {
+        let words: Vec<&[u8]> = words.map(str::as_bytes).collect();
+
+        let mut nodes = vec![TrieNode {
+            letter: 0,
+            terminal: false,
+            child_count: 0,
+            first_child: 0,
+        }];
+
+        // (node, range of words sharing the node's prefix, prefix length)
+        let mut queue = VecDeque::new();
+        queue.push_back((Trie::ROOT, 0, words.len(), 0));
+
+        while let Some((node, mut lo, hi, depth)) = queue.pop_front() {
+            // In sorted order the word equal to the prefix comes first
+            if lo < hi && words[lo].len() == depth {
+                nodes[node].terminal = true;
+                lo += 1;
+            }
+
+            nodes[node].first_child = nodes.len() as u32;
+
+            while lo < hi {
+                let letter = words[lo][depth];
+                let mut end = lo;
+                while end < hi && words[end][depth] == letter {
+                    end += 1;
+                }
+
+                queue.push_back((nodes.len(), lo, end, depth + 1));
+                nodes.push(TrieNode {
+                    letter,
+                    terminal: false,
+                    child_count: 0,
+                    first_child: 0,
+                });
+                nodes[node].child_count += 1;
+
+                lo = end;
+            }
+        }
+
+        Trie { nodes }
+    }
+
+    pub fn child(&self, node: usize, letter: char) -> Option<usize> {
+        // Nodes hold a byte, anything wider would alias an ASCII letter
+        if !letter.is_ascii() {
+            return None;
+        }
+
+        let TrieNode { first_child, child_count, .. } = self.nodes[node];
+        let first = first_child as usize;
+        let children = &self.nodes[first..first + child_count as usize];
+
+        children
+            .binary_search_by_key(&(letter as u8), |child| child.letter)
+            .ok()
+            .map(|idx| first + idx)
+    }
+
+    pub fn children(&self, node: usize) -> impl Iterator<Item = (char, usize)> + '_ {
+        let TrieNode { first_child, child_count, .. } = self.nodes[node];
+        let first = first_child as usize;
+
+        (first..first + child_count as usize).map(move |idx| (self.nodes[idx].letter as char, idx))
+    }
+
+    pub fn is_terminal(&self, node: usize) -> bool {
+        self.nodes[node].terminal
+    }
+
+    /**
+     * Follows `prefix` from the root
+     */
+    pub fn walk(&self, prefix: &str) -> Option<usize> {
+        prefix
+            .chars()
+            .try_fold(Trie::ROOT, |node, letter| self.child(node, letter))
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, word: &str) -> bool {
+        self.walk(word).is_some_and(|node| self.is_terminal(node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_trie() {
+        let trie = Trie::from_sorted_words(vec!["A", "AB", "ABC", "B", "BA"].into_iter());
+
+        assert!(trie.contains("AB"));
+        assert!(trie.contains("BA"));
+        assert!(!trie.contains("ABCD"));
+        assert!(!trie.contains(""));
+        assert!(trie.walk("AB").is_some());
+
+        let root_children: Vec<char> = trie.children(Trie::ROOT).map(|(c, _)| c).collect();
+        assert_eq!(root_children, vec!['A', 'B']);
+    }
+
+    #[test]
+    fn matches_dictionary() {
+        assert!(TRIE.contains("MOTORCYCLE"));
+        assert!(TRIE.contains("ACTOR"));
+        assert!(!TRIE.contains("ACTO"));
+        assert!(TRIE.walk("ACTO").is_some());
+        assert!(!TRIE.contains("BLAHBLAHBLAH"));
+    }
+
+    #[test]
+    fn letters_outside_ascii() {
+        // Ł is U+0141, which would be an A if it were cut down to a byte
+        assert_eq!(TRIE.child(Trie::ROOT, 'Ł'), None);
+        assert!(!TRIE.contains("ŁCTOR"));
+        assert!(TRIE.walk("ÑO").is_none());
+    }
+}
@@ -1,5 +1,6 @@
 use words_game::{Game, Tile};
 
+#[allow(clippy::redundant_closure)]
 pub fn stub_current_player_hand(game: &mut Game, hand: &str) {
     let current_player = game.get_current_player();
 
@@ -1,11 +1,12 @@
 extern crate words_game;
 mod common;
 
-use words_game::{Result, Point, Direction, Game};
+use words_game::{Result, Point, Direction, Game, Square};
 use common::stub_current_player_hand;
 
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn full_game_test() -> Result<()>{
     let mut game = Game::new(2);
 
@@ -65,6 +66,37 @@ fn full_game_test() -> Result<()>{
 }
 
 #[test]
+fn score_breakdown_test() -> Result<()> {
+    let mut game = Game::new(2);
+
+    stub_current_player_hand(&mut game, "ACTORSEOYK");
+    let result = game.play_word(
+        Point::new(7, 7),
+        Direction::right(),
+        "ACTOR"
+    )?;
+
+    assert_eq!(result.breakdown.len(), 1);
+    assert_eq!(result.breakdown[0].to_string(), "ACTOR: A(1) C(3) T(1) O(1) R(1x2) = 8");
+
+    stub_current_player_hand(&mut game, "BOARSANALS");
+    let result = game.play_word(
+        Point::new(10, 6),
+        Direction::down(),
+        "BOARS"
+    )?;
+
+    // The O was already on the board so its square does not count again
+    assert_eq!(result.breakdown[0].letters[1].premium, None);
+    let premiums: Vec<Square> = result.breakdown[0].letters.iter().filter_map(|l| l.premium).collect();
+    assert_eq!(premiums, vec![Square::DoubleWord]);
+    assert_eq!(result.breakdown[0].to_string(), "BOARS: B(3) O(1) A(1) R(1) S(1) = 7 x2 = 14");
+
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
 fn no_cover_starting() {
     let mut game = Game::new(2);
 
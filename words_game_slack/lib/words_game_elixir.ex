@@ -5,6 +5,9 @@ defmodule WordsGameElixir do
   # When your NIF is loaded, it will override this function.
   def new_game(_player_count), do: :erlang.nif_error(:nif_not_loaded)
 
+  @spec new_game_with_players([{String.t(), String.t()}]) :: WordsGameElixir.t()
+  def new_game_with_players(_players), do: :erlang.nif_error(:nif_not_loaded)
+
   @spec play_word(WordsGameElixir.t(), {number, number}, String.t(), String.t()) ::
           {:error, String.t()} | {:ok, PlayWordResult.t(), WordsGameElixir.t()}
   def play_word(
@@ -15,30 +18,135 @@ defmodule WordsGameElixir do
       ),
       do: :erlang.nif_error(:nif_not_loaded)
 
+  @spec add_player(WordsGameElixir.t()) :: {:error, String.t()} | {:ok, number, WordsGameElixir.t()}
+  def add_player(_game), do: :erlang.nif_error(:nif_not_loaded)
+
+  @spec resign(WordsGameElixir.t(), number) :: {:error, String.t()} | {:ok, WordsGameElixir.t()}
+  def resign(_game, _player_idx), do: :erlang.nif_error(:nif_not_loaded)
+
+  @spec tick(WordsGameElixir.t(), number) :: WordsGameElixir.t()
+  def tick(_game, _now_ms), do: :erlang.nif_error(:nif_not_loaded)
+
+  @spec unseen_tiles(WordsGameElixir.t(), number) :: UnseenTiles.t()
+  def unseen_tiles(_game, _player_idx), do: :erlang.nif_error(:nif_not_loaded)
+
+  @spec view_for(WordsGameElixir.t(), number) :: {:error, String.t()} | {:ok, String.t()}
+  def view_for(_game, _player_idx), do: :erlang.nif_error(:nif_not_loaded)
+
   @spec check_dictionary(String.t()) :: bool
   def check_dictionary(_word), do: :erlang.nif_error(:nif_not_loaded)
 
+  @spec find_words(WordQuery.t()) :: {:error, String.t()} | {:ok, [String.t()]}
+  def find_words(_query), do: :erlang.nif_error(:nif_not_loaded)
+
+  @spec load_definitions(String.t()) :: :ok | {:error, String.t()}
+  def load_definitions(_path), do: :erlang.nif_error(:nif_not_loaded)
+
+  @spec lookup(String.t()) :: Lookup.t()
+  def lookup(_word), do: :erlang.nif_error(:nif_not_loaded)
+
+  @spec hooks(String.t()) :: {:error, String.t()} | {:ok, {String.t(), String.t()}}
+  def hooks(_word), do: :erlang.nif_error(:nif_not_loaded)
+
+  @spec parse_play_notation(String.t()) ::
+          {:error, String.t()} | {:ok, {number, number}, String.t()}
+  def parse_play_notation(_text), do: :erlang.nif_error(:nif_not_loaded)
+
   @spec check_dictionary(WordsGameElixir.t()) :: number
   def get_current_player_idx(_game), do: :erlang.nif_error(:nif_not_loaded)
 
+  @spec active_player_count(WordsGameElixir.t()) :: number
+  def active_player_count(%WordsGameElixir{players: players}),
+    do: Enum.count(players, fn player -> !player.resigned end)
+
   @type t :: %WordsGameElixir{
           board: String.t(),
           players: Player.t(),
           turn: number,
           tile_bag: String.t(),
-          has_word_been_played: bool
+          has_word_been_played: bool,
+          seat_offset: number,
+          time_control: TimeControl.t() | nil,
+          clock_updated_at: number | nil,
+          language: String.t(),
+          scoreless_turns: number
         }
-  defstruct [:board, :players, :turn, :tile_bag, :has_word_been_played]
+  defstruct [
+    :board,
+    :players,
+    :turn,
+    :tile_bag,
+    :has_word_been_played,
+    :time_control,
+    :clock_updated_at,
+    seat_offset: 0,
+    language: "en",
+    scoreless_turns: 0
+  ]
 
   defmodule Player do
     @type t :: %Player{
             hand: String.t(),
-            score: number
+            score: number,
+            id: String.t() | nil,
+            display_name: String.t() | nil,
+            kind: String.t(),
+            resigned: bool,
+            time_remaining_ms: number | nil
+          }
+    defstruct [:hand, :score, :id, :display_name, :time_remaining_ms, kind: "human", resigned: false]
+  end
+
+  defmodule TimeControl do
+    @type t :: %TimeControl{
+            initial_ms: number,
+            increment_ms: number,
+            overtime_penalty_per_minute: number
+          }
+    defstruct [:initial_ms, :increment_ms, :overtime_penalty_per_minute]
+  end
+
+  defmodule UnseenTiles do
+    @type t :: %UnseenTiles{
+            counts: %{String.t() => number},
+            in_bag: number
+          }
+    defstruct counts: %{}, in_bag: 0
+  end
+
+  defmodule Definition do
+    @type t :: %Definition{
+            part_of_speech: String.t() | nil,
+            definition: String.t(),
+            lexicons: [String.t()]
+          }
+    defstruct [:part_of_speech, :definition, lexicons: []]
+  end
+
+  defmodule Lookup do
+    @type t :: %Lookup{
+            word: String.t(),
+            valid: bool,
+            definitions: [Definition.t()]
+          }
+    defstruct [:word, valid: false, definitions: []]
+  end
+
+  defmodule WordQuery do
+    @type t :: %WordQuery{
+            rack: String.t() | nil,
+            pattern: String.t() | nil,
+            contains: String.t() | nil,
+            min_length: number | nil,
+            max_length: number | nil,
+            limit: number | nil
           }
-    defstruct [:hand, :score]
+    defstruct [:rack, :pattern, :contains, :min_length, :max_length, :limit]
   end
 
   defmodule Board do
+    # A character per cell, a tile is followed by the square it covers
+    # like E3 for an E on a triple word or E. for one on a plain square
     @type t :: %Board{
             cells: String.t(),
             board_dimension: number
@@ -49,9 +157,10 @@ defmodule WordsGameElixir do
   defmodule PlayWordResult do
     @type t :: %PlayWordResult{
             score: number,
-            words: [String.t()]
+            words: [String.t()],
+            breakdown: [String.t()]
           }
-    defstruct [:score, :words]
+    defstruct [:score, :words, breakdown: []]
   end
 
   def serialize(%WordsGameElixir{} = game), do: Poison.encode!(game)
@@ -66,7 +175,8 @@ defmodule WordsGameElixir do
         str,
         as: %WordsGameElixir{
           board: %Board{},
-          players: [%Player{}]
+          players: [%Player{}],
+          time_control: %TimeControl{}
         }
       )
 
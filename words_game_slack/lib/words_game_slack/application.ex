@@ -4,8 +4,11 @@ defmodule WordsGameSlack.Application do
   @moduledoc false
 
   use Application
+  require Logger
 
   def start(_type, _args) do
+    load_definitions()
+
     # List all child processes to be supervised
     children = [
       # Start the Ecto repository
@@ -22,6 +25,19 @@ defmodule WordsGameSlack.Application do
     Supervisor.start_link(children, opts)
   end
 
+  # Definitions are optional, without them `dict` only says if a word is valid
+  defp load_definitions() do
+    case System.get_env("DEFINITIONS_PATH") do
+      nil ->
+        :ok
+
+      path ->
+        with {:error, reason} <- WordsGameElixir.load_definitions(path) do
+          Logger.warn("Could not load definitions from #{path}: #{reason}")
+        end
+    end
+  end
+
   # Tell Phoenix to update the endpoint configuration
   # whenever the application is updated.
   def config_change(changed, _new, removed) do
@@ -12,6 +12,7 @@ defmodule WordsGameSlack.Commands do
       "play" <> rest -> Commands.Play.parse(rest)
       "start" <> rest -> Commands.Start.parse(rest)
       "hand" <> rest -> Commands.Hand.parse(rest)
+      "unseen" <> rest -> Commands.Unseen.parse(rest)
       "dict" <> rest -> Commands.Dict.parse(rest)
       "quit" <> rest -> Commands.Quit.parse(rest)
       "help" <> rest -> Commands.Help.parse(rest)
@@ -35,18 +36,32 @@ defmodule WordsGameSlack.Commands do
     @type t :: %Play{start: {number, number}, dir: String.t(), word: String.t()}
     defstruct [:start, :dir, :word]
 
-    # Etc /play 7,7 right ACTOR
+    # Etc /play ACTOR 7,7 right
     #           ^ parse this section
     @regex ~r/^(\w+) (\d+)(?:,|:) ?(\d+) (right|down)/i
+    # Or in Scrabble notation, /play ACTOR 8H
+    @notation_regex ~r/^(\w+) (\w+)$/
 
     @spec parse(binary) :: {:error, String.t()} | {:ok, WordsGameSlack.Commands.Play.t()}
     def parse(text) do
-      case Regex.run(@regex, String.trim(text)) do
-        nil ->
-          {:error, "command is invalid"}
+      text = String.trim(text)
 
-        [_, word, start_x, start_y, dir] ->
+      case {Regex.run(@regex, text), Regex.run(@notation_regex, text)} do
+        {[_, word, start_x, start_y, dir], _} ->
           {:ok, create_play_command(start_x, start_y, dir, word)}
+
+        {nil, [_, word, notation]} ->
+          parse_notation(word, notation)
+
+        _ ->
+          {:error, "command is invalid"}
+      end
+    end
+
+    defp parse_notation(word, notation) do
+      case WordsGameElixir.parse_play_notation(notation) do
+        {:ok, start, dir} -> {:ok, %Play{start: start, dir: dir, word: String.upcase(word)}}
+        {:error, msg} -> {:error, msg}
       end
     end
 
@@ -97,12 +112,24 @@ defmodule WordsGameSlack.Commands do
     def parse(_), do: {:ok, %Hand{}}
   end
 
+  defmodule Unseen do
+    @type t :: %Unseen{}
+    defstruct []
+
+    @spec parse(any) :: {:ok, WordsGameSlack.Commands.Unseen.t()}
+    def parse(_), do: {:ok, %Unseen{}}
+  end
+
   defmodule Dict do
-    @type t :: %Dict{word: String.t()}
+    @type t :: %Dict{word: String.t(), mode: :check | :make | :match | :hooks}
     @enforce_keys [:word]
-    defstruct [:word]
+    defstruct [:word, mode: :check]
 
+    # etc /dict make RETAINS?, /dict match ?A??R, /dict hooks ARE
     @spec parse(any) :: {:error, String.t()} | {:ok, WordsGameSlack.Commands.Dict.t()}
+    def parse(" make " <> letters), do: {:ok, %Dict{word: String.trim(letters), mode: :make}}
+    def parse(" match " <> pattern), do: {:ok, %Dict{word: String.trim(pattern), mode: :match}}
+    def parse(" hooks " <> word), do: {:ok, %Dict{word: String.trim(word), mode: :hooks}}
     def parse(" " <> word), do: {:ok, %Dict{word: word}}
     def parse(_), do: {:error, "Please specify a word"}
   end
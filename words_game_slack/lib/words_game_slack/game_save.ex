@@ -29,8 +29,8 @@ defmodule WordsGameSlack.GameSave do
 
   @spec create_new_game([any], any, any) :: any
   def create_new_game(players, team_id, channel_id) do
-    # Create the game
-    words_game = WordsGameElixir.new_game(length(players))
+    # Create the game, the players keep their slack id and name inside of it
+    words_game = WordsGameElixir.new_game_with_players(players)
 
     # Create players in the order they are passed in
     players =
@@ -14,6 +14,18 @@ defmodule WordsGameSlack.Slack do
   @spec render_tiles(String.t()) :: String.t()
   def render_tiles(str), do: Slack.Renderer.render_tiles(str)
 
+  @spec render_unseen_tiles(WordsGameElixir.UnseenTiles.t()) :: String.t()
+  def render_unseen_tiles(unseen), do: Slack.Renderer.render_unseen_tiles(unseen)
+
+  @spec render_lookup(String.t(), WordsGameElixir.Lookup.t()) :: String.t()
+  def render_lookup(word, lookup), do: Slack.Renderer.render_lookup(word, lookup)
+
+  @spec render_found_words([String.t()]) :: String.t()
+  def render_found_words(words), do: Slack.Renderer.render_found_words(words)
+
+  @spec render_hooks(String.t(), String.t(), String.t()) :: String.t()
+  def render_hooks(word, front, back), do: Slack.Renderer.render_hooks(word, front, back)
+
   @spec render_board(WordsGameSlack.GameSave.Game.t()) :: {:ok, String.t()} | {:error, String.t()}
   def render_board(game) do
     with {:ok, words_game} <- WordsGameElixir.deserialize(game) do
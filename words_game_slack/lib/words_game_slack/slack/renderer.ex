@@ -1,6 +1,6 @@
 defmodule WordsGameSlack.Slack.Renderer do
   alias WordsGameSlack.GameSave
-  alias WordsGameElixir.{Player, Board, PlayWordResult}
+  alias WordsGameElixir.{Player, Board, PlayWordResult, UnseenTiles, Lookup, Definition}
 
   @command_name Application.get_env(:words_game_slack, :command_name)
 
@@ -33,10 +33,10 @@ defmodule WordsGameSlack.Slack.Renderer do
 
     top_row = ":scrabble-board:#{top_row_coords}"
 
+    # A tile is followed by the square it covers, like E3 or E.
     cell_render =
-      cells
-      |> String.codepoints()
-      |> Enum.map(&render_tile/1)
+      Regex.scan(~r/[^.23@#+][.23@#+]|./u, cells)
+      |> Enum.map(fn [cell] -> cell |> String.first() |> render_tile() end)
       |> Enum.chunk_every(dim)
       |> Enum.map(&Enum.join/1)
       |> Enum.with_index()
@@ -47,7 +47,13 @@ defmodule WordsGameSlack.Slack.Renderer do
   end
 
   @spec render_player({Player.t(), GameSave.Player.t()}, bool) :: String.t()
-  def render_player({%Player{score: score}, %GameSave.Player{user_name: name}}, is_turn) do
+  def render_player(
+        {%Player{score: score, display_name: display_name}, %GameSave.Player{user_name: user_name}},
+        is_turn
+      ) do
+    # Games created before players were stored in the game have no display name
+    name = display_name || user_name
+
     "#{name}: #{score} points #{if is_turn, do: "[current turn]", else: ""}"
   end
 
@@ -72,6 +78,47 @@ defmodule WordsGameSlack.Slack.Renderer do
     ":#{tile_name}:"
   end
 
+  @spec render_unseen_tiles(UnseenTiles.t()) :: String.t()
+  def render_unseen_tiles(%UnseenTiles{counts: counts, in_bag: in_bag}) do
+    tiles =
+      counts
+      |> Enum.sort()
+      |> Enum.map(fn
+        {"?", count} -> "blank x#{count}"
+        {letter, count} -> "#{render_tile(letter)} x#{count}"
+      end)
+      |> Enum.join("  ")
+
+    "Unseen tiles (#{in_bag} in the bag):\n#{tiles}"
+  end
+
+  @spec render_lookup(String.t(), Lookup.t()) :: String.t()
+  def render_lookup(word, %Lookup{valid: valid, definitions: definitions}) do
+    rendered_definitions =
+      Enum.map(definitions, fn %Definition{} = definition ->
+        part_of_speech = if definition.part_of_speech, do: "_#{definition.part_of_speech}_ ", else: ""
+        lexicons = if definition.lexicons == [], do: "", else: " (#{Enum.join(definition.lexicons, ", ")})"
+
+        ">#{part_of_speech}#{definition.definition}#{lexicons}"
+      end)
+
+    Enum.join(["#{word} is #{if valid, do: "valid", else: "not valid"}" | rendered_definitions], "\n")
+  end
+
+  @spec render_found_words([String.t()]) :: String.t()
+  def render_found_words([]), do: "No words found"
+  def render_found_words(words), do: Enum.join(words, ", ")
+
+  @spec render_hooks(String.t(), String.t(), String.t()) :: String.t()
+  def render_hooks(word, front, back) do
+    render_letters = fn
+      "" -> "none"
+      letters -> letters |> String.graphemes() |> Enum.join(" ")
+    end
+
+    "Before #{word}: #{render_letters.(front)}\nAfter #{word}: #{render_letters.(back)}"
+  end
+
   defp render_coordinate_tile(num) do
     ":#{rem(num, 10) |> num_to_word}:"
   end
@@ -101,8 +148,13 @@ Here is how you use the Words with Coworkers bot
 in game:
 >`#{@command_name} board` - Show the state of the board
 >`#{@command_name} hand` - Shows you your hand
+>`#{@command_name} unseen` - Shows the tiles you haven't seen yet
 >`#{@command_name} play <word> <x>:<y> <right|down>` - To play a word
+>`#{@command_name} play <word> <8H|H8>` - The same in Scrabble notation, row first to go right
 >`#{@command_name} dict <word>` - To check if a word is valid
+>`#{@command_name} dict make <letters>` - Words you can make from some letters, `?` for a blank
+>`#{@command_name} dict match <pattern>` - Words that fit a pattern like `?A??R`
+>`#{@command_name} dict hooks <word>` - Letters that can go before or after a word
 >`#{@command_name} quit` - Quit the current game
     "
   end
@@ -110,7 +162,7 @@ in game:
   def render_play_word_result(%PlayWordResult{} = result, player_name) do
     ~s"
 #{player_name} played the words:
-#{result.words |> Enum.join("\n")}
+#{result.breakdown |> Enum.join("\n")}
 For #{result.score} points
     "
   end
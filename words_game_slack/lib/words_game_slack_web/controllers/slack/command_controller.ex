@@ -3,6 +3,9 @@ defmodule WordsGameSlackWeb.Slack.CommandController do
   require Logger
   alias WordsGameSlack.{Commands, GameSave}
 
+  # Slack messages get hard to read with more words than this
+  @found_words_limit 40
+
   @expected_keys [
     "channel_id",
     "command",
@@ -83,6 +86,20 @@ defmodule WordsGameSlackWeb.Slack.CommandController do
     end
   end
 
+  defp execute_command(%Commands.Unseen{}, %{"user_id" => user_id} = params) do
+    with {:ok, game_save} <- game_from_params(params),
+         {:ok, words_game} <- WordsGameElixir.deserialize(game_save) do
+      player_idx = GameSave.player_idx_in_game(game_save, user_id)
+
+      unseen_render =
+        words_game
+        |> WordsGameElixir.unseen_tiles(player_idx)
+        |> WordsGameSlack.Slack.render_unseen_tiles()
+
+      {:ok, :ephemeral, unseen_render}
+    end
+  end
+
   defp execute_command(
          %Commands.Play{start: start, dir: dir, word: word},
          %{"user_id" => user_id} = params
@@ -91,7 +108,9 @@ defmodule WordsGameSlackWeb.Slack.CommandController do
          {:ok, words_game} <- WordsGameElixir.deserialize(game_save),
          :ok <- ensure_player_turn(words_game, GameSave.player_idx_in_game(game_save, user_id)),
          {:ok, play_word_result, new_words_game} <-
-           words_game |> WordsGameElixir.play_word(start, dir, word),
+           words_game
+           |> WordsGameElixir.tick(System.os_time(:millisecond))
+           |> WordsGameElixir.play_word(start, dir, word),
          {:ok, new_game_save} <- GameSave.update(game_save, new_words_game),
          {:ok, game_render} <- WordsGameSlack.Slack.render_game(new_game_save) do
       result_render =
@@ -101,16 +120,48 @@ defmodule WordsGameSlackWeb.Slack.CommandController do
     end
   end
 
+  defp execute_command(%Commands.Dict{mode: :make, word: letters}, _) do
+    query = %WordsGameElixir.WordQuery{rack: letters, min_length: 2, limit: @found_words_limit}
+
+    with {:ok, words} <- WordsGameElixir.find_words(query) do
+      {:ok, :ephemeral, WordsGameSlack.Slack.render_found_words(words)}
+    end
+  end
+
+  defp execute_command(%Commands.Dict{mode: :match, word: pattern}, _) do
+    query = %WordsGameElixir.WordQuery{pattern: pattern, limit: @found_words_limit}
+
+    with {:ok, words} <- WordsGameElixir.find_words(query) do
+      {:ok, :ephemeral, WordsGameSlack.Slack.render_found_words(words)}
+    end
+  end
+
+  defp execute_command(%Commands.Dict{mode: :hooks, word: word}, _) do
+    with {:ok, {front, back}} <- WordsGameElixir.hooks(word) do
+      {:ok, :ephemeral, WordsGameSlack.Slack.render_hooks(String.upcase(word), front, back)}
+    end
+  end
+
   defp execute_command(%Commands.Dict{word: word}, _) do
-    is_valid = word |> String.upcase() |> WordsGameElixir.check_dictionary()
+    lookup = WordsGameElixir.lookup(word)
 
-    {:ok, :ephemeral, "#{word} is #{if is_valid, do: "valid", else: "not valid"}"}
+    {:ok, :ephemeral, WordsGameSlack.Slack.render_lookup(word, lookup)}
   end
 
-  defp execute_command(%Commands.Quit{}, params) do
-    with {:ok, game} <- game_from_params(params) do
-      GameSave.delete(game)
-      {:ok, "Game has ended"}
+  defp execute_command(%Commands.Quit{}, %{"user_id" => user_id} = params) do
+    with {:ok, game_save} <- game_from_params(params),
+         {:ok, words_game} <- WordsGameElixir.deserialize(game_save),
+         {:ok, new_words_game} <-
+           WordsGameElixir.resign(words_game, GameSave.player_idx_in_game(game_save, user_id)) do
+      # The game only keeps going while there is someone left to play against
+      if WordsGameElixir.active_player_count(new_words_game) < 2 do
+        GameSave.delete(game_save)
+        {:ok, "Game has ended"}
+      else
+        with {:ok, _} <- GameSave.update(game_save, new_words_game) do
+          {:ok, "#{params["user_name"]} has left the game"}
+        end
+      end
     end
   end
 
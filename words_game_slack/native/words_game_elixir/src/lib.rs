@@ -3,6 +3,8 @@ extern crate words_game;
 extern crate serde;
 extern crate serde_rustler;
 
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::fmt;
 use rustler::{Encoder, Env, Error, Term};
 use serde::{Serialize, Deserialize};
@@ -21,9 +23,20 @@ rustler::rustler_export_nifs!(
     "Elixir.WordsGameElixir",
     [
         ("new_game", 1, new_game),
+        ("new_game_with_players", 1, new_game_with_players),
         ("play_word", 4, play_word),
         ("check_dictionary", 1, check_dictionary),
         ("get_current_player_idx", 1, get_current_player_idx),
+        ("add_player", 1, add_player),
+        ("resign", 2, resign),
+        ("tick", 2, tick),
+        ("unseen_tiles", 2, unseen_tiles),
+        ("view_for", 2, view_for),
+        ("find_words", 1, find_words),
+        ("hooks", 1, hooks),
+        ("load_definitions", 1, load_definitions),
+        ("lookup", 1, lookup),
+        ("parse_play_notation", 1, parse_play_notation),
     ],
     None
 );
@@ -33,11 +46,16 @@ rustler::rustler_export_nifs!(
 pub struct PlayWordResult {
     score: u32,
     words: Vec<String>,
+    breakdown: Vec<String>,
 }
 
 impl From<words_game::PlayWordResult> for PlayWordResult {
     fn from(other: words_game::PlayWordResult) -> Self {
-        Self { score: other.score, words: other.words}
+        Self {
+            score: other.score,
+            words: other.words,
+            breakdown: other.breakdown.iter().map(ToString::to_string).collect()
+        }
     }
 }
 
@@ -46,6 +64,11 @@ impl From<words_game::PlayWordResult> for PlayWordResult {
 struct Player {
     hand: String,
     score: u32,
+    id: Option<String>,
+    display_name: Option<String>,
+    kind: String,
+    resigned: bool,
+    time_remaining_ms: Option<i64>,
 }
 
 impl From<words_game::Player> for Player {
@@ -54,18 +77,46 @@ impl From<words_game::Player> for Player {
                              .map(|x| Into::<char>::into(*x))
                              .collect();
 
-        Self {score: other.score, hand }
+        let kind = match other.kind {
+            words_game::PlayerKind::Human => "human",
+            words_game::PlayerKind::Bot => "bot",
+        };
+
+        Self {
+            score: other.score,
+            hand,
+            id: other.id,
+            display_name: other.display_name,
+            kind: kind.to_string(),
+            resigned: other.resigned,
+            time_remaining_ms: other.time_remaining_ms,
+        }
     }
 }
 
-impl From<Player> for words_game::Player {
-    fn from(other: Player) -> words_game::Player {
+impl TryFrom<Player> for words_game::Player {
+    type Error = Box<words_game::Error>;
+
+    fn try_from(other: Player) -> words_game::Result<words_game::Player> {
         let hand = other.hand
                     .chars()
-                    .map(Into::<words_game::Tile>::into)
-                    .collect();
+                    .map(words_game::Tile::from_char)
+                    .collect::<words_game::Result<_>>()?;
+
+        let kind = match &other.kind[..] {
+            "bot" => words_game::PlayerKind::Bot,
+            _ => words_game::PlayerKind::Human,
+        };
 
-        words_game::Player { hand, score: other.score }
+        Ok(words_game::Player {
+            hand,
+            score: other.score,
+            id: other.id,
+            display_name: other.display_name,
+            kind,
+            resigned: other.resigned,
+            time_remaining_ms: other.time_remaining_ms,
+        })
     }
 }
 
@@ -80,17 +131,117 @@ impl From<words_game::Board> for Board {
     fn from(other: words_game::Board) -> Self {
         Self {
             board_dimension: words_game::BOARD_SIZE,
-            cells: other.cells.iter().map(Into::<char>::into).collect()
+            cells: other.encode_cells()
+        }
+    }
+}
+
+impl TryFrom<Board> for words_game::Board {
+    type Error = Box<words_game::Error>;
+
+    fn try_from(other: Board) -> words_game::Result<Self> {
+        Self::decode_cells(&other.cells)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename = "Elixir.WordsGameElixir.TimeControl")]
+struct TimeControl {
+    initial_ms: u64,
+    increment_ms: u64,
+    overtime_penalty_per_minute: u32,
+}
+
+impl From<words_game::TimeControl> for TimeControl {
+    fn from(other: words_game::TimeControl) -> Self {
+        Self {
+            initial_ms: other.initial_ms,
+            increment_ms: other.increment_ms,
+            overtime_penalty_per_minute: other.overtime_penalty_per_minute,
+        }
+    }
+}
+
+impl From<TimeControl> for words_game::TimeControl {
+    fn from(other: TimeControl) -> Self {
+        words_game::TimeControl::new(
+            other.initial_ms,
+            other.increment_ms,
+            other.overtime_penalty_per_minute
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename = "Elixir.WordsGameElixir.UnseenTiles")]
+struct UnseenTiles {
+    counts: BTreeMap<String, u32>,
+    in_bag: usize,
+}
+
+impl From<words_game::UnseenTiles> for UnseenTiles {
+    fn from(other: words_game::UnseenTiles) -> Self {
+        Self {
+            counts: other.counts.into_iter().map(|(c, count)| (c.to_string(), count)).collect(),
+            in_bag: other.in_bag,
         }
     }
 }
 
-impl From<Board> for words_game::Board {
-    fn from(other: Board) -> Self {
+#[derive(Debug, Serialize)]
+#[serde(rename = "Elixir.WordsGameElixir.Definition")]
+struct Definition {
+    part_of_speech: Option<String>,
+    definition: String,
+    lexicons: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename = "Elixir.WordsGameElixir.Lookup")]
+struct Lookup {
+    word: String,
+    valid: bool,
+    definitions: Vec<Definition>,
+}
+
+impl From<words_game::definitions::Lookup> for Lookup {
+    fn from(other: words_game::definitions::Lookup) -> Self {
         Self {
-            cells: other.cells.chars()
-                .map(Into::<words_game::BoardCell>::into)
-                .collect()
+            word: other.word,
+            valid: other.valid,
+            definitions: other
+                .definitions
+                .into_iter()
+                .map(|definition| Definition {
+                    part_of_speech: definition.part_of_speech,
+                    definition: definition.definition,
+                    lexicons: definition.lexicons,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename = "Elixir.WordsGameElixir.WordQuery")]
+struct WordQuery {
+    rack: Option<String>,
+    pattern: Option<String>,
+    contains: Option<String>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    limit: Option<usize>,
+}
+
+impl From<WordQuery> for words_game::finder::WordQuery {
+    fn from(other: WordQuery) -> Self {
+        Self {
+            rack: other.rack,
+            pattern: other.pattern,
+            contains: other.contains,
+            min_length: other.min_length,
+            max_length: other.max_length,
+            limit: other.limit,
         }
     }
 }
@@ -102,7 +253,13 @@ struct Game {
     players: Vec<Player>,
     turn: u32,
     tile_bag: String,
-    has_word_been_played: bool
+    has_word_been_played: bool,
+    seat_offset: u32,
+    time_control: Option<TimeControl>,
+    clock_updated_at: Option<u64>,
+    /// The language code, e.g. "es"
+    language: String,
+    scoreless_turns: u32
 }
 
 impl From<words_game::Game> for Game {
@@ -112,22 +269,40 @@ impl From<words_game::Game> for Game {
             players: other.players.into_iter().map(Into::into).collect(),
             turn: other.turn,
             tile_bag: other.tile_bag.tiles.into_iter().map(Into::<char>::into).collect(),
-            has_word_been_played: other.has_word_been_played
+            has_word_been_played: other.has_word_been_played,
+            seat_offset: other.seat_offset,
+            time_control: other.time_control.map(Into::into),
+            clock_updated_at: other.clock_updated_at,
+            language: other.language.code().to_string(),
+            scoreless_turns: other.scoreless_turns
         }
     }
 }
 
-impl From<Game> for words_game::Game {
-    fn from(other: Game) -> words_game::Game {
-        Self {
-            board: Into::into(other.board),
-            players: other.players.into_iter().map(Into::into).collect(),
+impl TryFrom<Game> for words_game::Game {
+    type Error = Box<words_game::Error>;
+
+    fn try_from(other: Game) -> words_game::Result<words_game::Game> {
+        Ok(Self {
+            board: words_game::Board::try_from(other.board)?,
+            players: other.players
+                          .into_iter()
+                          .map(words_game::Player::try_from)
+                          .collect::<words_game::Result<_>>()?,
             turn: other.turn,
-            tile_bag: words_game::TileBag {
-                tiles: other.tile_bag.chars().map(Into::<words_game::Tile>::into).collect()
-            },
-            has_word_been_played: other.has_word_been_played
-        }
+            tile_bag: words_game::TileBag::from_tiles(
+                other.tile_bag
+                     .chars()
+                     .map(words_game::Tile::from_char)
+                     .collect::<words_game::Result<_>>()?
+            ),
+            has_word_been_played: other.has_word_been_played,
+            seat_offset: other.seat_offset,
+            time_control: other.time_control.map(Into::into),
+            clock_updated_at: other.clock_updated_at,
+            language: other.language.parse()?,
+            scoreless_turns: other.scoreless_turns
+        })
     }
 }
 
@@ -152,8 +327,21 @@ pub fn new_game<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error>
     to_term(env, game).map_err(Into::into)
 }
 
+pub fn new_game_with_players<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let players: Vec<(String, String)> = args[0].decode()?;
+
+    let seats: Vec<(&str, &str, words_game::PlayerKind)> = players
+        .iter()
+        .map(|(id, name)| (&id[..], &name[..], words_game::PlayerKind::Human))
+        .collect();
+
+    let game: Game = words_game::Game::with_players(&seats).into();
+
+    to_term(env, game).map_err(Into::into)
+}
+
 pub fn play_word<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
-    let mut game: words_game::Game = from_term::<Game>(args[0])?.into();
+    let mut game = handle_result!(words_game::Game::try_from(from_term::<Game>(args[0])?), env);
     let (start_x, start_y): (i32, i32) = args[1].decode()?;
     let direction: String = args[2].decode()?;
     let word: String = args[3].decode()?;
@@ -184,7 +372,98 @@ pub fn check_dictionary<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>,
 }
 
 pub fn get_current_player_idx<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
-    let game: words_game::Game = from_term::<Game>(args[0])?.into();
+    let game = handle_result!(words_game::Game::try_from(from_term::<Game>(args[0])?), env);
 
     Ok(game.get_current_player_idx().encode(env))
 }
+
+pub fn add_player<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let mut game = handle_result!(words_game::Game::try_from(from_term::<Game>(args[0])?), env);
+
+    let player_idx = handle_result!(game.add_player(), env);
+
+    Ok((
+        atoms::ok(),
+        player_idx,
+        to_term(env, Game::from(game)).map_err(Into::<Error>::into)?
+    ).encode(env))
+}
+
+pub fn resign<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let mut game = handle_result!(words_game::Game::try_from(from_term::<Game>(args[0])?), env);
+    let player_idx: usize = args[1].decode()?;
+
+    handle_result!(game.resign(player_idx), env);
+
+    Ok((
+        atoms::ok(),
+        to_term(env, Game::from(game)).map_err(Into::<Error>::into)?
+    ).encode(env))
+}
+
+pub fn tick<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let mut game = handle_result!(words_game::Game::try_from(from_term::<Game>(args[0])?), env);
+    let now_ms: u64 = args[1].decode()?;
+
+    game.tick(now_ms);
+
+    to_term(env, Game::from(game)).map_err(Into::into)
+}
+
+pub fn unseen_tiles<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let game = handle_result!(words_game::Game::try_from(from_term::<Game>(args[0])?), env);
+    let player_idx: usize = args[1].decode()?;
+
+    to_term(env, UnseenTiles::from(game.unseen_tiles(player_idx))).map_err(Into::into)
+}
+
+pub fn view_for<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let game = handle_result!(words_game::Game::try_from(from_term::<Game>(args[0])?), env);
+    let player_idx: usize = args[1].decode()?;
+
+    let view = handle_result!(game.view_for(player_idx), env);
+
+    Ok((atoms::ok(), view.serialize()).encode(env))
+}
+
+pub fn find_words<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let query: words_game::finder::WordQuery = from_term::<WordQuery>(args[0])?.into();
+
+    let words = handle_result!(words_game::finder::find_words(&query), env);
+
+    Ok((atoms::ok(), words).encode(env))
+}
+
+pub fn hooks<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let word: String = args[0].decode()?;
+
+    let hooks = handle_result!(words_game::finder::hooks(&word), env);
+    let front: String = hooks.front.into_iter().collect();
+    let back: String = hooks.back.into_iter().collect();
+
+    Ok((atoms::ok(), (front, back)).encode(env))
+}
+
+pub fn parse_play_notation<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let text: String = args[0].decode()?;
+
+    let (start, dir) = handle_result!(words_game::coordinates::parse_play_notation(&text), env);
+    let dir = if dir == words_game::Direction::right() { "right" } else { "down" };
+
+    Ok((atoms::ok(), (start.x, start.y), dir).encode(env))
+}
+
+pub fn load_definitions<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let path: String = args[0].decode()?;
+
+    let definitions = handle_result!(words_game::definitions::Definitions::from_file(&path), env);
+    words_game::definitions::install(definitions);
+
+    Ok(atoms::ok().encode(env))
+}
+
+pub fn lookup<'a>(env: Env<'a>, args: &[Term<'a>]) -> Result<Term<'a>, Error> {
+    let word: String = args[0].decode()?;
+
+    to_term(env, Lookup::from(words_game::definitions::lookup(&word))).map_err(Into::into)
+}